@@ -0,0 +1,507 @@
+use crate::math::scalar_module::cast;
+use crate::math::shape_module::Aabb;
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        Dynamic AABB Tree
+// -----------------------------------------
+
+/// Handle to a proxy stored in a `DynamicAabbTree`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ProxyId(pub usize);
+
+#[derive(Debug, Clone)]
+struct Node<T, D>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    aabb: Aabb<T>,
+    parent: Option<usize>,
+    /// `None` for leaves.
+    children: Option<[usize; 2]>,
+    height: usize,
+    /// Only leaves carry data, so a node is a live proxy when this is `Some`.
+    data: Option<D>,
+    moved: bool,
+}
+
+/// A dynamic bounding volume hierarchy for broadphase collision detection.
+///
+/// Every proxy is stored with a fattened box so small motions do not touch
+/// the tree; proxies are inserted using the surface area heuristic and the
+/// tree is kept balanced with rotations. The proxies created or reinserted
+/// since the last call to `update_pairs` form the move buffer used for
+/// incremental pair finding.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::aabb_tree_module::DynamicAabbTree;
+/// use vectorlib::math::shape_module::Aabb;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let mut tree: DynamicAabbTree<f64, &str> = DynamicAabbTree::new(0.1);
+/// let a = tree.create_proxy(Aabb::new(Vector2d::new(0.0, 0.0), Vector2d::new(1.0, 1.0)), "a");
+/// let b = tree.create_proxy(Aabb::new(Vector2d::new(0.5, 0.5), Vector2d::new(2.0, 2.0)), "b");
+/// let mut pairs = Vec::new();
+/// tree.update_pairs(|x, y| pairs.push((x, y)));
+/// assert_eq!(pairs, vec![(a, b)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DynamicAabbTree<T, D>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    margin: T,
+    /// Scale applied to the displacement given to `move_proxy` when
+    /// predicting where a proxy is heading.
+    pub displacement_multiplier: T,
+    nodes: Vec<Node<T, D>>,
+    free: Vec<usize>,
+    root: Option<usize>,
+    move_buffer: Vec<usize>,
+    proxy_count: usize,
+}
+
+impl<T, D> DynamicAabbTree<T, D>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create an empty tree fattening every box by `margin`.
+    pub fn new(margin: T) -> Self {
+        Self {
+            margin,
+            displacement_multiplier: cast(2.0),
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            move_buffer: Vec::new(),
+            proxy_count: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.proxy_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.proxy_count == 0
+    }
+
+    /// Height of the tree, zero when it is empty or a single leaf.
+    pub fn height(&self) -> usize {
+        self.root.map_or(0, |root| self.nodes[root].height)
+    }
+
+    /// The fattened box of a proxy.
+    pub fn fat_aabb(&self, proxy: ProxyId) -> Option<Aabb<T>> {
+        self.live(proxy).map(|node| node.aabb)
+    }
+
+    pub fn user_data(&self, proxy: ProxyId) -> Option<&D> {
+        self.live(proxy).and_then(|node| node.data.as_ref())
+    }
+
+    pub fn user_data_mut(&mut self, proxy: ProxyId) -> Option<&mut D> {
+        self.nodes
+            .get_mut(proxy.0)
+            .and_then(|node| node.data.as_mut())
+    }
+
+    /// Inserts a proxy for `aabb` and adds it to the move buffer.
+    pub fn create_proxy(&mut self, aabb: Aabb<T>, data: D) -> ProxyId {
+        let leaf = self.allocate(Node {
+            aabb: aabb.inflate(self.margin),
+            parent: None,
+            children: None,
+            height: 0,
+            data: Some(data),
+            moved: false,
+        });
+        self.insert_leaf(leaf);
+        self.mark_moved(leaf);
+        self.proxy_count += 1;
+        ProxyId(leaf)
+    }
+
+    /// Removes a proxy, returning its data.
+    pub fn destroy_proxy(&mut self, proxy: ProxyId) -> Option<D> {
+        self.live(proxy)?;
+        self.remove_leaf(proxy.0);
+        self.move_buffer.retain(|&node| node != proxy.0);
+        self.proxy_count -= 1;
+        let node = &mut self.nodes[proxy.0];
+        node.moved = false;
+        self.free.push(proxy.0);
+        node.data.take()
+    }
+
+    /// Moves a proxy to `aabb`. Nothing happens while `aabb` stays inside the
+    /// fattened box; otherwise the proxy is reinserted with a box fattened by
+    /// the margin and stretched along `displacement`.
+    ///
+    /// # Returns
+    ///
+    /// `true` when the proxy was reinserted (and added to the move buffer).
+    pub fn move_proxy(&mut self, proxy: ProxyId, aabb: Aabb<T>, displacement: Vector2d<T>) -> bool {
+        let Some(node) = self.live(proxy) else {
+            return false;
+        };
+        let mut fat = aabb.inflate(self.margin);
+        let stretch = displacement * self.displacement_multiplier;
+        fat.min = Vector2d::new(
+            fat.min.x + stretch.x.min(T::zero()),
+            fat.min.y + stretch.y.min(T::zero()),
+        );
+        fat.max = Vector2d::new(
+            fat.max.x + stretch.x.max(T::zero()),
+            fat.max.y + stretch.y.max(T::zero()),
+        );
+
+        // Keep the old box unless it no longer contains the object or has
+        // become much larger than needed.
+        let huge = fat.inflate(self.margin * cast(4.0));
+        if node.aabb.contains_aabb(&aabb) && huge.contains_aabb(&node.aabb) {
+            return false;
+        }
+        self.remove_leaf(proxy.0);
+        self.nodes[proxy.0].aabb = fat;
+        self.insert_leaf(proxy.0);
+        self.mark_moved(proxy.0);
+        true
+    }
+
+    /// Calls `callback` for every proxy whose fattened box overlaps `area`
+    /// until it returns `false`.
+    pub fn query<F>(&self, area: &Aabb<T>, mut callback: F)
+    where
+        F: FnMut(ProxyId) -> bool,
+    {
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.aabb.overlaps(area) {
+                continue;
+            }
+            match node.children {
+                Some(children) => stack.extend(children),
+                None => {
+                    if !callback(ProxyId(index)) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Every proxy whose fattened box overlaps `area`.
+    pub fn query_aabb(&self, area: &Aabb<T>) -> Vec<ProxyId> {
+        let mut found = Vec::new();
+        self.query(area, |proxy| {
+            found.push(proxy);
+            true
+        });
+        found
+    }
+
+    /// Casts the segment `origin + translation * t`, `t` in `[0, 1]`, through
+    /// the tree.
+    ///
+    /// `callback` receives every proxy whose fattened box the ray reaches,
+    /// together with the current maximum fraction, and returns the new one:
+    /// `0` stops the cast, a smaller positive value clips the ray (for example
+    /// to the exact hit against the proxy's shape) and any other value leaves
+    /// it unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::aabb_tree_module::DynamicAabbTree;
+    /// use vectorlib::math::shape_module::Aabb;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let mut tree: DynamicAabbTree<f64, ()> = DynamicAabbTree::new(0.0);
+    /// let near = tree.create_proxy(Aabb::new(Vector2d::new(2.0, -1.0), Vector2d::new(3.0, 1.0)), ());
+    /// tree.create_proxy(Aabb::new(Vector2d::new(6.0, -1.0), Vector2d::new(7.0, 1.0)), ());
+    ///
+    /// // Clip at the entry point of each box so only the closest one is kept.
+    /// let mut closest = None;
+    /// tree.ray_cast(Vector2d::new(0.0, 0.0), Vector2d::new(10.0, 0.0), |proxy, _| {
+    ///     let entry = if proxy == near { 0.2 } else { 0.6 };
+    ///     closest = Some(proxy);
+    ///     entry
+    /// });
+    /// assert_eq!(closest, Some(near));
+    /// ```
+    pub fn ray_cast<F>(&self, origin: Vector2d<T>, translation: Vector2d<T>, mut callback: F)
+    where
+        F: FnMut(ProxyId, T) -> T,
+    {
+        let mut max_fraction = T::one();
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !ray_hits_box(origin, translation, max_fraction, &node.aabb) {
+                continue;
+            }
+            match node.children {
+                Some(children) => stack.extend(children),
+                None => {
+                    let value = callback(ProxyId(index), max_fraction);
+                    if value == T::zero() {
+                        return;
+                    }
+                    if value > T::zero() && value < max_fraction {
+                        max_fraction = value;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reports every overlapping pair involving a proxy from the move buffer,
+    /// once, as `(smaller id, larger id)`, then clears the buffer.
+    pub fn update_pairs<F>(&mut self, mut callback: F)
+    where
+        F: FnMut(ProxyId, ProxyId),
+    {
+        let moved = std::mem::take(&mut self.move_buffer);
+        for &index in &moved {
+            let aabb = self.nodes[index].aabb;
+            self.query(&aabb, |other| {
+                // Pairs of two moved proxies are reported from the smaller id.
+                if other.0 != index && !(self.nodes[other.0].moved && other.0 < index) {
+                    callback(ProxyId(index.min(other.0)), ProxyId(index.max(other.0)));
+                }
+                true
+            });
+        }
+        for index in moved {
+            self.nodes[index].moved = false;
+        }
+    }
+
+    /// Every pair of proxies with overlapping fattened boxes, as
+    /// `(smaller id, larger id)`.
+    pub fn overlap_pairs(&self) -> Vec<(ProxyId, ProxyId)> {
+        let mut pairs = Vec::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            if node.data.is_none() {
+                continue;
+            }
+            self.query(&node.aabb, |other| {
+                if other.0 > index {
+                    pairs.push((ProxyId(index), other));
+                }
+                true
+            });
+        }
+        pairs
+    }
+
+    fn live(&self, proxy: ProxyId) -> Option<&Node<T, D>> {
+        self.nodes.get(proxy.0).filter(|node| node.data.is_some())
+    }
+
+    fn mark_moved(&mut self, leaf: usize) {
+        if !self.nodes[leaf].moved {
+            self.nodes[leaf].moved = true;
+            self.move_buffer.push(leaf);
+        }
+    }
+
+    fn allocate(&mut self, node: Node<T, D>) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn release(&mut self, index: usize) {
+        let node = &mut self.nodes[index];
+        node.parent = None;
+        node.children = None;
+        self.free.push(index);
+    }
+
+    fn insert_leaf(&mut self, leaf: usize) {
+        let Some(root) = self.root else {
+            self.root = Some(leaf);
+            self.nodes[leaf].parent = None;
+            return;
+        };
+
+        // Walk down picking the child that increases the perimeter least.
+        let leaf_aabb = self.nodes[leaf].aabb;
+        let mut index = root;
+        while let Some([child1, child2]) = self.nodes[index].children {
+            let area = self.nodes[index].aabb.perimeter();
+            let combined_area = self.nodes[index].aabb.union(&leaf_aabb).perimeter();
+            let two: T = cast(2.0);
+            let cost = combined_area * two;
+            let inheritance_cost = (combined_area - area) * two;
+            let descend_cost = |child: usize| {
+                let node = &self.nodes[child];
+                let enlarged = node.aabb.union(&leaf_aabb).perimeter();
+                match node.children {
+                    None => enlarged + inheritance_cost,
+                    Some(_) => enlarged - node.aabb.perimeter() + inheritance_cost,
+                }
+            };
+            let (cost1, cost2) = (descend_cost(child1), descend_cost(child2));
+            if cost < cost1 && cost < cost2 {
+                break;
+            }
+            index = if cost1 < cost2 { child1 } else { child2 };
+        }
+
+        let sibling = index;
+        let old_parent = self.nodes[sibling].parent;
+        let new_parent = self.allocate(Node {
+            aabb: leaf_aabb.union(&self.nodes[sibling].aabb),
+            parent: old_parent,
+            children: Some([sibling, leaf]),
+            height: self.nodes[sibling].height + 1,
+            data: None,
+            moved: false,
+        });
+        self.nodes[sibling].parent = Some(new_parent);
+        self.nodes[leaf].parent = Some(new_parent);
+        match old_parent {
+            Some(parent) => self.replace_child(parent, sibling, new_parent),
+            None => self.root = Some(new_parent),
+        }
+        self.refit_upwards(Some(new_parent));
+    }
+
+    fn remove_leaf(&mut self, leaf: usize) {
+        if self.root == Some(leaf) {
+            self.root = None;
+            return;
+        }
+        let parent = self.nodes[leaf]
+            .parent
+            .expect("non-root leaves have a parent");
+        let [child1, child2] = self.nodes[parent].children.expect("parents have children");
+        let sibling = if child1 == leaf { child2 } else { child1 };
+        let grandparent = self.nodes[parent].parent;
+        self.nodes[sibling].parent = grandparent;
+        match grandparent {
+            Some(grandparent) => self.replace_child(grandparent, parent, sibling),
+            None => self.root = Some(sibling),
+        }
+        self.release(parent);
+        self.nodes[leaf].parent = None;
+        self.refit_upwards(grandparent);
+    }
+
+    fn refit_upwards(&mut self, mut current: Option<usize>) {
+        while let Some(index) = current {
+            let index = self.balance(index);
+            self.refit(index);
+            current = self.nodes[index].parent;
+        }
+    }
+
+    fn refit(&mut self, index: usize) {
+        if let Some([a, b]) = self.nodes[index].children {
+            self.nodes[index].height = 1 + self.nodes[a].height.max(self.nodes[b].height);
+            self.nodes[index].aabb = self.nodes[a].aabb.union(&self.nodes[b].aabb);
+        }
+    }
+
+    fn replace_child(&mut self, parent: usize, old: usize, new: usize) {
+        if let Some(children) = self.nodes[parent].children.as_mut() {
+            for child in children.iter_mut() {
+                if *child == old {
+                    *child = new;
+                }
+            }
+        }
+    }
+
+    // Rotates the taller grandchild up when the subtree heights of `a`
+    // differ by more than one, returning the new root of the subtree.
+    fn balance(&mut self, a: usize) -> usize {
+        let Some([b, c]) = self.nodes[a].children else {
+            return a;
+        };
+        if self.nodes[a].height < 2 {
+            return a;
+        }
+        let (height_b, height_c) = (self.nodes[b].height, self.nodes[c].height);
+        if height_c > height_b + 1 {
+            self.rotate_up(a, c, 1)
+        } else if height_b > height_c + 1 {
+            self.rotate_up(a, b, 0)
+        } else {
+            a
+        }
+    }
+
+    // Swaps `a` with its child `up` (stored in slot `slot` of `a`). The
+    // shorter child of `up` is handed over to `a`.
+    fn rotate_up(&mut self, a: usize, up: usize, slot: usize) -> usize {
+        let [f, g] = self.nodes[up].children.expect("a tall child is internal");
+        let parent = self.nodes[a].parent;
+
+        self.nodes[up].parent = parent;
+        self.nodes[a].parent = Some(up);
+        match parent {
+            Some(parent) => self.replace_child(parent, a, up),
+            None => self.root = Some(up),
+        }
+
+        let (keep, give) = if self.nodes[f].height > self.nodes[g].height {
+            (f, g)
+        } else {
+            (g, f)
+        };
+        self.nodes[up].children = Some([a, keep]);
+        if let Some(children) = self.nodes[a].children.as_mut() {
+            children[slot] = give;
+        }
+        self.nodes[give].parent = Some(a);
+        self.refit(a);
+        self.refit(up);
+        up
+    }
+}
+
+// Slab test of the segment `origin + translation * t`, `t` in `[0, max]`.
+fn ray_hits_box<T>(origin: Vector2d<T>, translation: Vector2d<T>, max: T, aabb: &Aabb<T>) -> bool
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let (mut t_min, mut t_max) = (T::zero(), max);
+    for axis in 0..2 {
+        let (o, d) = (origin[axis], translation[axis]);
+        let (lo, hi) = (aabb.min[axis], aabb.max[axis]);
+        if d.abs() < T::epsilon() {
+            if o < lo || o > hi {
+                return false;
+            }
+            continue;
+        }
+        let (mut t1, mut t2) = ((lo - o) / d, (hi - o) / d);
+        if t1 > t2 {
+            std::mem::swap(&mut t1, &mut t2);
+        }
+        t_min = t_min.max(t1);
+        t_max = t_max.min(t2);
+        if t_min > t_max {
+            return false;
+        }
+    }
+    true
+}
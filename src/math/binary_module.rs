@@ -0,0 +1,645 @@
+use crate::math::scalar_module::{to_f64, vector_to_f64};
+use crate::math::shape_module::Aabb;
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+use std::io::{Read, Write};
+use std::marker::PhantomData;
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        Binary Vector Buffers
+// -----------------------------------------
+//
+// Layout, header fields little-endian:
+//
+//   magic "V2DB" | version u8 | element type u8 | flags u8 | reserved u8
+//   count u64 | [quantization box: min x, min y, max x, max y as f64]
+//   payload | CRC-32 of everything before it, u32
+//
+// The payload holds `x, y` per vector. Plain components are stored with the
+// element width in the flagged endianness, quantized ones as `u16`. With
+// delta encoding each component is instead the LEB128 varint of the zigzag
+// difference to the previous vector's component, which is small for smooth
+// animation samples whatever the endianness.
+
+/// The format version written by `VectorWriter`.
+pub const FORMAT_VERSION: u8 = 1;
+
+const MAGIC: [u8; 4] = *b"V2DB";
+const FLAG_BIG_ENDIAN: u8 = 1;
+const FLAG_QUANTIZED: u8 = 1 << 1;
+const FLAG_DELTA: u8 = 1 << 2;
+const QUANTIZATION_STEPS: f64 = u16::MAX as f64;
+
+/// Byte order of the payload.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+/// Float type of the stored components.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ElementType {
+    F32,
+    F64,
+}
+
+impl ElementType {
+    /// The element type matching `T` in size.
+    pub fn of<T: Float>() -> Self {
+        if std::mem::size_of::<T>() <= 4 {
+            ElementType::F32
+        } else {
+            ElementType::F64
+        }
+    }
+
+    fn width(self) -> usize {
+        match self {
+            ElementType::F32 => 4,
+            ElementType::F64 => 8,
+        }
+    }
+}
+
+/// How a buffer is encoded. The default is little-endian, unquantized and
+/// without delta encoding.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EncodeOptions<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub endianness: Endianness,
+    /// Store each component as a 16-bit fixed point value inside this box.
+    pub quantize: Option<Aabb<T>>,
+    /// Store differences between consecutive vectors.
+    pub delta: bool,
+}
+
+impl<T> Default for EncodeOptions<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn default() -> Self {
+        Self {
+            endianness: Endianness::Little,
+            quantize: None,
+            delta: false,
+        }
+    }
+}
+
+/// The decoded header of a buffer.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Header {
+    pub version: u8,
+    pub element_type: ElementType,
+    pub endianness: Endianness,
+    pub count: u64,
+    /// The quantization box as `(min, max)`.
+    pub quantization: Option<(Vector2d<f64>, Vector2d<f64>)>,
+    pub delta: bool,
+}
+
+impl Header {
+    fn width(&self) -> usize {
+        if self.quantization.is_some() {
+            2
+        } else {
+            self.element_type.width()
+        }
+    }
+}
+
+/// Encodes `points` into a new buffer.
+///
+/// # Returns
+///
+/// The bytes, or an `Err` when a point lies outside the quantization box.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::binary_module::{decode, encode, EncodeOptions};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let points = vec![Vector2d::new(1.0, 2.0), Vector2d::new(1.5, 2.25)];
+/// let bytes = encode(&points, &EncodeOptions::default()).unwrap();
+/// assert_eq!(decode::<f64>(&bytes).unwrap(), points);
+/// ```
+pub fn encode<T>(points: &[Vector2d<T>], options: &EncodeOptions<T>) -> Result<Vec<u8>, String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let mut writer = VectorWriter::new(Vec::new(), points.len() as u64, options)?;
+    for point in points {
+        writer.write(*point)?;
+    }
+    writer.finish()
+}
+
+/// Decodes a whole buffer written by `encode` or `VectorWriter`.
+///
+/// # Returns
+///
+/// The vectors, or an `Err` when the buffer is malformed, truncated, has
+/// trailing bytes or fails its checksum.
+pub fn decode<T>(bytes: &[u8]) -> Result<Vec<Vector2d<T>>, String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let mut input = bytes;
+    let points = VectorReader::new(&mut input)?.read_all()?;
+    if !input.is_empty() {
+        return Err(format!(
+            "[::ERROR::] -> {} unexpected bytes after the checksum.",
+            input.len()
+        ));
+    }
+    Ok(points)
+}
+
+// -----------------------------------------
+//        Streaming Writer
+// -----------------------------------------
+
+/// Streams vectors into a `Write`. The number of vectors is part of the
+/// header, so it must be known up front; `finish` appends the checksum.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::binary_module::{EncodeOptions, VectorReader, VectorWriter};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let mut writer = VectorWriter::new(Vec::new(), 3, &EncodeOptions { delta: true, ..Default::default() }).unwrap();
+/// for i in 0..3 {
+///     writer.write(Vector2d::new(i as f32, 0.5)).unwrap();
+/// }
+/// let bytes = writer.finish().unwrap();
+///
+/// let reader = VectorReader::<_, f32>::new(bytes.as_slice()).unwrap();
+/// assert_eq!(reader.header().count, 3);
+/// let points: Result<Vec<_>, _> = reader.collect();
+/// assert_eq!(points.unwrap()[2], Vector2d::new(2.0, 0.5));
+/// ```
+pub struct VectorWriter<W, T>
+where
+    W: Write,
+    T: Float + AddAssign + std::fmt::Display,
+{
+    inner: W,
+    header: Header,
+    written: u64,
+    previous: [u64; 2],
+    crc: u32,
+    _element: PhantomData<T>,
+}
+
+impl<W, T> VectorWriter<W, T>
+where
+    W: Write,
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Writes the header for `count` vectors to `inner`.
+    ///
+    /// # Returns
+    ///
+    /// The writer, or an `Err` when the quantization box is empty or writing
+    /// fails.
+    pub fn new(inner: W, count: u64, options: &EncodeOptions<T>) -> Result<Self, String> {
+        let quantization = match options.quantize {
+            Some(area) => {
+                let min = vector_to_f64(area.min);
+                let max = vector_to_f64(area.max);
+                if min.x >= max.x || min.y >= max.y || !(max - min).magnitude().is_finite() {
+                    return Err(
+                        "[::ERROR::] -> The quantization box must have a finite, non-zero area."
+                            .to_string(),
+                    );
+                }
+                Some((min, max))
+            }
+            None => None,
+        };
+        let header = Header {
+            version: FORMAT_VERSION,
+            element_type: ElementType::of::<T>(),
+            endianness: options.endianness,
+            count,
+            quantization,
+            delta: options.delta,
+        };
+        let mut writer = Self {
+            inner,
+            header,
+            written: 0,
+            previous: [0; 2],
+            crc: CRC_INIT,
+            _element: PhantomData,
+        };
+        let mut bytes = MAGIC.to_vec();
+        let mut flags = 0;
+        if header.endianness == Endianness::Big {
+            flags |= FLAG_BIG_ENDIAN;
+        }
+        if quantization.is_some() {
+            flags |= FLAG_QUANTIZED;
+        }
+        if header.delta {
+            flags |= FLAG_DELTA;
+        }
+        bytes.extend([header.version, header.element_type as u8, flags, 0]);
+        bytes.extend(count.to_le_bytes());
+        if let Some((min, max)) = quantization {
+            for value in [min.x, min.y, max.x, max.y] {
+                bytes.extend(value.to_le_bytes());
+            }
+        }
+        writer.emit(&bytes)?;
+        Ok(writer)
+    }
+
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Appends one vector.
+    ///
+    /// # Returns
+    ///
+    /// An `Err` when all vectors announced in the header have been written,
+    /// the vector lies outside the quantization box or writing fails.
+    pub fn write(&mut self, vector: Vector2d<T>) -> Result<(), String> {
+        if self.written == self.header.count {
+            return Err(format!(
+                "[::ERROR::] -> The header announced {} vectors.",
+                self.header.count
+            ));
+        }
+        // Encode both components before touching the delta state, so a
+        // rejected vector leaves the stream as it was.
+        let codes = [self.code(0, vector.x)?, self.code(1, vector.y)?];
+        let mut bytes = Vec::with_capacity(16);
+        for (axis, code) in codes.into_iter().enumerate() {
+            if self.header.delta {
+                let difference = code.wrapping_sub(self.previous[axis]) as i64;
+                write_varint(&mut bytes, ((difference << 1) ^ (difference >> 63)) as u64);
+            } else {
+                let width = self.header.width();
+                match self.header.endianness {
+                    Endianness::Little => bytes.extend(&code.to_le_bytes()[..width]),
+                    Endianness::Big => bytes.extend(&code.to_be_bytes()[8 - width..]),
+                }
+            }
+        }
+        self.emit(&bytes)?;
+        self.previous = codes;
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Appends the checksum and hands back the inner writer.
+    ///
+    /// # Returns
+    ///
+    /// The inner writer, or an `Err` when fewer vectors than announced were
+    /// written or writing fails.
+    pub fn finish(mut self) -> Result<W, String> {
+        if self.written != self.header.count {
+            return Err(format!(
+                "[::ERROR::] -> Only {} of the {} announced vectors were written.",
+                self.written, self.header.count
+            ));
+        }
+        let checksum = !self.crc;
+        self.inner
+            .write_all(&checksum.to_le_bytes())
+            .and_then(|_| self.inner.flush())
+            .map_err(io_error)?;
+        Ok(self.inner)
+    }
+
+    fn code(&self, axis: usize, value: T) -> Result<u64, String> {
+        if let Some((min, max)) = self.header.quantization {
+            let (low, high) = if axis == 0 {
+                (min.x, max.x)
+            } else {
+                (min.y, max.y)
+            };
+            let value = to_f64(value);
+            if !(low..=high).contains(&value) {
+                return Err(format!(
+                    "[::ERROR::] -> {} is outside the quantization range [{}, {}].",
+                    value, low, high
+                ));
+            }
+            return Ok(((value - low) / (high - low) * QUANTIZATION_STEPS).round() as u64);
+        }
+        Ok(match self.header.element_type {
+            ElementType::F32 => value.to_f32().unwrap_or(f32::NAN).to_bits() as u64,
+            ElementType::F64 => to_f64(value).to_bits(),
+        })
+    }
+
+    fn emit(&mut self, bytes: &[u8]) -> Result<(), String> {
+        self.crc = crc32_update(self.crc, bytes);
+        self.inner.write_all(bytes).map_err(io_error)
+    }
+}
+
+// -----------------------------------------
+//        Streaming Reader
+// -----------------------------------------
+
+/// Streams vectors out of a `Read`, as an iterator of `Result`s. The checksum
+/// is verified after the last vector; a mismatch is reported as a final
+/// `Err` item.
+pub struct VectorReader<R, T>
+where
+    R: Read,
+    T: Float + AddAssign + std::fmt::Display,
+{
+    inner: R,
+    header: Header,
+    read: u64,
+    previous: [u64; 2],
+    crc: u32,
+    done: bool,
+    _element: PhantomData<T>,
+}
+
+impl<R, T> VectorReader<R, T>
+where
+    R: Read,
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Reads and validates the header.
+    ///
+    /// # Returns
+    ///
+    /// The reader, or an `Err` when the magic, version, element type or flags
+    /// are not recognised or the input ends early.
+    pub fn new(inner: R) -> Result<Self, String> {
+        let mut reader = Self {
+            inner,
+            header: Header {
+                version: FORMAT_VERSION,
+                element_type: ElementType::F64,
+                endianness: Endianness::Little,
+                count: 0,
+                quantization: None,
+                delta: false,
+            },
+            read: 0,
+            previous: [0; 2],
+            crc: CRC_INIT,
+            done: false,
+            _element: PhantomData,
+        };
+        let fixed: [u8; 16] = reader.take_bytes()?;
+        if fixed[..4] != MAGIC {
+            return Err("[::ERROR::] -> Not a Vector2d buffer.".to_string());
+        }
+        let version = fixed[4];
+        if version == 0 || version > FORMAT_VERSION {
+            return Err(format!(
+                "[::ERROR::] -> Unsupported format version {}.",
+                version
+            ));
+        }
+        let element_type = match fixed[5] {
+            0 => ElementType::F32,
+            1 => ElementType::F64,
+            other => {
+                return Err(format!("[::ERROR::] -> Unknown element type {}.", other));
+            }
+        };
+        let flags = fixed[6];
+        if flags & !(FLAG_BIG_ENDIAN | FLAG_QUANTIZED | FLAG_DELTA) != 0 || fixed[7] != 0 {
+            return Err(format!("[::ERROR::] -> Unknown flags {:#04x}.", flags));
+        }
+        let mut count = [0; 8];
+        count.copy_from_slice(&fixed[8..]);
+        let quantization = if flags & FLAG_QUANTIZED != 0 {
+            let raw: [u8; 32] = reader.take_bytes()?;
+            let value = |i: usize| {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(&raw[i * 8..i * 8 + 8]);
+                f64::from_le_bytes(bytes)
+            };
+            Some((
+                Vector2d::new(value(0), value(1)),
+                Vector2d::new(value(2), value(3)),
+            ))
+        } else {
+            None
+        };
+        reader.header = Header {
+            version,
+            element_type,
+            endianness: if flags & FLAG_BIG_ENDIAN != 0 {
+                Endianness::Big
+            } else {
+                Endianness::Little
+            },
+            count: u64::from_le_bytes(count),
+            quantization,
+            delta: flags & FLAG_DELTA != 0,
+        };
+        Ok(reader)
+    }
+
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Reads every remaining vector and verifies the checksum.
+    pub fn read_all(self) -> Result<Vec<Vector2d<T>>, String> {
+        // Do not trust the header count for the allocation.
+        let capacity = self.header.count.min(1 << 20) as usize;
+        let mut points = Vec::with_capacity(capacity);
+        for point in self {
+            points.push(point?);
+        }
+        Ok(points)
+    }
+
+    fn next_vector(&mut self) -> Result<Vector2d<T>, String> {
+        let mut components = [T::zero(); 2];
+        for (axis, component) in components.iter_mut().enumerate() {
+            let code = if self.header.delta {
+                let zigzag = self.varint()?;
+                let difference = ((zigzag >> 1) as i64) ^ -((zigzag & 1) as i64);
+                self.previous[axis] = self.previous[axis].wrapping_add(difference as u64);
+                self.previous[axis]
+            } else {
+                let width = self.header.width();
+                let raw: [u8; 8] = self.take_partial(width)?;
+                match self.header.endianness {
+                    Endianness::Little => u64::from_le_bytes(raw),
+                    Endianness::Big => u64::from_be_bytes(raw),
+                }
+            };
+            *component = self.value(axis, code)?;
+        }
+        Ok(Vector2d::new(components[0], components[1]))
+    }
+
+    fn value(&self, axis: usize, code: u64) -> Result<T, String> {
+        let value = if let Some((min, max)) = self.header.quantization {
+            if code > u16::MAX as u64 {
+                return Err("[::ERROR::] -> Corrupted quantized component.".to_string());
+            }
+            let (low, high) = if axis == 0 {
+                (min.x, max.x)
+            } else {
+                (min.y, max.y)
+            };
+            low + (high - low) * code as f64 / QUANTIZATION_STEPS
+        } else {
+            match self.header.element_type {
+                ElementType::F32 => {
+                    let bits = u32::try_from(code)
+                        .map_err(|_| "[::ERROR::] -> Corrupted f32 component.".to_string())?;
+                    f32::from_bits(bits) as f64
+                }
+                ElementType::F64 => f64::from_bits(code),
+            }
+        };
+        Ok(T::from(value).unwrap_or_else(T::nan))
+    }
+
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let [byte] = self.take_bytes::<1>()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("[::ERROR::] -> Varint longer than 64 bits.".to_string())
+    }
+
+    fn take_bytes<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut bytes = [0; N];
+        self.inner.read_exact(&mut bytes).map_err(io_error)?;
+        self.crc = crc32_update(self.crc, &bytes);
+        Ok(bytes)
+    }
+
+    // Reads `width` bytes into the low (little-endian) or high (big-endian)
+    // end of an 8 byte buffer.
+    fn take_partial(&mut self, width: usize) -> Result<[u8; 8], String> {
+        let mut bytes = [0; 8];
+        let range = match self.header.endianness {
+            Endianness::Little => 0..width,
+            Endianness::Big => 8 - width..8,
+        };
+        self.inner
+            .read_exact(&mut bytes[range.clone()])
+            .map_err(io_error)?;
+        self.crc = crc32_update(self.crc, &bytes[range]);
+        Ok(bytes)
+    }
+
+    fn verify_checksum(&mut self) -> Result<(), String> {
+        let expected = !self.crc;
+        let mut stored = [0; 4];
+        self.inner.read_exact(&mut stored).map_err(io_error)?;
+        let stored = u32::from_le_bytes(stored);
+        if stored != expected {
+            return Err(format!(
+                "[::ERROR::] -> Checksum mismatch: stored {:#010x}, computed {:#010x}.",
+                stored, expected
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<R, T> Iterator for VectorReader<R, T>
+where
+    R: Read,
+    T: Float + AddAssign + std::fmt::Display,
+{
+    type Item = Result<Vector2d<T>, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if self.read == self.header.count {
+            self.done = true;
+            return self.verify_checksum().err().map(Err);
+        }
+        let result = self.next_vector();
+        match result {
+            Ok(_) => self.read += 1,
+            Err(_) => self.done = true,
+        }
+        Some(result)
+    }
+}
+
+fn io_error(error: std::io::Error) -> String {
+    format!("[::ERROR::] -> I/O failure: {}.", error)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+// -----------------------------------------
+//        CRC-32 (IEEE 802.3)
+// -----------------------------------------
+
+const CRC_INIT: u32 = 0xffff_ffff;
+
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn crc32_update(mut crc: u32, bytes: &[u8]) -> u32 {
+    for &byte in bytes {
+        crc = CRC_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    crc
+}
+
+/// The CRC-32 checksum used to protect buffers.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::binary_module::crc32;
+///
+/// assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+/// ```
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !crc32_update(CRC_INIT, bytes)
+}
@@ -0,0 +1,412 @@
+use crate::math::gjk_module::{epa_penetration, gjk_distance, SupportMap};
+use crate::math::polyline_module::closest_on_segment;
+use crate::math::scalar_module::cast;
+use crate::math::shape_module::{Aabb, Circle};
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+const MAX_ADVANCEMENT_ITERATIONS: usize = 64;
+
+// -----------------------------------------
+//        Time Of Impact Result
+// -----------------------------------------
+
+/// The earliest contact between a moving shape and an obstacle.
+///
+/// `time` is expressed in the same unit as the velocities (a hit after half
+/// of a `dt` long step is reported at `0.5 * dt`). `normal` is the unit
+/// surface normal at the contact, pointing from the obstacle towards the
+/// moving shape. Shapes that already overlap at the start report a time of
+/// `0`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TimeOfImpact<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub time: T,
+    pub normal: Vector2d<T>,
+}
+
+// -----------------------------------------
+//        Moving Circle Queries
+// -----------------------------------------
+
+/// Computes when a circle moving with `velocity` first touches the segment `a`-`b`.
+///
+/// # Arguments
+///
+/// * `circle` - The circle at its start position.
+/// * `velocity` - The circle velocity.
+/// * `a`, `b` - The segment end points.
+/// * `max_time` - Hits later than `max_time` are ignored (usually the step `dt`).
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::ccd_module::circle_segment_toi;
+/// use vectorlib::math::shape_module::Circle;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let bullet = Circle::new(Vector2d::new(0.0_f64, 0.0), 0.1);
+/// let wall = (Vector2d::new(5.0, -1.0), Vector2d::new(5.0, 1.0));
+/// let hit = circle_segment_toi(&bullet, Vector2d::new(100.0, 0.0), wall.0, wall.1, 1.0).unwrap();
+/// assert!((hit.time - 0.049).abs() < 1e-12);
+/// assert_eq!(hit.normal, Vector2d::new(-1.0, 0.0));
+/// ```
+pub fn circle_segment_toi<T>(
+    circle: &Circle<T>,
+    velocity: Vector2d<T>,
+    a: Vector2d<T>,
+    b: Vector2d<T>,
+    max_time: T,
+) -> Option<TimeOfImpact<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let c = circle.center;
+    let r = circle.radius;
+
+    // Already overlapping: report the separation direction at time zero.
+    let (closest, _) = closest_on_segment(c, a, b);
+    let offset = c - closest;
+    if offset.dot_product(offset) <= r * r {
+        let normal = offset
+            .normalize()
+            .or_else(|_| (b - a).perpendicular().normalize())
+            .unwrap_or(Vector2d::new(T::zero(), T::one()));
+        return Some(TimeOfImpact {
+            time: T::zero(),
+            normal,
+        });
+    }
+
+    let mut best: Option<TimeOfImpact<T>> = None;
+    let mut consider = |candidate: TimeOfImpact<T>| {
+        if candidate.time >= T::zero()
+            && candidate.time <= max_time
+            && best.is_none_or(|b| candidate.time < b.time)
+        {
+            best = Some(candidate);
+        }
+    };
+
+    // Segment interior.
+    if let Ok(mut normal) = (b - a).perpendicular().normalize() {
+        let mut distance = normal.dot_product(c - a);
+        if distance < T::zero() {
+            normal = -normal;
+            distance = -distance;
+        }
+        let speed = -normal.dot_product(velocity);
+        if speed > T::zero() {
+            let time = (distance - r) / speed;
+            let contact = c + velocity * time - normal * r;
+            let edge = b - a;
+            let u = (contact - a).dot_product(edge) / edge.dot_product(edge);
+            if u >= T::zero() && u <= T::one() {
+                consider(TimeOfImpact { time, normal });
+            }
+        }
+    }
+
+    // Segment end points.
+    for end in [a, b] {
+        if let Some(time) = ray_circle(c, velocity, end, r) {
+            let normal = (c + velocity * time - end) * (T::one() / r);
+            consider(TimeOfImpact { time, normal });
+        }
+    }
+    best
+}
+
+/// Computes when a moving circle first touches the outline of a polygon.
+///
+/// The polygon is given as a closed vertex list and does not need to be convex.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::ccd_module::circle_polygon_toi;
+/// use vectorlib::math::shape_module::Circle;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let ball = Circle::new(Vector2d::new(0.0, 5.0), 1.0);
+/// let ground = vec![
+///     Vector2d::new(-10.0, -1.0),
+///     Vector2d::new(10.0, -1.0),
+///     Vector2d::new(10.0, 0.0),
+///     Vector2d::new(-10.0, 0.0),
+/// ];
+/// let hit = circle_polygon_toi(&ball, Vector2d::new(0.0, -8.0), &ground, 1.0).unwrap();
+/// assert_eq!(hit.time, 0.5);
+/// assert_eq!(hit.normal, Vector2d::new(0.0, 1.0));
+/// ```
+pub fn circle_polygon_toi<T>(
+    circle: &Circle<T>,
+    velocity: Vector2d<T>,
+    polygon: &[Vector2d<T>],
+    max_time: T,
+) -> Option<TimeOfImpact<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let n = polygon.len();
+    let mut best: Option<TimeOfImpact<T>> = None;
+    for i in 0..n {
+        let hit = circle_segment_toi(circle, velocity, polygon[i], polygon[(i + 1) % n], max_time);
+        if let Some(hit) = hit {
+            if best.is_none_or(|b| hit.time < b.time) {
+                best = Some(hit);
+            }
+        }
+    }
+    best
+}
+
+/// Computes when a moving circle first touches an axis aligned box.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::ccd_module::circle_aabb_toi;
+/// use vectorlib::math::shape_module::{Aabb, Circle};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let ball = Circle::new(Vector2d::new(-3.0, 0.0), 1.0);
+/// let block = Aabb::new(Vector2d::new(0.0, -1.0), Vector2d::new(1.0, 1.0));
+/// let hit = circle_aabb_toi(&ball, Vector2d::new(4.0, 0.0), &block, 1.0).unwrap();
+/// assert_eq!(hit.time, 0.5);
+/// ```
+pub fn circle_aabb_toi<T>(
+    circle: &Circle<T>,
+    velocity: Vector2d<T>,
+    aabb: &Aabb<T>,
+    max_time: T,
+) -> Option<TimeOfImpact<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    if aabb.contains_point(circle.center) {
+        let offset = circle.center - aabb.center();
+        let normal =
+            if offset.x.abs() * aabb.half_extents().y >= offset.y.abs() * aabb.half_extents().x {
+                Vector2d::new(T::one().copysign(offset.x), T::zero())
+            } else {
+                Vector2d::new(T::zero(), T::one().copysign(offset.y))
+            };
+        return Some(TimeOfImpact {
+            time: T::zero(),
+            normal,
+        });
+    }
+    circle_polygon_toi(circle, velocity, &aabb.corners(), max_time)
+}
+
+// -----------------------------------------
+//        Swept Boxes
+// -----------------------------------------
+
+/// Sweeps two moving axis aligned boxes against each other.
+///
+/// # Returns
+///
+/// The earliest time at which `a` touches `b` with the normal of the hit face
+/// of `b`, or `None` when they do not meet within `max_time`.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::ccd_module::swept_aabb;
+/// use vectorlib::math::shape_module::Aabb;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let player = Aabb::new(Vector2d::new(0.0, 0.0), Vector2d::new(1.0, 1.0));
+/// let wall = Aabb::new(Vector2d::new(3.0, -5.0), Vector2d::new(4.0, 5.0));
+/// let zero = Vector2d::new(0.0, 0.0);
+/// let hit = swept_aabb(&player, Vector2d::new(4.0, 1.0), &wall, zero, 1.0).unwrap();
+/// assert_eq!(hit.time, 0.5);
+/// assert_eq!(hit.normal, Vector2d::new(-1.0, 0.0));
+/// ```
+pub fn swept_aabb<T>(
+    a: &Aabb<T>,
+    velocity_a: Vector2d<T>,
+    b: &Aabb<T>,
+    velocity_b: Vector2d<T>,
+    max_time: T,
+) -> Option<TimeOfImpact<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let v = velocity_a - velocity_b;
+    let mut entry = T::neg_infinity();
+    let mut exit = T::infinity();
+    let mut normal = Vector2d::new(T::zero(), T::zero());
+
+    for axis in 0..2 {
+        let (a_min, a_max, b_min, b_max, speed) =
+            (a.min[axis], a.max[axis], b.min[axis], b.max[axis], v[axis]);
+        if speed == T::zero() {
+            if a_max < b_min || b_max < a_min {
+                return None;
+            }
+            continue;
+        }
+        let (axis_entry, axis_exit) = if speed > T::zero() {
+            ((b_min - a_max) / speed, (b_max - a_min) / speed)
+        } else {
+            ((b_max - a_min) / speed, (b_min - a_max) / speed)
+        };
+        if axis_entry > entry {
+            entry = axis_entry;
+            normal = Vector2d::new(T::zero(), T::zero());
+            normal[axis] = -T::one().copysign(speed);
+        }
+        exit = exit.min(axis_exit);
+    }
+
+    if entry > exit || exit < T::zero() || entry > max_time {
+        return None;
+    }
+    if entry == T::neg_infinity() {
+        // Not moving relative to each other, and overlapping on both axes.
+        let offset = a.center() - b.center();
+        return Some(TimeOfImpact {
+            time: T::zero(),
+            normal: offset
+                .normalize()
+                .unwrap_or(Vector2d::new(T::zero(), T::one())),
+        });
+    }
+    Some(TimeOfImpact {
+        time: entry.max(T::zero()),
+        normal,
+    })
+}
+
+// -----------------------------------------
+//        Conservative Advancement
+// -----------------------------------------
+
+/// Computes the time of impact of two translating convex shapes by
+/// conservative advancement.
+///
+/// The shapes are repeatedly advanced by the largest step that cannot make
+/// them overlap (their GJK distance divided by the closing speed) until the
+/// distance drops below `tolerance`. Works with any pair of [`SupportMap`]
+/// shapes; rotation is not taken into account.
+///
+/// The returned normal points from `b` towards `a`.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::ccd_module::conservative_advancement;
+/// use vectorlib::math::shape_module::{Capsule, ConvexPolygon};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let stick = Capsule::new(Vector2d::new(-1.0_f64, 5.0), Vector2d::new(1.0, 5.0), 0.25);
+/// let floor = ConvexPolygon::rectangle(Vector2d::new(0.0, -1.0), Vector2d::new(10.0, 1.0));
+/// let zero = Vector2d::new(0.0, 0.0);
+/// let hit = conservative_advancement(&stick, Vector2d::new(0.0, -9.5), &floor, zero, 1.0, 1e-6)
+///     .unwrap();
+/// assert!((hit.time - 0.5).abs() < 1e-6);
+/// ```
+pub fn conservative_advancement<T, A, B>(
+    a: &A,
+    velocity_a: Vector2d<T>,
+    b: &B,
+    velocity_b: Vector2d<T>,
+    max_time: T,
+    tolerance: T,
+) -> Option<TimeOfImpact<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    A: SupportMap<T> + ?Sized,
+    B: SupportMap<T> + ?Sized,
+{
+    let relative = velocity_a - velocity_b;
+    let mut time = T::zero();
+    for _ in 0..MAX_ADVANCEMENT_ITERATIONS {
+        let moved = Translated {
+            shape: a,
+            offset: relative * time,
+        };
+        let Some(closest) = gjk_distance(&moved, b) else {
+            // Only reachable at time zero: the shapes start overlapping.
+            let normal = epa_penetration(&moved, b)
+                .map(|p| -p.normal)
+                .unwrap_or(Vector2d::new(T::zero(), T::one()));
+            return Some(TimeOfImpact { time, normal });
+        };
+        let normal = (closest.point_a - closest.point_b)
+            .normalize()
+            .unwrap_or(Vector2d::new(T::zero(), T::one()));
+        if closest.distance <= tolerance {
+            return Some(TimeOfImpact { time, normal });
+        }
+        let closing_speed = -relative.dot_product(normal);
+        if closing_speed <= T::zero() {
+            return None;
+        }
+        time += (closest.distance - tolerance * cast(0.5)) / closing_speed;
+        if time > max_time {
+            return None;
+        }
+    }
+    None
+}
+
+// -----------------------------------------
+//        Internal helpers
+// -----------------------------------------
+
+struct Translated<'a, T, S>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    S: SupportMap<T> + ?Sized,
+{
+    shape: &'a S,
+    offset: Vector2d<T>,
+}
+
+impl<T, S> SupportMap<T> for Translated<'_, T, S>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    S: SupportMap<T> + ?Sized,
+{
+    fn support(&self, direction: Vector2d<T>) -> Vector2d<T> {
+        self.shape.support(direction) + self.offset
+    }
+}
+
+/// Smallest non negative `t` such that `origin + direction * t` lies on the
+/// circle of the given `center` and `radius`.
+pub(crate) fn ray_circle<T>(
+    origin: Vector2d<T>,
+    direction: Vector2d<T>,
+    center: Vector2d<T>,
+    radius: T,
+) -> Option<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let m = origin - center;
+    let a = direction.dot_product(direction);
+    if a == T::zero() {
+        return None;
+    }
+    let b = m.dot_product(direction);
+    let c = m.dot_product(m) - radius * radius;
+    if c > T::zero() && b > T::zero() {
+        return None;
+    }
+    let discriminant = b * b - a * c;
+    if discriminant < T::zero() {
+        return None;
+    }
+    let t = (-b - discriminant.sqrt()) / a;
+    Some(t.max(T::zero()))
+}
@@ -0,0 +1,452 @@
+use crate::math::polyline_module::closest_on_segment;
+use crate::math::shape_module::{Aabb, Capsule, Circle, ConvexPolygon};
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+const MAX_GJK_ITERATIONS: usize = 64;
+const MAX_EPA_ITERATIONS: usize = 64;
+
+// -----------------------------------------
+//        Support Mapping
+// -----------------------------------------
+
+/// A convex shape described by its support function.
+///
+/// `support(direction)` returns the point of the shape that is the farthest
+/// along `direction`. This is the only thing GJK and EPA need to know about a
+/// shape, so a new shape type only has to implement this trait.
+pub trait SupportMap<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn support(&self, direction: Vector2d<T>) -> Vector2d<T>;
+}
+
+impl<T> SupportMap<T> for Vector2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn support(&self, _direction: Vector2d<T>) -> Vector2d<T> {
+        *self
+    }
+}
+
+impl<T> SupportMap<T> for Circle<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn support(&self, direction: Vector2d<T>) -> Vector2d<T> {
+        match direction.normalize() {
+            Ok(unit) => self.center + unit * self.radius,
+            Err(_) => self.center,
+        }
+    }
+}
+
+impl<T> SupportMap<T> for Capsule<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn support(&self, direction: Vector2d<T>) -> Vector2d<T> {
+        let end = if direction.dot_product(self.b - self.a) > T::zero() {
+            self.b
+        } else {
+            self.a
+        };
+        match direction.normalize() {
+            Ok(unit) => end + unit * self.radius,
+            Err(_) => end,
+        }
+    }
+}
+
+impl<T> SupportMap<T> for ConvexPolygon<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn support(&self, direction: Vector2d<T>) -> Vector2d<T> {
+        let mut best = self.vertices()[0];
+        let mut best_dot = best.dot_product(direction);
+        for &v in &self.vertices()[1..] {
+            let d = v.dot_product(direction);
+            if d > best_dot {
+                best = v;
+                best_dot = d;
+            }
+        }
+        best
+    }
+}
+
+impl<T> SupportMap<T> for Aabb<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn support(&self, direction: Vector2d<T>) -> Vector2d<T> {
+        Vector2d::new(
+            if direction.x >= T::zero() {
+                self.max.x
+            } else {
+                self.min.x
+            },
+            if direction.y >= T::zero() {
+                self.max.y
+            } else {
+                self.min.y
+            },
+        )
+    }
+}
+
+// -----------------------------------------
+//        Query Results
+// -----------------------------------------
+
+/// The closest points between two separated shapes.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ClosestPoints<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub distance: T,
+    pub point_a: Vector2d<T>,
+    pub point_b: Vector2d<T>,
+}
+
+/// The penetration between two overlapping shapes.
+///
+/// `normal` points from the first shape towards the second one; moving the
+/// second shape by `normal * depth` brings the shapes into touching contact.
+/// `point_a` and `point_b` are the deepest points of each shape.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Penetration<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub normal: Vector2d<T>,
+    pub depth: T,
+    pub point_a: Vector2d<T>,
+    pub point_b: Vector2d<T>,
+}
+
+// -----------------------------------------
+//     Gilbert–Johnson–Keerthi Queries
+// -----------------------------------------
+
+/// Tests two convex shapes for intersection with GJK.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::gjk_module::gjk_intersects;
+/// use vectorlib::math::shape_module::{Circle, ConvexPolygon};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let ball = Circle::new(Vector2d::new(0.0, 1.4), 0.5);
+/// let floor = ConvexPolygon::rectangle(Vector2d::new(0.0, 0.0), Vector2d::new(4.0, 1.0));
+/// assert!(gjk_intersects(&ball, &floor));
+/// ```
+pub fn gjk_intersects<T, A, B>(a: &A, b: &B) -> bool
+where
+    T: Float + AddAssign + std::fmt::Display,
+    A: SupportMap<T> + ?Sized,
+    B: SupportMap<T> + ?Sized,
+{
+    matches!(run_gjk(a, b), GjkOutcome::Intersecting(_))
+}
+
+/// Computes the distance and closest points between two convex shapes.
+///
+/// # Returns
+///
+/// `None` when the shapes intersect, otherwise the closest points.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::gjk_module::gjk_distance;
+/// use vectorlib::math::shape_module::Circle;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let a = Circle::new(Vector2d::new(0.0, 0.0), 1.0);
+/// let b = Vector2d::new(3.0_f64, 0.0);
+/// let closest = gjk_distance(&a, &b).unwrap();
+/// assert!((closest.distance - 2.0).abs() < 1e-6);
+/// ```
+pub fn gjk_distance<T, A, B>(a: &A, b: &B) -> Option<ClosestPoints<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    A: SupportMap<T> + ?Sized,
+    B: SupportMap<T> + ?Sized,
+{
+    match run_gjk(a, b) {
+        GjkOutcome::Separated(closest) => Some(closest),
+        GjkOutcome::Intersecting(_) => None,
+    }
+}
+
+/// Computes the penetration depth and normal of two intersecting convex
+/// shapes with the Expanding Polytope Algorithm.
+///
+/// # Returns
+///
+/// `None` when the shapes do not intersect.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::gjk_module::epa_penetration;
+/// use vectorlib::math::shape_module::ConvexPolygon;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let a = ConvexPolygon::rectangle(Vector2d::new(0.0_f64, 0.0), Vector2d::new(1.0, 1.0));
+/// let b = ConvexPolygon::rectangle(Vector2d::new(1.5, 0.0), Vector2d::new(1.0, 1.0));
+/// let penetration = epa_penetration(&a, &b).unwrap();
+/// assert!((penetration.depth - 0.5).abs() < 1e-9);
+/// assert!((penetration.normal.x - 1.0).abs() < 1e-9);
+/// ```
+pub fn epa_penetration<T, A, B>(a: &A, b: &B) -> Option<Penetration<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    A: SupportMap<T> + ?Sized,
+    B: SupportMap<T> + ?Sized,
+{
+    match run_gjk(a, b) {
+        GjkOutcome::Separated(_) => None,
+        GjkOutcome::Intersecting(simplex) => Some(run_epa(a, b, simplex)),
+    }
+}
+
+// -----------------------------------------
+//        Internal helpers
+// -----------------------------------------
+
+/// A vertex of the Minkowski difference `A - B` with the support points
+/// of each shape that produced it.
+#[derive(Copy, Clone)]
+struct MinkowskiVertex<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    w: Vector2d<T>,
+    a: Vector2d<T>,
+    b: Vector2d<T>,
+}
+
+enum GjkOutcome<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    Separated(ClosestPoints<T>),
+    Intersecting(Vec<MinkowskiVertex<T>>),
+}
+
+fn tolerance<T: Float>() -> T {
+    T::epsilon().sqrt()
+}
+
+fn minkowski_support<T, A, B>(a: &A, b: &B, direction: Vector2d<T>) -> MinkowskiVertex<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    A: SupportMap<T> + ?Sized,
+    B: SupportMap<T> + ?Sized,
+{
+    let pa = a.support(direction);
+    let pb = b.support(-direction);
+    MinkowskiVertex {
+        w: pa - pb,
+        a: pa,
+        b: pb,
+    }
+}
+
+fn run_gjk<T, A, B>(a: &A, b: &B) -> GjkOutcome<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    A: SupportMap<T> + ?Sized,
+    B: SupportMap<T> + ?Sized,
+{
+    let eps = tolerance::<T>();
+    let mut simplex = vec![minkowski_support(a, b, Vector2d::new(T::one(), T::zero()))];
+    let mut closest = (simplex[0].w, vec![T::one()]);
+
+    for _ in 0..MAX_GJK_ITERATIONS {
+        let v = closest.0;
+        let v_squared = v.dot_product(v);
+        if v_squared <= eps * eps || simplex.len() == 3 {
+            return GjkOutcome::Intersecting(simplex);
+        }
+        let vertex = minkowski_support(a, b, -v);
+        let duplicate = simplex.iter().any(|s| s.w == vertex.w);
+        // No progress towards the origin: `v` is the closest point.
+        if duplicate || v_squared - v.dot_product(vertex.w) <= eps * v_squared {
+            break;
+        }
+        simplex.push(vertex);
+        closest = reduce_simplex(&mut simplex);
+    }
+
+    let (v, weights) = closest;
+    let mut point_a = Vector2d::new(T::zero(), T::zero());
+    let mut point_b = Vector2d::new(T::zero(), T::zero());
+    for (vertex, &weight) in simplex.iter().zip(weights.iter()) {
+        point_a += vertex.a * weight;
+        point_b += vertex.b * weight;
+    }
+    GjkOutcome::Separated(ClosestPoints {
+        distance: v.magnitude(),
+        point_a,
+        point_b,
+    })
+}
+
+/// Reduces the simplex to the smallest feature containing the point closest
+/// to the origin and returns that point with the barycentric weights of the
+/// remaining vertices. A triangle is only kept when it contains the origin.
+fn reduce_simplex<T>(simplex: &mut Vec<MinkowskiVertex<T>>) -> (Vector2d<T>, Vec<T>)
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    if simplex.len() == 2 {
+        let (p, q) = (simplex[0].w, simplex[1].w);
+        let (point, t) = closest_to_origin(p, q);
+        if t <= T::zero() {
+            simplex.truncate(1);
+            return (p, vec![T::one()]);
+        }
+        if t >= T::one() {
+            simplex.remove(0);
+            return (q, vec![T::one()]);
+        }
+        return (point, vec![T::one() - t, t]);
+    }
+
+    let (p, q, r) = (simplex[0].w, simplex[1].w, simplex[2].w);
+    let area = (q - p).cross_product(r - p);
+    let d0 = (q - p).cross_product(-p) * area;
+    let d1 = (r - q).cross_product(-q) * area;
+    let d2 = (p - r).cross_product(-r) * area;
+    if d0 >= T::zero() && d1 >= T::zero() && d2 >= T::zero() && area != T::zero() {
+        return (Vector2d::new(T::zero(), T::zero()), Vec::new());
+    }
+
+    // The origin lies outside: keep the closest edge.
+    let mut best: Option<(T, [usize; 2], Vector2d<T>, T)> = None;
+    for pair in [[0, 1], [1, 2], [2, 0]] {
+        let (point, t) = closest_to_origin(simplex[pair[0]].w, simplex[pair[1]].w);
+        let d = point.dot_product(point);
+        if best.is_none_or(|(best_d, ..)| d < best_d) {
+            best = Some((d, pair, point, t));
+        }
+    }
+    let (_, pair, point, t) = best.unwrap();
+    let kept = [simplex[pair[0]], simplex[pair[1]]];
+    simplex.clear();
+    if t <= T::zero() {
+        simplex.push(kept[0]);
+        (point, vec![T::one()])
+    } else if t >= T::one() {
+        simplex.push(kept[1]);
+        (point, vec![T::one()])
+    } else {
+        simplex.extend_from_slice(&kept);
+        (point, vec![T::one() - t, t])
+    }
+}
+
+// Closest point to the origin on the segment from `p` to `q`.
+fn closest_to_origin<T>(p: Vector2d<T>, q: Vector2d<T>) -> (Vector2d<T>, T)
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    closest_on_segment(Vector2d::new(T::zero(), T::zero()), p, q)
+}
+
+fn run_epa<T, A, B>(a: &A, b: &B, mut polytope: Vec<MinkowskiVertex<T>>) -> Penetration<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    A: SupportMap<T> + ?Sized,
+    B: SupportMap<T> + ?Sized,
+{
+    let eps = tolerance::<T>();
+    // GJK may stop with the origin on a vertex or an edge; grow the simplex
+    // into a triangle with a non zero area first.
+    let directions = [
+        Vector2d::new(T::one(), T::zero()),
+        Vector2d::new(-T::one(), T::zero()),
+        Vector2d::new(T::zero(), T::one()),
+        Vector2d::new(T::zero(), -T::one()),
+    ];
+    for direction in directions {
+        if polytope.len() >= 3 {
+            break;
+        }
+        let direction = if polytope.len() == 2 {
+            let edge = polytope[1].w - polytope[0].w;
+            let normal = edge.perpendicular();
+            if direction.dot_product(normal) < T::zero() {
+                -normal
+            } else {
+                normal
+            }
+        } else {
+            direction
+        };
+        let vertex = minkowski_support(a, b, direction);
+        if polytope.iter().all(|p| p.w.distance(vertex.w) > eps) {
+            polytope.push(vertex);
+        }
+    }
+    if polytope.len() < 3 {
+        // Degenerate shapes (segments or points) touching at the origin.
+        let v = polytope[0];
+        return Penetration {
+            normal: Vector2d::new(T::one(), T::zero()),
+            depth: T::zero(),
+            point_a: v.a,
+            point_b: v.b,
+        };
+    }
+    let area = (polytope[1].w - polytope[0].w).cross_product(polytope[2].w - polytope[0].w);
+    if area < T::zero() {
+        polytope.swap(1, 2);
+    }
+
+    let mut result = None;
+    for _ in 0..MAX_EPA_ITERATIONS {
+        let n = polytope.len();
+        let mut best = (T::infinity(), 0, Vector2d::new(T::zero(), T::zero()));
+        for i in 0..n {
+            let edge = polytope[(i + 1) % n].w - polytope[i].w;
+            if let Ok(normal) = Vector2d::new(edge.y, -edge.x).normalize() {
+                let distance = normal.dot_product(polytope[i].w);
+                if distance < best.0 {
+                    best = (distance, i, normal);
+                }
+            }
+        }
+        let (distance, index, normal) = best;
+        result = Some((distance, index, normal));
+        let vertex = minkowski_support(a, b, normal);
+        if normal.dot_product(vertex.w) - distance <= eps * distance.max(T::one()) {
+            break;
+        }
+        polytope.insert(index + 1, vertex);
+    }
+
+    let (distance, index, normal) = result.unwrap();
+    let n = polytope.len();
+    let (p, q) = (polytope[index], polytope[(index + 1) % n]);
+    let (_, t) = closest_to_origin(p.w, q.w);
+    let point_a = p.a + (q.a - p.a) * t;
+    let point_b = p.b + (q.b - p.b) * t;
+    Penetration {
+        normal,
+        depth: distance,
+        point_a,
+        point_b,
+    }
+}
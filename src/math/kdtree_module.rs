@@ -0,0 +1,277 @@
+use crate::math::shape_module::Aabb;
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+use std::cmp::Ordering;
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        K-d Tree
+// -----------------------------------------
+
+/// A static, balanced 2-d tree over a fixed set of points.
+///
+/// The tree is implicit: the points are reordered so the median of every
+/// range is its node, splitting on `x` at even depths and `y` at odd depths.
+/// Queries report the index of the point in the slice given to `new`, along
+/// with its squared distance, and never allocate: the `_into` variants reuse
+/// the caller's buffer and the `for_each_` variants use a callback.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::kdtree_module::KdTree2d;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let points = vec![
+///     Vector2d::new(0.0, 0.0),
+///     Vector2d::new(5.0, 5.0),
+///     Vector2d::new(2.0, 1.0),
+/// ];
+/// let tree = KdTree2d::new(&points);
+/// assert_eq!(tree.nearest(Vector2d::new(2.5, 1.0)), Some((2, 0.25)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct KdTree2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    points: Vec<Vector2d<T>>,
+    indices: Vec<usize>,
+}
+
+impl<T> KdTree2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Builds the tree in `O(n log n)` by median selection.
+    pub fn new(points: &[Vector2d<T>]) -> Self {
+        let mut order: Vec<(Vector2d<T>, usize)> =
+            points.iter().copied().zip(0..points.len()).collect();
+        build(&mut order, 0);
+        let (points, indices) = order.into_iter().unzip();
+        Self { points, indices }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The closest point to `query` as `(index, squared distance)`.
+    pub fn nearest(&self, query: Vector2d<T>) -> Option<(usize, T)> {
+        let mut best: Option<(usize, T)> = None;
+        self.search_nearest(query, 0, self.len(), 0, &mut best);
+        best
+    }
+
+    /// Fills `found` with the `k` closest points as `(index, squared
+    /// distance)`, nearest first. `found` is cleared first.
+    pub fn k_nearest_into(&self, query: Vector2d<T>, k: usize, found: &mut Vec<(usize, T)>) {
+        found.clear();
+        let k = k.min(self.len());
+        if k == 0 {
+            return;
+        }
+        found.reserve(k + 1);
+        self.search_k_nearest(query, k, 0, self.len(), 0, found);
+    }
+
+    /// The `k` closest points as `(index, squared distance)`, nearest first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::kdtree_module::KdTree2d;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let points: Vec<Vector2d<f64>> = (0..10).map(|i| Vector2d::new(i as f64, 0.0)).collect();
+    /// let tree = KdTree2d::new(&points);
+    /// assert_eq!(tree.k_nearest(Vector2d::new(3.2, 0.0), 2)[1].0, 4);
+    /// ```
+    pub fn k_nearest(&self, query: Vector2d<T>, k: usize) -> Vec<(usize, T)> {
+        let mut found = Vec::with_capacity(k.min(self.len()) + 1);
+        self.k_nearest_into(query, k, &mut found);
+        found
+    }
+
+    /// Calls `visitor` with `(index, squared distance)` for every point within
+    /// `radius` of `center`, in no particular order.
+    pub fn for_each_within_radius<F>(&self, center: Vector2d<T>, radius: T, mut visitor: F)
+    where
+        F: FnMut(usize, T),
+    {
+        self.search_radius(center, radius * radius, 0, self.len(), 0, &mut visitor);
+    }
+
+    /// Appends `(index, squared distance)` for every point within `radius` of
+    /// `center` to `found`.
+    pub fn within_radius_into(&self, center: Vector2d<T>, radius: T, found: &mut Vec<(usize, T)>) {
+        self.for_each_within_radius(center, radius, |i, d| found.push((i, d)));
+    }
+
+    pub fn within_radius(&self, center: Vector2d<T>, radius: T) -> Vec<(usize, T)> {
+        let mut found = Vec::new();
+        self.within_radius_into(center, radius, &mut found);
+        found
+    }
+
+    /// Calls `visitor` with the index of every point inside `area`.
+    pub fn for_each_in_bbox<F>(&self, area: &Aabb<T>, mut visitor: F)
+    where
+        F: FnMut(usize),
+    {
+        self.search_bbox(area, 0, self.len(), 0, &mut visitor);
+    }
+
+    /// Appends the index of every point inside `area` to `found`.
+    pub fn in_bbox_into(&self, area: &Aabb<T>, found: &mut Vec<usize>) {
+        self.for_each_in_bbox(area, |i| found.push(i));
+    }
+
+    pub fn in_bbox(&self, area: &Aabb<T>) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.in_bbox_into(area, &mut found);
+        found
+    }
+
+    fn search_nearest(
+        &self,
+        query: Vector2d<T>,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        best: &mut Option<(usize, T)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let node = self.points[mid];
+        let distance = squared_distance(node, query);
+        if best.is_none_or(|(_, d)| distance < d) {
+            *best = Some((self.indices[mid], distance));
+        }
+        let diff = query[depth % 2] - node[depth % 2];
+        let (near, far) = halves(lo, mid, hi, diff);
+        self.search_nearest(query, near.0, near.1, depth + 1, best);
+        if best.is_none_or(|(_, d)| diff * diff < d) {
+            self.search_nearest(query, far.0, far.1, depth + 1, best);
+        }
+    }
+
+    fn search_k_nearest(
+        &self,
+        query: Vector2d<T>,
+        k: usize,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        found: &mut Vec<(usize, T)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let node = self.points[mid];
+        let distance = squared_distance(node, query);
+        if found.len() < k || distance < found[found.len() - 1].1 {
+            let at = found.partition_point(|&(_, d)| d <= distance);
+            found.insert(at, (self.indices[mid], distance));
+            found.truncate(k);
+        }
+        let diff = query[depth % 2] - node[depth % 2];
+        let (near, far) = halves(lo, mid, hi, diff);
+        self.search_k_nearest(query, k, near.0, near.1, depth + 1, found);
+        if found.len() < k || diff * diff < found[found.len() - 1].1 {
+            self.search_k_nearest(query, k, far.0, far.1, depth + 1, found);
+        }
+    }
+
+    fn search_radius<F>(
+        &self,
+        center: Vector2d<T>,
+        limit: T,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        visitor: &mut F,
+    ) where
+        F: FnMut(usize, T),
+    {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let node = self.points[mid];
+        let distance = squared_distance(node, center);
+        if distance <= limit {
+            visitor(self.indices[mid], distance);
+        }
+        let diff = center[depth % 2] - node[depth % 2];
+        let (near, far) = halves(lo, mid, hi, diff);
+        self.search_radius(center, limit, near.0, near.1, depth + 1, visitor);
+        if diff * diff <= limit {
+            self.search_radius(center, limit, far.0, far.1, depth + 1, visitor);
+        }
+    }
+
+    fn search_bbox<F>(&self, area: &Aabb<T>, lo: usize, hi: usize, depth: usize, visitor: &mut F)
+    where
+        F: FnMut(usize),
+    {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let node = self.points[mid];
+        if area.contains_point(node) {
+            visitor(self.indices[mid]);
+        }
+        let axis = depth % 2;
+        if area.min[axis] <= node[axis] {
+            self.search_bbox(area, lo, mid, depth + 1, visitor);
+        }
+        if area.max[axis] >= node[axis] {
+            self.search_bbox(area, mid + 1, hi, depth + 1, visitor);
+        }
+    }
+}
+
+fn build<T>(points: &mut [(Vector2d<T>, usize)], depth: usize)
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    if points.len() <= 1 {
+        return;
+    }
+    let axis = depth % 2;
+    let mid = points.len() / 2;
+    points.select_nth_unstable_by(mid, |a, b| {
+        a.0[axis].partial_cmp(&b.0[axis]).unwrap_or(Ordering::Equal)
+    });
+    let (left, right) = points.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+// The half containing the query first, then the other one.
+fn halves<T: Float>(lo: usize, mid: usize, hi: usize, diff: T) -> ((usize, usize), (usize, usize)) {
+    if diff < T::zero() {
+        ((lo, mid), (mid + 1, hi))
+    } else {
+        ((mid + 1, hi), (lo, mid))
+    }
+}
+
+fn squared_distance<T>(a: Vector2d<T>, b: Vector2d<T>) -> T
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let (dx, dy) = (a.x - b.x, a.y - b.y);
+    dx * dx + dy * dy
+}
@@ -1,2 +1,27 @@
 pub mod vector2d_module;
 pub mod vector2d_verbose_module;
+pub mod polygon_offset_module;
+pub mod polyline_module;
+pub mod sat_collision_module;
+pub mod shape_module;
+pub mod gjk_module;
+pub mod ccd_module;
+pub mod physics_world_module;
+pub mod particle_constraints_module;
+pub mod spatial_hash_module;
+pub mod quadtree_module;
+pub mod kdtree_module;
+pub mod aabb_tree_module;
+pub mod steering_module;
+pub mod pathfinding_module;
+pub mod random_vector_module;
+pub mod sampling_module;
+pub mod noise_module;
+pub mod vector_field_module;
+#[cfg(feature = "serde")]
+pub mod serde_module;
+pub mod binary_module;
+pub mod svg_path_module;
+pub mod svg_debug_module;
+pub mod raster_module;
+pub(crate) mod scalar_module;
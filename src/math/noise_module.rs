@@ -0,0 +1,448 @@
+use crate::math::scalar_module::{cast, vector_to_f64};
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+use rand::seq::SliceRandom;
+use rand::Rng;
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// Step of the central differences used by `Noise2d::gradient`.
+const GRADIENT_STEP: f64 = 1e-3;
+
+// -----------------------------------------
+//        Noise Trait
+// -----------------------------------------
+
+/// A scalar noise function of the plane.
+///
+/// Only `sample` has to be implemented; the gradient and its curl are derived
+/// from it by central differences.
+pub trait Noise2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn sample(&self, point: Vector2d<T>) -> T;
+
+    /// The gradient of the noise at `point`.
+    fn gradient(&self, point: Vector2d<T>) -> Vector2d<T> {
+        let h = cast::<T>(GRADIENT_STEP);
+        let dx = Vector2d::new(h, T::zero());
+        let dy = Vector2d::new(T::zero(), h);
+        Vector2d::new(
+            self.sample(point + dx) - self.sample(point - dx),
+            self.sample(point + dy) - self.sample(point - dy),
+        ) * (T::one() / (h + h))
+    }
+
+    /// The curl of the noise taken as a stream function: the gradient rotated
+    /// by -90°. The resulting flow is divergence free, which is what makes
+    /// curl-noise particles swirl without bunching up.
+    fn curl(&self, point: Vector2d<T>) -> Vector2d<T> {
+        let gradient = self.gradient(point);
+        Vector2d::new(gradient.y, -gradient.x)
+    }
+}
+
+impl<T, N> Noise2d<T> for &N
+where
+    T: Float + AddAssign + std::fmt::Display,
+    N: Noise2d<T> + ?Sized,
+{
+    fn sample(&self, point: Vector2d<T>) -> T {
+        (**self).sample(point)
+    }
+}
+
+// Seeded permutation table shared by the lattice noises.
+#[derive(Debug, Clone)]
+struct Permutation {
+    table: [u8; 512],
+}
+
+impl Permutation {
+    fn new<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut values: Vec<u8> = (0..=255).collect();
+        values.shuffle(rng);
+        let mut table = [0; 512];
+        for (i, slot) in table.iter_mut().enumerate() {
+            *slot = values[i & 255];
+        }
+        Self { table }
+    }
+
+    fn hash(&self, i: i64, j: i64) -> usize {
+        let a = self.table[(i & 255) as usize] as usize;
+        self.table[a + (j & 255) as usize] as usize
+    }
+}
+
+// -----------------------------------------
+//        Perlin Noise
+// -----------------------------------------
+
+/// Ken Perlin's improved gradient noise, scaled to roughly `[-1, 1]` and zero
+/// on every integer lattice point.
+///
+/// # Examples
+///
+/// ```
+/// use rand::SeedableRng;
+/// use vectorlib::math::noise_module::{Noise2d, Perlin};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let perlin = Perlin::new(&mut rand::rngs::StdRng::seed_from_u64(1));
+/// assert_eq!(perlin.sample(Vector2d::new(3.0, -2.0)), 0.0);
+/// let value: f64 = perlin.sample(Vector2d::new(3.5, -2.25));
+/// assert!(value.abs() <= 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Perlin {
+    permutation: Permutation,
+}
+
+impl Perlin {
+    /// Create a new `Perlin` noise whose lattice is shuffled by `rng`.
+    pub fn new<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            permutation: Permutation::new(rng),
+        }
+    }
+}
+
+impl<T> Noise2d<T> for Perlin
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn sample(&self, point: Vector2d<T>) -> T {
+        let Vector2d { x, y } = vector_to_f64(point);
+        let (i, j) = (x.floor(), y.floor());
+        let (fx, fy) = (x - i, y - j);
+        // The permutation repeats every 256 cells, so reducing first keeps
+        // the corner offsets from overflowing for far away samples.
+        let (i, j) = (i as i64 & 255, j as i64 & 255);
+        let fade = |t: f64| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+        let corner = |di: i64, dj: i64| {
+            let (dx, dy) = (fx - di as f64, fy - dj as f64);
+            match self.permutation.hash(i + di, j + dj) & 7 {
+                0 => dx + dy,
+                1 => dx - dy,
+                2 => -dx + dy,
+                3 => -dx - dy,
+                4 => dx,
+                5 => -dx,
+                6 => dy,
+                _ => -dy,
+            }
+        };
+        let (u, v) = (fade(fx), fade(fy));
+        let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+        let bottom = lerp(corner(0, 0), corner(1, 0), u);
+        let top = lerp(corner(0, 1), corner(1, 1), u);
+        cast((lerp(bottom, top, v)).clamp(-1.0, 1.0))
+    }
+}
+
+// -----------------------------------------
+//        OpenSimplex Noise
+// -----------------------------------------
+
+/// OpenSimplex2 style noise: gradient noise on a triangular lattice with 24
+/// gradient directions, which shows fewer axis aligned artefacts than Perlin
+/// noise. The output lies in `[-1, 1]`.
+#[derive(Debug, Clone)]
+pub struct OpenSimplex {
+    permutation: Permutation,
+}
+
+impl OpenSimplex {
+    /// Create a new `OpenSimplex` noise whose lattice is shuffled by `rng`.
+    pub fn new<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            permutation: Permutation::new(rng),
+        }
+    }
+}
+
+impl<T> Noise2d<T> for OpenSimplex
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn sample(&self, point: Vector2d<T>) -> T {
+        let skew = (3f64.sqrt() - 1.0) / 2.0;
+        let unskew = (3.0 - 3f64.sqrt()) / 6.0;
+        let Vector2d { x, y } = vector_to_f64(point);
+        let s = (x + y) * skew;
+        let (i, j) = ((x + s).floor(), (y + s).floor());
+        let t = (i + j) * unskew;
+        let (x0, y0) = (x - (i - t), y - (j - t));
+        let (i, j) = (i as i64 & 255, j as i64 & 255);
+        let (di, dj) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+        let corner = |ci: i64, cj: i64, dx: f64, dy: f64| {
+            let falloff = 0.5 - dx * dx - dy * dy;
+            if falloff <= 0.0 {
+                return 0.0;
+            }
+            let angle =
+                (self.permutation.hash(i + ci, j + cj) % 24) as f64 * std::f64::consts::PI / 12.0;
+            let falloff = falloff * falloff;
+            falloff * falloff * (angle.cos() * dx + angle.sin() * dy)
+        };
+        let value = corner(0, 0, x0, y0)
+            + corner(di, dj, x0 - di as f64 + unskew, y0 - dj as f64 + unskew)
+            + corner(1, 1, x0 - 1.0 + 2.0 * unskew, y0 - 1.0 + 2.0 * unskew);
+        cast((value * 99.2).clamp(-1.0, 1.0))
+    }
+}
+
+// -----------------------------------------
+//        Worley Noise
+// -----------------------------------------
+
+/// The distance measured from a sample to the Worley feature points.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WorleyDistance {
+    Euclidean,
+    Manhattan,
+    Chebyshev,
+}
+
+/// What a `Worley` noise returns, `F1` and `F2` being the distances to the
+/// nearest and second nearest feature points.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WorleyFeature {
+    F1,
+    F2,
+    F2MinusF1,
+}
+
+/// Worley (cellular) noise: the distance to jittered feature points, one per
+/// unit cell. `F2MinusF1` gives the classic cell borders.
+///
+/// # Examples
+///
+/// ```
+/// use rand::SeedableRng;
+/// use vectorlib::math::noise_module::{Noise2d, Worley, WorleyFeature};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let mut worley = Worley::new(&mut rand::rngs::StdRng::seed_from_u64(1));
+/// worley.feature = WorleyFeature::F2MinusF1;
+/// let value: f64 = worley.sample(Vector2d::new(0.3, 4.7));
+/// assert!(value >= 0.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Worley {
+    pub distance: WorleyDistance,
+    pub feature: WorleyFeature,
+    permutation: Permutation,
+    jitter: Vec<(f64, f64)>,
+}
+
+impl Worley {
+    /// Create a new Euclidean `F1` `Worley` noise seeded by `rng`.
+    pub fn new<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let permutation = Permutation::new(rng);
+        let jitter = (0..256).map(|_| (rng.gen(), rng.gen())).collect();
+        Self {
+            distance: WorleyDistance::Euclidean,
+            feature: WorleyFeature::F1,
+            permutation,
+            jitter,
+        }
+    }
+}
+
+impl<T> Noise2d<T> for Worley
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn sample(&self, point: Vector2d<T>) -> T {
+        let Vector2d { x, y } = vector_to_f64(point);
+        let (fx, fy) = (x - x.floor(), y - y.floor());
+        let (i, j) = (x.floor() as i64 & 255, y.floor() as i64 & 255);
+        let (mut f1, mut f2) = (f64::INFINITY, f64::INFINITY);
+        // Feature points are inside their cell, so the 5x5 block always
+        // contains the two nearest ones. Offsets are taken relative to the
+        // sample's cell so far away samples neither overflow nor lose
+        // precision.
+        for dj in -2..=2 {
+            for di in -2..=2 {
+                let (jx, jy) = self.jitter[self.permutation.hash(i + di, j + dj)];
+                let (dx, dy) = ((di as f64 + jx - fx).abs(), (dj as f64 + jy - fy).abs());
+                let d = match self.distance {
+                    WorleyDistance::Euclidean => (dx * dx + dy * dy).sqrt(),
+                    WorleyDistance::Manhattan => dx + dy,
+                    WorleyDistance::Chebyshev => dx.max(dy),
+                };
+                if d < f1 {
+                    f2 = f1;
+                    f1 = d;
+                } else if d < f2 {
+                    f2 = d;
+                }
+            }
+        }
+        cast(match self.feature {
+            WorleyFeature::F1 => f1,
+            WorleyFeature::F2 => f2,
+            WorleyFeature::F2MinusF1 => f2 - f1,
+        })
+    }
+}
+
+// -----------------------------------------
+//        Fractal Sums and Domain Warping
+// -----------------------------------------
+
+/// How the octaves of a `Fractal` are combined.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FractalMode {
+    /// Plain fractal Brownian motion.
+    Fbm,
+    /// The sum of absolute octaves, giving billowy creases.
+    Turbulence,
+}
+
+/// A sum of `octaves` copies of `noise`, each `lacunarity` times finer and
+/// `gain` times weaker than the previous one, normalised back to the range of
+/// a single octave.
+///
+/// # Examples
+///
+/// ```
+/// use rand::SeedableRng;
+/// use vectorlib::math::noise_module::{Fractal, Noise2d, Perlin};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let perlin = Perlin::new(&mut rand::rngs::StdRng::seed_from_u64(1));
+/// let fbm = Fractal::fbm(&perlin, 5);
+/// let value: f64 = fbm.sample(Vector2d::new(0.4, 1.3));
+/// assert!(value.abs() <= 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Fractal<T, N>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub noise: N,
+    pub mode: FractalMode,
+    pub octaves: usize,
+    pub lacunarity: T,
+    pub gain: T,
+}
+
+impl<T, N> Fractal<T, N>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    N: Noise2d<T>,
+{
+    /// Fractal Brownian motion with lacunarity 2 and gain 0.5.
+    pub fn fbm(noise: N, octaves: usize) -> Self {
+        Self {
+            noise,
+            mode: FractalMode::Fbm,
+            octaves,
+            lacunarity: cast(2.0),
+            gain: cast(0.5),
+        }
+    }
+
+    /// Turbulence with lacunarity 2 and gain 0.5; the output lies in `[0, 1]`.
+    pub fn turbulence(noise: N, octaves: usize) -> Self {
+        Self {
+            mode: FractalMode::Turbulence,
+            ..Self::fbm(noise, octaves)
+        }
+    }
+}
+
+impl<T, N> Noise2d<T> for Fractal<T, N>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    N: Noise2d<T>,
+{
+    fn sample(&self, point: Vector2d<T>) -> T {
+        let mut sum = T::zero();
+        let mut total = T::zero();
+        let mut amplitude = T::one();
+        let mut frequency = T::one();
+        for octave in 0..self.octaves {
+            // Shift every octave so that their lattices do not line up at the
+            // origin.
+            let offset = cast::<T>(octave as f64 * 17.31);
+            let value = self.noise.sample(point * frequency + offset);
+            sum += amplitude
+                * match self.mode {
+                    FractalMode::Fbm => value,
+                    FractalMode::Turbulence => value.abs(),
+                };
+            total += amplitude;
+            amplitude = amplitude * self.gain;
+            frequency = frequency * self.lacunarity;
+        }
+        if total > T::zero() {
+            sum / total
+        } else {
+            T::zero()
+        }
+    }
+}
+
+/// Domain warping: `noise` sampled at `point` displaced by `amplitude` times
+/// two decorrelated samples of `warp`.
+///
+/// # Examples
+///
+/// ```
+/// use rand::SeedableRng;
+/// use vectorlib::math::noise_module::{DomainWarp, Noise2d, Perlin};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+/// let warped = DomainWarp::new(Perlin::new(&mut rng), Perlin::new(&mut rng), 4.0);
+/// let value: f64 = warped.sample(Vector2d::new(0.5, 0.5));
+/// assert!(value.abs() <= 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DomainWarp<T, N, W>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub noise: N,
+    pub warp: W,
+    pub amplitude: T,
+}
+
+impl<T, N, W> DomainWarp<T, N, W>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    N: Noise2d<T>,
+    W: Noise2d<T>,
+{
+    pub fn new(noise: N, warp: W, amplitude: T) -> Self {
+        Self {
+            noise,
+            warp,
+            amplitude,
+        }
+    }
+
+    /// The displacement applied to `point` before sampling `noise`.
+    pub fn displacement(&self, point: Vector2d<T>) -> Vector2d<T> {
+        let shift = Vector2d::new(cast(5.2), cast(1.3));
+        Vector2d::new(self.warp.sample(point), self.warp.sample(point + shift)) * self.amplitude
+    }
+}
+
+impl<T, N, W> Noise2d<T> for DomainWarp<T, N, W>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    N: Noise2d<T>,
+    W: Noise2d<T>,
+{
+    fn sample(&self, point: Vector2d<T>) -> T {
+        self.noise.sample(point + self.displacement(point))
+    }
+}
@@ -0,0 +1,394 @@
+use crate::math::polyline_module::closest_on_segment;
+use crate::math::scalar_module::cast;
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        Particles
+// -----------------------------------------
+
+/// A Verlet particle: the velocity is implied by the difference between the
+/// current and the previous position.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Particle<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub position: Vector2d<T>,
+    pub previous_position: Vector2d<T>,
+    /// Zero for particles that are never moved by the solver.
+    pub inverse_mass: T,
+}
+
+impl<T> Particle<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create a particle at rest with the given `mass`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::particle_constraints_module::Particle;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let p = Particle::new(Vector2d::new(1.0, 2.0), 4.0);
+    /// assert_eq!(p.inverse_mass, 0.25);
+    /// ```
+    pub fn new(position: Vector2d<T>, mass: T) -> Self {
+        Self {
+            position,
+            previous_position: position,
+            inverse_mass: if mass > T::zero() {
+                T::one() / mass
+            } else {
+                T::zero()
+            },
+        }
+    }
+
+    /// Create a particle that never moves.
+    pub fn fixed(position: Vector2d<T>) -> Self {
+        Self {
+            position,
+            previous_position: position,
+            inverse_mass: T::zero(),
+        }
+    }
+
+    /// The displacement of the particle during the last substep.
+    pub fn displacement(&self) -> Vector2d<T> {
+        self.position - self.previous_position
+    }
+}
+
+// -----------------------------------------
+//        Constraints
+// -----------------------------------------
+
+/// A positional constraint between particles, referenced by index.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Constraint<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Keeps two particles `rest_length` apart. A `stiffness` of `1` is rigid,
+    /// smaller values give a springy link.
+    Distance {
+        a: usize,
+        b: usize,
+        rest_length: T,
+        stiffness: T,
+    },
+    /// Keeps the distance between two particles within `[min_length, max_length]`.
+    /// A rope is a `Rope` link with a zero `min_length`.
+    Rope {
+        a: usize,
+        b: usize,
+        min_length: T,
+        max_length: T,
+    },
+    /// Attaches a particle to a fixed world point.
+    Pin {
+        particle: usize,
+        target: Vector2d<T>,
+    },
+    /// Keeps the signed angle `a`-`center`-`c` (counter-clockwise from `a`
+    /// to `c`, in radians) within `[min_angle, max_angle]`.
+    Angle {
+        a: usize,
+        center: usize,
+        c: usize,
+        min_angle: T,
+        max_angle: T,
+    },
+}
+
+impl<T> Constraint<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn particles(&self) -> Vec<usize> {
+        match *self {
+            Constraint::Distance { a, b, .. } | Constraint::Rope { a, b, .. } => vec![a, b],
+            Constraint::Pin { particle, .. } => vec![particle],
+            Constraint::Angle { a, center, c, .. } => vec![a, center, c],
+        }
+    }
+}
+
+// -----------------------------------------
+//        Particle System
+// -----------------------------------------
+
+/// A position based dynamics solver over Verlet particles.
+///
+/// Every step is split into `substeps`; each substep integrates the
+/// particles, then projects the constraints `iterations` times and finally
+/// pushes the particles out of the static segments.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::particle_constraints_module::{Constraint, Particle, ParticleSystem};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let mut rope: ParticleSystem<f64> = ParticleSystem::new(Vector2d::new(0.0, -9.81));
+/// let anchor = rope.add_particle(Particle::fixed(Vector2d::new(0.0, 0.0)));
+/// let weight = rope.add_particle(Particle::new(Vector2d::new(1.0, 0.0), 1.0));
+/// rope.add_constraint(Constraint::Distance { a: anchor, b: weight, rest_length: 1.0, stiffness: 1.0 })
+///     .unwrap();
+///
+/// for _ in 0..120 {
+///     rope.step(1.0 / 60.0);
+/// }
+/// let length = rope.particles()[weight].position.magnitude();
+/// assert!((length - 1.0).abs() < 1e-3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParticleSystem<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub gravity: Vector2d<T>,
+    /// Constraint projection passes per substep.
+    pub iterations: usize,
+    /// Number of substeps each call to `step` is split into.
+    pub substeps: usize,
+    /// Fraction of the velocity kept at every substep (`1` keeps everything).
+    pub damping: T,
+    /// Radius of the particles when colliding against static segments.
+    pub collision_radius: T,
+    particles: Vec<Particle<T>>,
+    constraints: Vec<Constraint<T>>,
+    segments: Vec<(Vector2d<T>, Vector2d<T>)>,
+    /// Inverse masses used by the solver, zero for pinned particles.
+    weights: Vec<T>,
+}
+
+impl<T> ParticleSystem<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create an empty system with 8 iterations, 2 substeps and no damping.
+    pub fn new(gravity: Vector2d<T>) -> Self {
+        Self {
+            gravity,
+            iterations: 8,
+            substeps: 2,
+            damping: T::one(),
+            collision_radius: cast(0.01),
+            particles: Vec::new(),
+            constraints: Vec::new(),
+            segments: Vec::new(),
+            weights: Vec::new(),
+        }
+    }
+
+    /// Adds a particle and returns its index.
+    pub fn add_particle(&mut self, particle: Particle<T>) -> usize {
+        self.particles.push(particle);
+        self.particles.len() - 1
+    }
+
+    /// Adds a constraint and returns its index.
+    ///
+    /// # Returns
+    ///
+    /// The constraint index, or an `Err` when it references a missing particle.
+    pub fn add_constraint(&mut self, constraint: Constraint<T>) -> Result<usize, String> {
+        if let Some(missing) = constraint
+            .particles()
+            .into_iter()
+            .find(|&i| i >= self.particles.len())
+        {
+            return Err(format!(
+                "[::ERROR::] -> Constraint references a missing particle: {}",
+                missing
+            ));
+        }
+        self.constraints.push(constraint);
+        Ok(self.constraints.len() - 1)
+    }
+
+    /// Removes a constraint, for example to tear a cloth. The last constraint
+    /// takes the index of the removed one.
+    pub fn remove_constraint(&mut self, index: usize) -> Option<Constraint<T>> {
+        if index < self.constraints.len() {
+            Some(self.constraints.swap_remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Adds a static segment the particles collide against.
+    pub fn add_segment(&mut self, a: Vector2d<T>, b: Vector2d<T>) {
+        self.segments.push((a, b));
+    }
+
+    pub fn particles(&self) -> &[Particle<T>] {
+        &self.particles
+    }
+
+    pub fn particles_mut(&mut self) -> &mut [Particle<T>] {
+        &mut self.particles
+    }
+
+    pub fn constraints(&self) -> &[Constraint<T>] {
+        &self.constraints
+    }
+
+    /// Advances the simulation by `dt`.
+    pub fn step(&mut self, dt: T) {
+        let substeps = self.substeps.max(1);
+        let h = dt / T::from(substeps).unwrap();
+        self.refresh_weights();
+        for _ in 0..substeps {
+            self.integrate(h);
+            for _ in 0..self.iterations {
+                for i in 0..self.constraints.len() {
+                    let constraint = self.constraints[i];
+                    self.project(&constraint);
+                }
+            }
+            self.collide_segments();
+        }
+    }
+
+    // Pinned particles act as if they had an infinite mass, so the other
+    // constraints never drag them away from their target.
+    fn refresh_weights(&mut self) {
+        self.weights.clear();
+        self.weights
+            .extend(self.particles.iter().map(|p| p.inverse_mass));
+        for constraint in &self.constraints {
+            if let Constraint::Pin { particle, .. } = *constraint {
+                self.weights[particle] = T::zero();
+            }
+        }
+    }
+
+    fn integrate(&mut self, h: T) {
+        let gravity_step = self.gravity * (h * h);
+        for p in self.particles.iter_mut() {
+            if p.inverse_mass == T::zero() {
+                p.previous_position = p.position;
+                continue;
+            }
+            let velocity = p.displacement() * self.damping;
+            p.previous_position = p.position;
+            p.position += velocity + gravity_step;
+        }
+    }
+
+    fn project(&mut self, constraint: &Constraint<T>) {
+        match *constraint {
+            Constraint::Distance {
+                a,
+                b,
+                rest_length,
+                stiffness,
+            } => {
+                // Stiffness per iteration, so the result does not depend on
+                // the iteration count.
+                let iterations = T::from(self.iterations.max(1)).unwrap();
+                let k = T::one()
+                    - (T::one() - stiffness.max(T::zero()).min(T::one()))
+                        .powf(T::one() / iterations);
+                self.solve_distance(a, b, rest_length, k);
+            }
+            Constraint::Rope {
+                a,
+                b,
+                min_length,
+                max_length,
+            } => {
+                let d = self.particles[a]
+                    .position
+                    .distance(self.particles[b].position);
+                if d > max_length {
+                    self.solve_distance(a, b, max_length, T::one());
+                } else if d < min_length {
+                    self.solve_distance(a, b, min_length, T::one());
+                }
+            }
+            Constraint::Pin { particle, target } => {
+                self.particles[particle].position = target;
+            }
+            Constraint::Angle {
+                a,
+                center,
+                c,
+                min_angle,
+                max_angle,
+            } => {
+                let pivot = self.particles[center].position;
+                let u = self.particles[a].position - pivot;
+                let v = self.particles[c].position - pivot;
+                let angle = u.cross_product(v).atan2(u.dot_product(v));
+                let target = angle.max(min_angle).min(max_angle);
+                let delta = target - angle;
+                let (wa, wc) = (self.weights[a], self.weights[c]);
+                let total = wa + wc;
+                if delta == T::zero() || total == T::zero() {
+                    return;
+                }
+                self.particles[a].position = pivot + u.rotate(-delta * wa / total);
+                self.particles[c].position = pivot + v.rotate(delta * wc / total);
+            }
+        }
+    }
+
+    fn solve_distance(&mut self, a: usize, b: usize, rest_length: T, stiffness: T) {
+        let (wa, wb) = (self.weights[a], self.weights[b]);
+        let total = wa + wb;
+        let delta = self.particles[b].position - self.particles[a].position;
+        let d = delta.magnitude();
+        if total == T::zero() || d == T::zero() {
+            return;
+        }
+        let correction = delta * ((d - rest_length) / (d * total) * stiffness);
+        self.particles[a].position += correction * wa;
+        self.particles[b].position = self.particles[b].position - correction * wb;
+    }
+
+    fn collide_segments(&mut self) {
+        let radius = self.collision_radius;
+        for p in self
+            .particles
+            .iter_mut()
+            .filter(|p| p.inverse_mass > T::zero())
+        {
+            for &(a, b) in &self.segments {
+                let edge = b - a;
+                let Ok(normal) = edge.perpendicular().normalize() else {
+                    continue;
+                };
+                let side_before = normal.dot_product(p.previous_position - a);
+                let side_after = normal.dot_product(p.position - a);
+
+                // Crossed the segment during the substep: put it back on its side.
+                if side_before.signum() != side_after.signum() && side_before != T::zero() {
+                    let t = side_before / (side_before - side_after);
+                    let hit = p.previous_position + p.displacement() * t;
+                    let u = (hit - a).dot_product(edge) / edge.dot_product(edge);
+                    if u >= T::zero() && u <= T::one() {
+                        p.position = hit + normal * (radius * side_before.signum());
+                        continue;
+                    }
+                }
+
+                let (closest, _) = closest_on_segment(p.position, a, b);
+                let offset = p.position - closest;
+                let distance = offset.magnitude();
+                if distance < radius {
+                    let push = offset.normalize().unwrap_or(normal * side_before.signum());
+                    p.position = closest + push * radius;
+                }
+            }
+        }
+    }
+}
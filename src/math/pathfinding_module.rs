@@ -0,0 +1,639 @@
+use crate::math::scalar_module::cast;
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        Occupancy Grid
+// -----------------------------------------
+
+/// A 2D occupancy grid placed in the world: cell `(x, y)` covers
+/// `origin + (x, y) * cell_size` to `origin + (x + 1, y + 1) * cell_size`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OccupancyGrid<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    width: usize,
+    height: usize,
+    blocked: Vec<bool>,
+    pub origin: Vector2d<T>,
+    pub cell_size: T,
+}
+
+impl<T> OccupancyGrid<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create a grid with every cell walkable.
+    ///
+    /// # Returns
+    ///
+    /// The grid, or an `Err` when it has no cells or `cell_size` is not positive.
+    pub fn new(
+        width: usize,
+        height: usize,
+        origin: Vector2d<T>,
+        cell_size: T,
+    ) -> Result<Self, String> {
+        if width == 0 || height == 0 {
+            return Err("[::ERROR::] -> The grid needs at least one cell.".to_string());
+        }
+        if cell_size <= T::zero() {
+            return Err("[::ERROR::] -> The cell size must be positive.".to_string());
+        }
+        Ok(Self {
+            width,
+            height,
+            blocked: vec![false; width * height],
+            origin,
+            cell_size,
+        })
+    }
+
+    /// Builds a grid from rows of text where `#` marks a blocked cell. The
+    /// first row is `y = 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::pathfinding_module::OccupancyGrid;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let grid = OccupancyGrid::from_rows(&["..#", "..."], Vector2d::new(0.0, 0.0), 1.0).unwrap();
+    /// assert!(grid.is_blocked(2, 0));
+    /// assert!(grid.is_walkable(2, 1));
+    /// ```
+    pub fn from_rows(rows: &[&str], origin: Vector2d<T>, cell_size: T) -> Result<Self, String> {
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        let mut grid = Self::new(width, rows.len(), origin, cell_size)?;
+        for (y, row) in rows.iter().enumerate() {
+            if row.chars().count() != width {
+                return Err(format!(
+                    "[::ERROR::] -> Row {} has {} cells instead of {}.",
+                    y,
+                    row.chars().count(),
+                    width
+                ));
+            }
+            for (x, cell) in row.chars().enumerate() {
+                grid.set_blocked(x, y, cell == '#');
+            }
+        }
+        Ok(grid)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn set_blocked(&mut self, x: usize, y: usize, blocked: bool) {
+        if x < self.width && y < self.height {
+            self.blocked[y * self.width + x] = blocked;
+        }
+    }
+
+    /// Cells outside the grid count as blocked.
+    pub fn is_blocked(&self, x: usize, y: usize) -> bool {
+        x >= self.width || y >= self.height || self.blocked[y * self.width + x]
+    }
+
+    pub fn is_walkable(&self, x: usize, y: usize) -> bool {
+        !self.is_blocked(x, y)
+    }
+
+    /// The cell containing `point`, if it lies on the grid.
+    pub fn cell_at(&self, point: Vector2d<T>) -> Option<(usize, usize)> {
+        let local = (point - self.origin) * (T::one() / self.cell_size);
+        let (x, y) = (local.x.floor(), local.y.floor());
+        if x < T::zero() || y < T::zero() {
+            return None;
+        }
+        let (x, y) = (x.to_usize()?, y.to_usize()?);
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    /// The world position of the centre of a cell.
+    pub fn cell_center(&self, x: usize, y: usize) -> Vector2d<T> {
+        let half: T = cast(0.5);
+        self.origin
+            + Vector2d::new(
+                (T::from(x).unwrap() + half) * self.cell_size,
+                (T::from(y).unwrap() + half) * self.cell_size,
+            )
+    }
+
+    /// Shortest 8-connected path between the cells containing `start` and
+    /// `goal` using A*, as world positions: `start`, the centres of the
+    /// intermediate cells and `goal`. Diagonal moves may not cut corners.
+    ///
+    /// # Returns
+    ///
+    /// The path, or `None` when either end is blocked or no path exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::pathfinding_module::OccupancyGrid;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let grid = OccupancyGrid::from_rows(&[".#.", ".#.", "..."], Vector2d::new(0.0, 0.0), 1.0).unwrap();
+    /// let path = grid.astar(Vector2d::new(0.5, 0.5), Vector2d::new(2.5, 0.5)).unwrap();
+    /// assert_eq!(path.first(), Some(&Vector2d::new(0.5, 0.5)));
+    /// assert_eq!(path.last(), Some(&Vector2d::new(2.5, 0.5)));
+    /// // The wall in the middle column forces a detour through the last row.
+    /// assert!(path.iter().any(|p| p.y > 2.0));
+    /// ```
+    pub fn astar(&self, start: Vector2d<T>, goal: Vector2d<T>) -> Option<Vec<Vector2d<T>>> {
+        self.search(start, goal, |grid, cell, _, _, out| {
+            grid.neighbors(cell, out)
+        })
+    }
+
+    /// Same result as `astar` using Jump Point Search, which skips over the
+    /// symmetric runs of open cells. The waypoints are the jump points, so
+    /// straight runs are not subdivided.
+    pub fn jps(&self, start: Vector2d<T>, goal: Vector2d<T>) -> Option<Vec<Vector2d<T>>> {
+        self.search(start, goal, |grid, cell, parent, goal, out| {
+            grid.jump_successors(cell, parent, goal, out);
+        })
+    }
+
+    /// World distance from every cell to the nearest of the cells containing
+    /// `goals`, moving like `astar`. Indexed by `y * width + x`; blocked and
+    /// unreachable cells are `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::pathfinding_module::OccupancyGrid;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let grid = OccupancyGrid::from_rows(&["..#", "..#"], Vector2d::new(0.0, 0.0), 2.0).unwrap();
+    /// let distances = grid.distance_map(&[Vector2d::new(1.0, 1.0)]);
+    /// assert_eq!(distances[0], Some(0.0));
+    /// assert_eq!(distances[1], Some(2.0));
+    /// assert_eq!(distances[2], None);
+    /// ```
+    pub fn distance_map(&self, goals: &[Vector2d<T>]) -> Vec<Option<T>> {
+        let mut cost: Vec<Option<f64>> = vec![None; self.width * self.height];
+        let mut open = BinaryHeap::new();
+        for (x, y) in goals.iter().filter_map(|&goal| self.cell_at(goal)) {
+            if self.is_walkable(x, y) && cost[y * self.width + x].is_none() {
+                cost[y * self.width + x] = Some(0.0);
+                open.push(Reverse(Ranked(0.0, (x, y))));
+            }
+        }
+        let mut buffer = Vec::new();
+        while let Some(Reverse(Ranked(g, cell))) = open.pop() {
+            if cost[cell.1 * self.width + cell.0].is_some_and(|known| g > known) {
+                continue;
+            }
+            buffer.clear();
+            self.neighbors(cell, &mut buffer);
+            for &next in &buffer {
+                let tentative = g + octile(cell, next);
+                let slot = &mut cost[next.1 * self.width + next.0];
+                if slot.is_none_or(|known| tentative < known) {
+                    *slot = Some(tentative);
+                    open.push(Reverse(Ranked(tentative, next)));
+                }
+            }
+        }
+        cost.into_iter()
+            .map(|c| c.map(|c| cast::<T>(c) * self.cell_size))
+            .collect()
+    }
+
+    fn search<F>(
+        &self,
+        start: Vector2d<T>,
+        goal: Vector2d<T>,
+        mut successors: F,
+    ) -> Option<Vec<Vector2d<T>>>
+    where
+        F: FnMut(&Self, Cell, Option<Cell>, Cell, &mut Vec<Cell>),
+    {
+        let start_cell = self.cell_at(start)?;
+        let goal_cell = self.cell_at(goal)?;
+        if self.is_blocked(start_cell.0, start_cell.1) || self.is_blocked(goal_cell.0, goal_cell.1)
+        {
+            return None;
+        }
+        let mut open = BinaryHeap::new();
+        let mut cost: HashMap<Cell, f64> = HashMap::new();
+        let mut parent: HashMap<Cell, Cell> = HashMap::new();
+        let mut buffer = Vec::new();
+        cost.insert(start_cell, 0.0);
+        open.push(Reverse(Ranked(octile(start_cell, goal_cell), start_cell)));
+        while let Some(Reverse(Ranked(_, cell))) = open.pop() {
+            if cell == goal_cell {
+                let mut cells = vec![cell];
+                let mut current = cell;
+                while let Some(&previous) = parent.get(&current) {
+                    cells.push(previous);
+                    current = previous;
+                }
+                cells.reverse();
+                // Both ends in one cell: walk straight there.
+                if cells.len() < 2 {
+                    return Some(if start == goal {
+                        vec![start]
+                    } else {
+                        vec![start, goal]
+                    });
+                }
+                let mut path = vec![start];
+                path.extend(
+                    cells[1..cells.len() - 1]
+                        .iter()
+                        .map(|&(x, y)| self.cell_center(x, y)),
+                );
+                path.push(goal);
+                return Some(path);
+            }
+            let g = cost[&cell];
+            buffer.clear();
+            successors(
+                self,
+                cell,
+                parent.get(&cell).copied(),
+                goal_cell,
+                &mut buffer,
+            );
+            for &next in &buffer {
+                let tentative = g + octile(cell, next);
+                if cost.get(&next).is_none_or(|&known| tentative < known) {
+                    cost.insert(next, tentative);
+                    parent.insert(next, cell);
+                    open.push(Reverse(Ranked(tentative + octile(next, goal_cell), next)));
+                }
+            }
+        }
+        None
+    }
+
+    fn can_step(&self, (x, y): Cell, (dx, dy): (isize, isize)) -> bool {
+        let (Some(nx), Some(ny)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) else {
+            return false;
+        };
+        if self.is_blocked(nx, ny) {
+            return false;
+        }
+        // No corner cutting: both orthogonal cells of a diagonal must be open.
+        dx == 0 || dy == 0 || (self.is_walkable(nx, y) && self.is_walkable(x, ny))
+    }
+
+    fn neighbors(&self, cell: Cell, out: &mut Vec<Cell>) {
+        for (dx, dy) in DIRECTIONS {
+            if self.can_step(cell, (dx, dy)) {
+                out.push(offset(cell, (dx, dy)));
+            }
+        }
+    }
+
+    fn jump_successors(&self, cell: Cell, parent: Option<Cell>, goal: Cell, out: &mut Vec<Cell>) {
+        for direction in self.pruned_directions(cell, parent) {
+            if let Some(jump) = self.jump(cell, direction, goal) {
+                out.push(jump);
+            }
+        }
+    }
+
+    // Directions worth scanning from a jump point. Without corner cutting a
+    // straight move can turn at any open side cell, so the sides and the
+    // forward diagonals are kept; `jump` discards the blocked ones.
+    fn pruned_directions(&self, cell: Cell, parent: Option<Cell>) -> Vec<(isize, isize)> {
+        let Some(parent) = parent else {
+            return DIRECTIONS.to_vec();
+        };
+        let dx = (cell.0 as isize - parent.0 as isize).signum();
+        let dy = (cell.1 as isize - parent.1 as isize).signum();
+        if dx != 0 && dy != 0 {
+            vec![(dx, 0), (0, dy), (dx, dy)]
+        } else if dx != 0 {
+            vec![(dx, 0), (0, 1), (0, -1), (dx, 1), (dx, -1)]
+        } else {
+            vec![(0, dy), (1, 0), (-1, 0), (1, dy), (-1, dy)]
+        }
+    }
+
+    fn jump(&self, mut cell: Cell, (dx, dy): (isize, isize), goal: Cell) -> Option<Cell> {
+        loop {
+            if !self.can_step(cell, (dx, dy)) {
+                return None;
+            }
+            cell = offset(cell, (dx, dy));
+            if cell == goal {
+                return Some(cell);
+            }
+            if dx != 0 && dy != 0 {
+                // Diagonal: stop when a straight scan finds something.
+                if self.jump(cell, (dx, 0), goal).is_some()
+                    || self.jump(cell, (0, dy), goal).is_some()
+                {
+                    return Some(cell);
+                }
+            } else if dx != 0 {
+                for side in [-1, 1] {
+                    if !self.can_step(cell, (-dx, side)) && self.can_step(cell, (0, side)) {
+                        return Some(cell);
+                    }
+                }
+            } else {
+                for side in [-1, 1] {
+                    if !self.can_step(cell, (side, -dy)) && self.can_step(cell, (side, 0)) {
+                        return Some(cell);
+                    }
+                }
+            }
+        }
+    }
+}
+
+type Cell = (usize, usize);
+
+const DIRECTIONS: [(isize, isize); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+fn offset((x, y): Cell, (dx, dy): (isize, isize)) -> Cell {
+    (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy))
+}
+
+// Octile distance in cells: the exact cost of an 8-connected move sequence
+// on an empty grid.
+fn octile(a: Cell, b: Cell) -> f64 {
+    let dx = a.0.abs_diff(b.0) as f64;
+    let dy = a.1.abs_diff(b.1) as f64;
+    dx.max(dy) + (std::f64::consts::SQRT_2 - 1.0) * dx.min(dy)
+}
+
+// Heap entry ordered by its score only.
+#[derive(Debug, Copy, Clone)]
+struct Ranked<V>(f64, V);
+
+impl<V> PartialEq for Ranked<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<V> Eq for Ranked<V> {}
+
+impl<V> PartialOrd for Ranked<V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<V> Ord for Ranked<V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+// -----------------------------------------
+//        Navigation Mesh
+// -----------------------------------------
+
+/// A navigation mesh made of convex polygons sharing vertices. Polygons are
+/// neighbours when they share an edge (two consecutive vertices).
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavMesh<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    vertices: Vec<Vector2d<T>>,
+    polygons: Vec<Vec<usize>>,
+    /// For every polygon, `(neighbour, left vertex, right vertex)` of each
+    /// shared edge, oriented as seen when leaving the polygon.
+    links: Vec<Vec<(usize, usize, usize)>>,
+}
+
+impl<T> NavMesh<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create a mesh from shared `vertices` and convex `polygons` listed as
+    /// vertex indices in counter-clockwise order.
+    ///
+    /// # Returns
+    ///
+    /// The mesh, or an `Err` when a polygon has fewer than three vertices or
+    /// references a missing one.
+    pub fn new(vertices: Vec<Vector2d<T>>, polygons: Vec<Vec<usize>>) -> Result<Self, String> {
+        for (index, polygon) in polygons.iter().enumerate() {
+            if polygon.len() < 3 {
+                return Err(format!(
+                    "[::ERROR::] -> Polygon {} has fewer than 3 vertices.",
+                    index
+                ));
+            }
+            if let Some(&missing) = polygon.iter().find(|&&v| v >= vertices.len()) {
+                return Err(format!(
+                    "[::ERROR::] -> Polygon {} references the missing vertex {}.",
+                    index, missing
+                ));
+            }
+        }
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+        for (index, polygon) in polygons.iter().enumerate() {
+            for i in 0..polygon.len() {
+                edges.insert((polygon[i], polygon[(i + 1) % polygon.len()]), index);
+            }
+        }
+        let links = polygons
+            .iter()
+            .map(|polygon| {
+                (0..polygon.len())
+                    .filter_map(|i| {
+                        let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+                        // Counter-clockwise winding: walking out through the
+                        // edge a -> b, `b` is on the left and `a` on the right.
+                        edges.get(&(b, a)).map(|&neighbour| (neighbour, b, a))
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Self {
+            vertices,
+            polygons,
+            links,
+        })
+    }
+
+    pub fn polygon_count(&self) -> usize {
+        self.polygons.len()
+    }
+
+    /// The index of the polygon containing `point`.
+    pub fn polygon_at(&self, point: Vector2d<T>) -> Option<usize> {
+        self.polygons.iter().position(|polygon| {
+            (0..polygon.len()).all(|i| {
+                let a = self.vertices[polygon[i]];
+                let b = self.vertices[polygon[(i + 1) % polygon.len()]];
+                (b - a).cross_product(point - a) >= -T::epsilon()
+            })
+        })
+    }
+
+    /// Shortest path from `start` to `goal` across the mesh: A* over the
+    /// polygons (between edge midpoints) followed by the funnel algorithm to
+    /// pull the path tight around the corners.
+    ///
+    /// # Returns
+    ///
+    /// The waypoints from `start` to `goal`, or `None` when either point is
+    /// off the mesh or the polygons are not connected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::pathfinding_module::NavMesh;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// // An L-shaped corridor made of three squares.
+    /// let vertices = vec![
+    ///     Vector2d::new(0.0, 0.0), Vector2d::new(1.0, 0.0), Vector2d::new(2.0, 0.0),
+    ///     Vector2d::new(0.0, 1.0), Vector2d::new(1.0, 1.0), Vector2d::new(2.0, 1.0),
+    ///     Vector2d::new(1.0, 2.0), Vector2d::new(2.0, 2.0),
+    /// ];
+    /// let mesh = NavMesh::new(vertices, vec![vec![0, 1, 4, 3], vec![1, 2, 5, 4], vec![4, 5, 7, 6]]).unwrap();
+    /// let path = mesh.find_path(Vector2d::new(0.5, 0.5), Vector2d::new(1.5, 1.5)).unwrap();
+    /// assert_eq!(path, vec![Vector2d::new(0.5, 0.5), Vector2d::new(1.0, 1.0), Vector2d::new(1.5, 1.5)]);
+    /// ```
+    pub fn find_path(&self, start: Vector2d<T>, goal: Vector2d<T>) -> Option<Vec<Vector2d<T>>> {
+        let from = self.polygon_at(start)?;
+        let to = self.polygon_at(goal)?;
+        let corridor = self.polygon_corridor(from, to, start, goal)?;
+        let mut portals = vec![(start, start)];
+        for pair in corridor.windows(2) {
+            let &(_, left, right) = self.links[pair[0]].iter().find(|link| link.0 == pair[1])?;
+            portals.push((self.vertices[left], self.vertices[right]));
+        }
+        portals.push((goal, goal));
+        Some(string_pull(&portals))
+    }
+
+    // A* over polygons, with the search nodes placed on the portal midpoints.
+    fn polygon_corridor(
+        &self,
+        from: usize,
+        to: usize,
+        start: Vector2d<T>,
+        goal: Vector2d<T>,
+    ) -> Option<Vec<usize>> {
+        let distance = |a: Vector2d<T>, b: Vector2d<T>| a.distance(b).to_f64().unwrap_or(f64::MAX);
+        let mut open = BinaryHeap::new();
+        let mut cost: HashMap<usize, (f64, Vector2d<T>)> = HashMap::new();
+        let mut parent: HashMap<usize, usize> = HashMap::new();
+        cost.insert(from, (0.0, start));
+        open.push(Reverse(Ranked(distance(start, goal), from)));
+        while let Some(Reverse(Ranked(_, polygon))) = open.pop() {
+            if polygon == to {
+                let mut corridor = vec![to];
+                while let Some(&previous) = parent.get(corridor.last()?) {
+                    corridor.push(previous);
+                }
+                corridor.reverse();
+                return Some(corridor);
+            }
+            let (g, position) = cost[&polygon];
+            for &(neighbour, left, right) in &self.links[polygon] {
+                let midpoint = (self.vertices[left] + self.vertices[right]) * cast::<T>(0.5);
+                let mut tentative = g + distance(position, midpoint);
+                if neighbour == to {
+                    tentative += distance(midpoint, goal);
+                }
+                if cost
+                    .get(&neighbour)
+                    .is_none_or(|&(known, _)| tentative < known)
+                {
+                    cost.insert(neighbour, (tentative, midpoint));
+                    parent.insert(neighbour, polygon);
+                    let heuristic = if neighbour == to {
+                        0.0
+                    } else {
+                        distance(midpoint, goal)
+                    };
+                    open.push(Reverse(Ranked(tentative + heuristic, neighbour)));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The simple stupid funnel algorithm: shortest path through a sequence of
+/// `(left, right)` portals whose first and last entries are the degenerate
+/// start and goal portals.
+pub fn string_pull<T>(portals: &[(Vector2d<T>, Vector2d<T>)]) -> Vec<Vector2d<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let Some(&(start, _)) = portals.first() else {
+        return Vec::new();
+    };
+    // Twice the signed area of the triangle a, b, c.
+    let area = |a: Vector2d<T>, b: Vector2d<T>, c: Vector2d<T>| (b - a).cross_product(c - a);
+    let mut path = vec![start];
+    let (mut apex, mut left, mut right) = (start, start, start);
+    let (mut left_index, mut right_index) = (0, 0);
+    let mut i = 1;
+    while i < portals.len() {
+        let (new_left, new_right) = portals[i];
+
+        // Tighten the right side of the funnel.
+        if area(apex, right, new_right) >= T::zero() {
+            if apex == right || area(apex, left, new_right) < T::zero() {
+                right = new_right;
+                right_index = i;
+            } else {
+                // The right side crossed the left one: the left is a corner.
+                apex = left;
+                path.push(apex);
+                right = apex;
+                right_index = left_index;
+                i = left_index + 1;
+                continue;
+            }
+        }
+
+        // Tighten the left side of the funnel.
+        if area(apex, left, new_left) <= T::zero() {
+            if apex == left || area(apex, right, new_left) > T::zero() {
+                left = new_left;
+                left_index = i;
+            } else {
+                apex = right;
+                path.push(apex);
+                left = apex;
+                left_index = right_index;
+                i = right_index + 1;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    let goal = portals[portals.len() - 1].0;
+    if path.last() != Some(&goal) {
+        path.push(goal);
+    }
+    path
+}
@@ -0,0 +1,787 @@
+use crate::math::polygon_offset_module::signed_area;
+use crate::math::polyline_module::closest_on_segment;
+use crate::math::sat_collision_module::{sat_manifold, ContactManifold, ContactPoint};
+use crate::math::scalar_module::{cast, pi};
+use crate::math::shape_module::{Aabb, ConvexPolygon};
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+use std::collections::HashMap;
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        Rigid Bodies
+// -----------------------------------------
+
+/// The collision shape of a rigid body, expressed in body space with the
+/// center of mass at the origin.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColliderShape<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    Circle { radius: T },
+    Polygon(ConvexPolygon<T>),
+}
+
+/// A rigid body simulated by [`World2d`].
+///
+/// A body with a zero `inverse_mass` and `inverse_inertia` is static: it is
+/// never moved by gravity, forces or contacts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RigidBody<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub position: Vector2d<T>,
+    pub velocity: Vector2d<T>,
+    pub angle: T,
+    pub angular_velocity: T,
+    pub mass: T,
+    pub inverse_mass: T,
+    pub inertia: T,
+    pub inverse_inertia: T,
+    pub restitution: T,
+    pub friction: T,
+    pub shape: ColliderShape<T>,
+    force: Vector2d<T>,
+    torque: T,
+}
+
+impl<T> RigidBody<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create a dynamic circle body with the given `density`.
+    ///
+    /// # Returns
+    ///
+    /// An `Err` unless `radius` and `density` are positive and finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::physics_world_module::RigidBody;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let ball = RigidBody::circle(Vector2d::new(0.0, 10.0), 0.5, 1.0).unwrap();
+    /// assert!((ball.mass - std::f64::consts::PI * 0.25).abs() < 1e-12);
+    /// assert!(RigidBody::circle(Vector2d::new(0.0, 10.0), 0.0, 1.0).is_err());
+    /// ```
+    pub fn circle(position: Vector2d<T>, radius: T, density: T) -> Result<Self, String> {
+        if !(radius > T::zero() && radius.is_finite()) {
+            return Err(format!(
+                "[::ERROR::] -> A circle body needs a positive radius, got {}.",
+                radius
+            ));
+        }
+        check_density(density)?;
+        let mass = density * pi::<T>() * radius * radius;
+        let inertia = mass * radius * radius / cast(2.0);
+        Ok(Self::with_mass(
+            position,
+            ColliderShape::Circle { radius },
+            mass,
+            inertia,
+        ))
+    }
+
+    /// Create a dynamic convex polygon body with the given `density`.
+    ///
+    /// `vertices` are given in world space; the body position is placed at
+    /// their centroid.
+    ///
+    /// # Returns
+    ///
+    /// The body, or an `Err` when the polygon has less than three vertices or
+    /// no area, or `density` is not positive and finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::physics_world_module::RigidBody;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let plank = RigidBody::polygon(
+    ///     &[
+    ///         Vector2d::new(0.0, 0.0),
+    ///         Vector2d::new(4.0, 0.0),
+    ///         Vector2d::new(4.0, 1.0),
+    ///         Vector2d::new(0.0, 1.0),
+    ///     ],
+    ///     2.0,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(plank.position, Vector2d::new(2.0, 0.5));
+    /// assert_eq!(plank.mass, 8.0);
+    /// ```
+    pub fn polygon(vertices: &[Vector2d<T>], density: T) -> Result<Self, String> {
+        check_density(density)?;
+        let area = signed_area(vertices).abs();
+        if vertices.len() < 3 || area == T::zero() {
+            return Err(
+                "[::ERROR::] -> A polygon body needs at least 3 vertices and a non zero area."
+                    .to_string(),
+            );
+        }
+        let centroid = polygon_centroid(vertices);
+        let local: Vec<Vector2d<T>> = vertices.iter().map(|&v| v - centroid).collect();
+        let polygon = ConvexPolygon::new(local)?;
+
+        // Second moment of area of the fan of triangles around the centroid.
+        let vs = polygon.vertices();
+        let n = vs.len();
+        let mut second_moment = T::zero();
+        for i in 0..n {
+            let (p, q) = (vs[i], vs[(i + 1) % n]);
+            let cross = p.cross_product(q).abs();
+            second_moment += cross * (p.dot_product(p) + p.dot_product(q) + q.dot_product(q));
+        }
+        let mass = density * area;
+        let inertia = density * second_moment / cast(12.0);
+        Ok(Self::with_mass(
+            centroid,
+            ColliderShape::Polygon(polygon),
+            mass,
+            inertia,
+        ))
+    }
+
+    /// Create an axis aligned box body centred on `position`.
+    ///
+    /// # Returns
+    ///
+    /// An `Err` unless both half extents and `density` are positive and
+    /// finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::physics_world_module::RigidBody;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let crate_body =
+    ///     RigidBody::rectangle(Vector2d::new(0.0, 1.0), Vector2d::new(0.5, 0.5), 1.0).unwrap();
+    /// assert_eq!(crate_body.mass, 1.0);
+    /// assert!(RigidBody::rectangle(Vector2d::new(0.0, 1.0), Vector2d::new(0.5, 0.0), 1.0).is_err());
+    /// ```
+    pub fn rectangle(
+        position: Vector2d<T>,
+        half_extents: Vector2d<T>,
+        density: T,
+    ) -> Result<Self, String> {
+        let valid = |extent: T| extent > T::zero() && extent.is_finite();
+        if !(valid(half_extents.x) && valid(half_extents.y)) {
+            return Err(format!(
+                "[::ERROR::] -> A box body needs positive half extents, got {}.",
+                half_extents
+            ));
+        }
+        let corners = Aabb::from_center(position, half_extents).corners();
+        Self::polygon(&corners, density)
+    }
+
+    /// Turns the body into a static body with infinite mass.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::physics_world_module::RigidBody;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let ground = RigidBody::rectangle(Vector2d::new(0.0, -1.0), Vector2d::new(50.0, 1.0), 1.0)
+    ///     .unwrap()
+    ///     .into_static();
+    /// assert!(ground.is_static());
+    /// ```
+    pub fn into_static(mut self) -> Self {
+        self.mass = T::zero();
+        self.inverse_mass = T::zero();
+        self.inertia = T::zero();
+        self.inverse_inertia = T::zero();
+        self.velocity = Vector2d::new(T::zero(), T::zero());
+        self.angular_velocity = T::zero();
+        self
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.inverse_mass == T::zero() && self.inverse_inertia == T::zero()
+    }
+
+    /// Accumulates a force applied at the center of mass until the next step.
+    pub fn apply_force(&mut self, force: Vector2d<T>) {
+        self.force += force;
+    }
+
+    /// Accumulates a force applied at the world point `point` until the next step.
+    pub fn apply_force_at_point(&mut self, force: Vector2d<T>, point: Vector2d<T>) {
+        self.force += force;
+        self.torque += (point - self.position).cross_product(force);
+    }
+
+    /// Accumulates a torque until the next step.
+    pub fn apply_torque(&mut self, torque: T) {
+        self.torque += torque;
+    }
+
+    /// Immediately changes the velocities by an impulse applied at the world point `point`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::physics_world_module::RigidBody;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let mut body =
+    ///     RigidBody::rectangle(Vector2d::new(0.0, 0.0), Vector2d::new(1.0, 1.0), 0.25).unwrap();
+    /// body.apply_impulse(Vector2d::new(2.0, 0.0), body.position);
+    /// assert_eq!(body.velocity, Vector2d::new(2.0, 0.0));
+    /// assert_eq!(body.angular_velocity, 0.0);
+    /// ```
+    pub fn apply_impulse(&mut self, impulse: Vector2d<T>, point: Vector2d<T>) {
+        self.velocity += impulse * self.inverse_mass;
+        self.angular_velocity +=
+            (point - self.position).cross_product(impulse) * self.inverse_inertia;
+    }
+
+    /// The velocity of the body at the world point `point`.
+    pub fn velocity_at(&self, point: Vector2d<T>) -> Vector2d<T> {
+        self.velocity + (point - self.position).perpendicular() * self.angular_velocity
+    }
+
+    /// The polygon vertices in world space, or an empty list for circles.
+    pub fn world_vertices(&self) -> Vec<Vector2d<T>> {
+        match &self.shape {
+            ColliderShape::Circle { .. } => Vec::new(),
+            ColliderShape::Polygon(polygon) => polygon
+                .vertices()
+                .iter()
+                .map(|&v| v.rotate(self.angle) + self.position)
+                .collect(),
+        }
+    }
+
+    /// The world space bounding box of the body.
+    pub fn aabb(&self) -> Aabb<T> {
+        match &self.shape {
+            ColliderShape::Circle { radius } => {
+                Aabb::from_center(self.position, Vector2d::new(*radius, *radius))
+            }
+            ColliderShape::Polygon(_) => {
+                let vertices = self.world_vertices();
+                let mut aabb = Aabb::new(vertices[0], vertices[0]);
+                for &v in &vertices[1..] {
+                    aabb = Aabb::new(
+                        Vector2d::new(aabb.min.x.min(v.x), aabb.min.y.min(v.y)),
+                        Vector2d::new(aabb.max.x.max(v.x), aabb.max.y.max(v.y)),
+                    );
+                }
+                aabb
+            }
+        }
+    }
+
+    fn with_mass(position: Vector2d<T>, shape: ColliderShape<T>, mass: T, inertia: T) -> Self {
+        let invert = |value: T| {
+            if value > T::zero() {
+                T::one() / value
+            } else {
+                T::zero()
+            }
+        };
+        Self {
+            position,
+            velocity: Vector2d::new(T::zero(), T::zero()),
+            angle: T::zero(),
+            angular_velocity: T::zero(),
+            mass,
+            inverse_mass: invert(mass),
+            inertia,
+            inverse_inertia: invert(inertia),
+            restitution: cast(0.2),
+            friction: cast(0.5),
+            shape,
+            force: Vector2d::new(T::zero(), T::zero()),
+            torque: T::zero(),
+        }
+    }
+}
+
+// -----------------------------------------
+//        Physics World
+// -----------------------------------------
+
+/// A handle to a body stored in a [`World2d`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BodyHandle(usize);
+
+/// A contact between two bodies found during the last step.
+///
+/// The manifold normal points from `body_a` towards `body_b`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BodyContact<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub body_a: BodyHandle,
+    pub body_b: BodyHandle,
+    pub manifold: ContactManifold<T>,
+}
+
+/// A minimal rigid body world with gravity, circle and convex polygon
+/// collisions and a sequential impulse contact solver.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::physics_world_module::{RigidBody, World2d};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let mut world: World2d<f64> = World2d::new(Vector2d::new(0.0, -9.81));
+/// world.add_body(
+///     RigidBody::rectangle(Vector2d::new(0.0, -1.0), Vector2d::new(10.0, 1.0), 1.0)
+///         .unwrap()
+///         .into_static(),
+/// );
+/// let ball = world.add_body(RigidBody::circle(Vector2d::new(0.0, 3.0), 0.5, 1.0).unwrap());
+///
+/// for _ in 0..240 {
+///     world.step(1.0 / 60.0);
+/// }
+/// let resting = world.body(ball).unwrap();
+/// assert!((resting.position.y - 0.5).abs() < 0.05);
+/// ```
+#[derive(Debug, Clone)]
+pub struct World2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub gravity: Vector2d<T>,
+    /// Number of sequential impulse iterations per step.
+    pub iterations: usize,
+    /// Fraction of the penetration removed by the position correction each step.
+    pub correction_percent: T,
+    /// Penetration allowed without position correction, to avoid jitter.
+    pub penetration_slop: T,
+    /// Approach speed below which contacts do not bounce, so resting bodies
+    /// settle instead of jittering.
+    pub restitution_threshold: T,
+    bodies: Vec<RigidBody<T>>,
+    contacts: Vec<BodyContact<T>>,
+    /// Impulses of the last step keyed by body pair and contact index, used
+    /// to warm start the solver.
+    warm_start: HashMap<(usize, usize, usize), (T, T)>,
+    accumulator: T,
+}
+
+impl<T> World2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create an empty world with the given `gravity`.
+    pub fn new(gravity: Vector2d<T>) -> Self {
+        Self {
+            gravity,
+            iterations: 10,
+            correction_percent: cast(0.4),
+            penetration_slop: cast(0.01),
+            restitution_threshold: T::one(),
+            bodies: Vec::new(),
+            contacts: Vec::new(),
+            warm_start: HashMap::new(),
+            accumulator: T::zero(),
+        }
+    }
+
+    /// Adds a body to the world and returns its handle.
+    pub fn add_body(&mut self, body: RigidBody<T>) -> BodyHandle {
+        self.bodies.push(body);
+        BodyHandle(self.bodies.len() - 1)
+    }
+
+    pub fn body(&self, handle: BodyHandle) -> Option<&RigidBody<T>> {
+        self.bodies.get(handle.0)
+    }
+
+    pub fn body_mut(&mut self, handle: BodyHandle) -> Option<&mut RigidBody<T>> {
+        self.bodies.get_mut(handle.0)
+    }
+
+    /// Iterates over every body with its handle.
+    pub fn bodies(&self) -> impl Iterator<Item = (BodyHandle, &RigidBody<T>)> {
+        self.bodies
+            .iter()
+            .enumerate()
+            .map(|(i, b)| (BodyHandle(i), b))
+    }
+
+    /// The contacts found during the last step.
+    pub fn contacts(&self) -> &[BodyContact<T>] {
+        &self.contacts
+    }
+
+    /// Advances the simulation by `elapsed` time using as many fixed steps of
+    /// `time_step` as fit, keeping the remainder for the next call.
+    ///
+    /// # Returns
+    ///
+    /// The number of fixed steps taken. A `time_step` that is not positive
+    /// and finite takes no steps and leaves the world untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::physics_world_module::World2d;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let mut world: World2d<f64> = World2d::new(Vector2d::new(0.0, -9.81));
+    /// assert_eq!(world.advance(0.05, 0.02), 2);
+    /// assert_eq!(world.advance(0.01, 0.02), 1);
+    /// assert_eq!(world.advance(0.05, 0.0), 0);
+    /// ```
+    pub fn advance(&mut self, elapsed: T, time_step: T) -> usize {
+        if !(time_step > T::zero() && time_step.is_finite()) {
+            return 0;
+        }
+        self.accumulator += elapsed;
+        let mut steps = 0;
+        let epsilon = time_step * cast(1e-9);
+        while self.accumulator + epsilon >= time_step {
+            self.step(time_step);
+            self.accumulator = self.accumulator - time_step;
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Advances the simulation by a single step of length `dt`.
+    pub fn step(&mut self, dt: T) {
+        // Integrate forces.
+        for body in self.bodies.iter_mut().filter(|b| !b.is_static()) {
+            let acceleration = self.gravity + body.force * body.inverse_mass;
+            body.velocity += acceleration * dt;
+            body.angular_velocity += body.torque * body.inverse_inertia * dt;
+            body.force = Vector2d::new(T::zero(), T::zero());
+            body.torque = T::zero();
+        }
+
+        self.contacts = self.find_contacts();
+        let mut constraints = self.prepare_constraints();
+
+        for constraint in &constraints {
+            let impulse = constraint.normal * constraint.normal_impulse
+                + constraint.tangent * constraint.tangent_impulse;
+            self.apply_pair_impulse(constraint, impulse);
+        }
+        for _ in 0..self.iterations {
+            for constraint in constraints.iter_mut() {
+                self.solve_constraint(constraint);
+            }
+        }
+        self.warm_start = constraints
+            .iter()
+            .map(|c| ((c.a, c.b, c.index), (c.normal_impulse, c.tangent_impulse)))
+            .collect();
+
+        // Integrate velocities.
+        for body in self.bodies.iter_mut().filter(|b| !b.is_static()) {
+            body.position += body.velocity * dt;
+            body.angle += body.angular_velocity * dt;
+        }
+
+        self.correct_positions();
+    }
+
+    /// Sort and sweep broadphase along the x axis followed by the narrowphase.
+    fn find_contacts(&self) -> Vec<BodyContact<T>> {
+        let boxes: Vec<Aabb<T>> = self.bodies.iter().map(|b| b.aabb()).collect();
+        let mut order: Vec<usize> = (0..self.bodies.len()).collect();
+        order.sort_by(|&a, &b| {
+            boxes[a]
+                .min
+                .x
+                .partial_cmp(&boxes[b].min.x)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut contacts = Vec::new();
+        for (k, &i) in order.iter().enumerate() {
+            for &j in &order[k + 1..] {
+                if boxes[j].min.x > boxes[i].max.x {
+                    break;
+                }
+                let (a, b) = (i.min(j), i.max(j));
+                if (self.bodies[a].is_static() && self.bodies[b].is_static())
+                    || !boxes[a].overlaps(&boxes[b])
+                {
+                    continue;
+                }
+                if let Some(manifold) = collide(&self.bodies[a], &self.bodies[b]) {
+                    contacts.push(BodyContact {
+                        body_a: BodyHandle(a),
+                        body_b: BodyHandle(b),
+                        manifold,
+                    });
+                }
+            }
+        }
+        contacts.sort_by_key(|c| (c.body_a, c.body_b));
+        contacts
+    }
+
+    fn prepare_constraints(&self) -> Vec<ContactConstraint<T>> {
+        let mut constraints = Vec::new();
+        for contact in &self.contacts {
+            let a = &self.bodies[contact.body_a.0];
+            let b = &self.bodies[contact.body_b.0];
+            let normal = contact.manifold.normal;
+            let tangent = -normal.perpendicular();
+            let restitution = a.restitution.max(b.restitution);
+            let friction = (a.friction * b.friction).sqrt();
+            for (index, point) in contact.manifold.contacts.iter().enumerate() {
+                let ra = point.position - a.position;
+                let rb = point.position - b.position;
+                let effective_mass = |axis: Vector2d<T>| {
+                    let rna = ra.cross_product(axis);
+                    let rnb = rb.cross_product(axis);
+                    let k = a.inverse_mass
+                        + b.inverse_mass
+                        + rna * rna * a.inverse_inertia
+                        + rnb * rnb * b.inverse_inertia;
+                    if k > T::zero() {
+                        T::one() / k
+                    } else {
+                        T::zero()
+                    }
+                };
+                let relative = b.velocity_at(point.position) - a.velocity_at(point.position);
+                let approach = relative.dot_product(normal);
+                let (normal_impulse, tangent_impulse) = self
+                    .warm_start
+                    .get(&(contact.body_a.0, contact.body_b.0, index))
+                    .copied()
+                    .unwrap_or((T::zero(), T::zero()));
+                // Resting contacts should not bounce.
+                let bounce = if approach < -self.restitution_threshold {
+                    -restitution * approach
+                } else {
+                    T::zero()
+                };
+                constraints.push(ContactConstraint {
+                    a: contact.body_a.0,
+                    b: contact.body_b.0,
+                    ra,
+                    rb,
+                    normal,
+                    tangent,
+                    normal_mass: effective_mass(normal),
+                    tangent_mass: effective_mass(tangent),
+                    friction,
+                    bounce,
+                    index,
+                    normal_impulse,
+                    tangent_impulse,
+                });
+            }
+        }
+        constraints
+    }
+
+    fn solve_constraint(&mut self, c: &mut ContactConstraint<T>) {
+        let relative_velocity = |bodies: &[RigidBody<T>]| {
+            let (a, b) = (&bodies[c.a], &bodies[c.b]);
+            (b.velocity + c.rb.perpendicular() * b.angular_velocity)
+                - (a.velocity + c.ra.perpendicular() * a.angular_velocity)
+        };
+
+        // Normal impulse, accumulated and clamped to stay repulsive.
+        let vn = relative_velocity(&self.bodies).dot_product(c.normal);
+        let delta = c.normal_mass * (c.bounce - vn);
+        let total = (c.normal_impulse + delta).max(T::zero());
+        let applied = total - c.normal_impulse;
+        c.normal_impulse = total;
+        self.apply_pair_impulse(c, c.normal * applied);
+
+        // Friction impulse, clamped by the Coulomb cone.
+        let vt = relative_velocity(&self.bodies).dot_product(c.tangent);
+        let limit = c.friction * c.normal_impulse;
+        let total = (c.tangent_impulse - c.tangent_mass * vt)
+            .max(-limit)
+            .min(limit);
+        let applied = total - c.tangent_impulse;
+        c.tangent_impulse = total;
+        self.apply_pair_impulse(c, c.tangent * applied);
+    }
+
+    fn apply_pair_impulse(&mut self, c: &ContactConstraint<T>, impulse: Vector2d<T>) {
+        let a = &mut self.bodies[c.a];
+        a.velocity = a.velocity - impulse * a.inverse_mass;
+        a.angular_velocity = a.angular_velocity - c.ra.cross_product(impulse) * a.inverse_inertia;
+        let b = &mut self.bodies[c.b];
+        b.velocity += impulse * b.inverse_mass;
+        b.angular_velocity += c.rb.cross_product(impulse) * b.inverse_inertia;
+    }
+
+    fn correct_positions(&mut self) {
+        for contact in &self.contacts {
+            let (ia, ib) = (contact.body_a.0, contact.body_b.0);
+            let total = self.bodies[ia].inverse_mass + self.bodies[ib].inverse_mass;
+            if total == T::zero() {
+                continue;
+            }
+            let depth = (contact.manifold.depth - self.penetration_slop).max(T::zero());
+            let correction = contact.manifold.normal * (depth * self.correction_percent / total);
+            let a = &mut self.bodies[ia];
+            a.position = a.position - correction * a.inverse_mass;
+            let b = &mut self.bodies[ib];
+            b.position += correction * b.inverse_mass;
+        }
+    }
+}
+
+// -----------------------------------------
+//        Narrowphase
+// -----------------------------------------
+
+struct ContactConstraint<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    a: usize,
+    b: usize,
+    ra: Vector2d<T>,
+    rb: Vector2d<T>,
+    normal: Vector2d<T>,
+    tangent: Vector2d<T>,
+    normal_mass: T,
+    tangent_mass: T,
+    friction: T,
+    bounce: T,
+    index: usize,
+    normal_impulse: T,
+    tangent_impulse: T,
+}
+
+/// Computes the contact manifold between two bodies, with the normal
+/// pointing from `a` towards `b`.
+fn collide<T>(a: &RigidBody<T>, b: &RigidBody<T>) -> Option<ContactManifold<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    match (&a.shape, &b.shape) {
+        (ColliderShape::Circle { radius: ra }, ColliderShape::Circle { radius: rb }) => {
+            let offset = b.position - a.position;
+            let distance = offset.magnitude();
+            let depth = *ra + *rb - distance;
+            if depth <= T::zero() {
+                return None;
+            }
+            let normal = offset
+                .normalize()
+                .unwrap_or(Vector2d::new(T::zero(), T::one()));
+            Some(ContactManifold {
+                normal,
+                depth,
+                contacts: vec![ContactPoint {
+                    position: a.position + normal * (*ra - depth / cast(2.0)),
+                    depth,
+                }],
+            })
+        }
+        (ColliderShape::Polygon(_), ColliderShape::Circle { radius }) => {
+            polygon_circle(&a.world_vertices(), b.position, *radius)
+        }
+        (ColliderShape::Circle { radius }, ColliderShape::Polygon(_)) => {
+            polygon_circle(&b.world_vertices(), a.position, *radius).map(|mut m| {
+                m.normal = -m.normal;
+                m
+            })
+        }
+        (ColliderShape::Polygon(_), ColliderShape::Polygon(_)) => {
+            sat_manifold(&a.world_vertices(), &b.world_vertices())
+        }
+    }
+}
+
+/// Contact between a counter-clockwise polygon and a circle, with the normal
+/// pointing from the polygon towards the circle.
+fn polygon_circle<T>(
+    vertices: &[Vector2d<T>],
+    center: Vector2d<T>,
+    radius: T,
+) -> Option<ContactManifold<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let n = vertices.len();
+    let mut max_separation = T::neg_infinity();
+    let mut face = 0;
+    for i in 0..n {
+        let edge = vertices[(i + 1) % n] - vertices[i];
+        let normal = Vector2d::new(edge.y, -edge.x).normalize().ok()?;
+        let separation = normal.dot_product(center - vertices[i]);
+        if separation > radius {
+            return None;
+        }
+        if separation > max_separation {
+            max_separation = separation;
+            face = i;
+        }
+    }
+
+    let (p, q) = (vertices[face], vertices[(face + 1) % n]);
+    let edge = q - p;
+    let face_normal = Vector2d::new(edge.y, -edge.x).normalize().ok()?;
+    let (normal, closest) = if max_separation <= T::zero() {
+        // The center is inside the polygon.
+        (face_normal, center - face_normal * max_separation)
+    } else {
+        let (closest, _) = closest_on_segment(center, p, q);
+        let offset = center - closest;
+        if offset.dot_product(offset) > radius * radius {
+            return None;
+        }
+        (offset.normalize().unwrap_or(face_normal), closest)
+    };
+    let distance = (center - closest).dot_product(normal);
+    let depth = radius - distance;
+    Some(ContactManifold {
+        normal,
+        depth,
+        contacts: vec![ContactPoint {
+            position: closest,
+            depth,
+        }],
+    })
+}
+
+fn check_density<T>(density: T) -> Result<(), String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    if density > T::zero() && density.is_finite() {
+        Ok(())
+    } else {
+        Err(format!(
+            "[::ERROR::] -> A body needs a positive density, got {}.",
+            density
+        ))
+    }
+}
+
+fn polygon_centroid<T>(vertices: &[Vector2d<T>]) -> Vector2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let n = vertices.len();
+    let mut centroid = Vector2d::new(T::zero(), T::zero());
+    let mut twice_area = T::zero();
+    for i in 0..n {
+        let (p, q) = (vertices[i], vertices[(i + 1) % n]);
+        let cross = p.cross_product(q);
+        twice_area += cross;
+        centroid += (p + q) * cross;
+    }
+    centroid * (T::one() / (twice_area * cast(3.0)))
+}
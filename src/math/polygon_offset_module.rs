@@ -0,0 +1,457 @@
+use crate::math::scalar_module::{cast, pi};
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        Join and Cap Styles
+// -----------------------------------------
+
+/// How two consecutive offset edges are connected on the outer side of a corner.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LineJoin {
+    /// Extend both edges until they meet, falling back to a bevel when the
+    /// miter ratio exceeds the style's `miter_limit`.
+    Miter,
+    /// Connect the edges with a circular arc centred on the corner.
+    Round,
+    /// Connect the edges with a straight segment.
+    Bevel,
+}
+
+/// How the two ends of an open polyline are closed when it gets stroked.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LineCap {
+    /// The outline stops exactly at the end point.
+    Butt,
+    /// The outline is extended by half the stroke width past the end point.
+    Square,
+    /// The outline is closed with a half circle around the end point.
+    Round,
+}
+
+/// Parameters shared by the offsetting and stroking functions.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StrokeStyle<T> {
+    pub join: LineJoin,
+    pub cap: LineCap,
+    /// Maximum ratio between the miter length and the offset distance
+    /// (same meaning as the SVG `stroke-miterlimit` attribute).
+    pub miter_limit: T,
+    /// Maximum distance between a round join/cap and its polygonal approximation.
+    pub arc_tolerance: T,
+}
+
+impl<T> StrokeStyle<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create a new style with the SVG defaults: a miter limit of `4` and an
+    /// arc tolerance of `0.1` units.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::polygon_offset_module::{LineCap, LineJoin, StrokeStyle};
+    ///
+    /// let style: StrokeStyle<f64> = StrokeStyle::new(LineJoin::Round, LineCap::Butt);
+    /// assert_eq!(style.miter_limit, 4.0);
+    /// ```
+    pub fn new(join: LineJoin, cap: LineCap) -> Self {
+        Self {
+            join,
+            cap,
+            miter_limit: cast(4.0),
+            arc_tolerance: cast(0.1),
+        }
+    }
+}
+
+// -----------------------------------------
+//        Polygon Offsetting
+// -----------------------------------------
+
+/// Offsets (inflates or deflates) a closed polygon by `distance`.
+///
+/// A positive `distance` grows the polygon and a negative one shrinks it,
+/// regardless of the winding order of `points`. The returned polygon keeps the
+/// winding order of the input. Only the outer side of each corner uses the
+/// style's join; `style.cap` is ignored since a polygon has no ends.
+///
+/// The result is not cleaned from self-intersections, so deflating a polygon
+/// by more than its inner radius produces a folded outline.
+///
+/// # Arguments
+///
+/// * `points` - The polygon vertices, without repeating the first vertex at the end.
+/// * `distance` - The offset distance.
+/// * `style` - The join style used on convex corners.
+///
+/// # Returns
+///
+/// The offset polygon, or an `Err` when `points` has fewer than three distinct vertices.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::polygon_offset_module::{offset_polygon, LineCap, LineJoin, StrokeStyle};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let square = vec![
+///     Vector2d::new(0.0, 0.0),
+///     Vector2d::new(2.0, 0.0),
+///     Vector2d::new(2.0, 2.0),
+///     Vector2d::new(0.0, 2.0),
+/// ];
+/// let style = StrokeStyle::new(LineJoin::Miter, LineCap::Butt);
+/// let grown = offset_polygon(&square, 1.0, &style).unwrap();
+/// assert_eq!(grown[0], Vector2d::new(-1.0, -1.0));
+/// ```
+pub fn offset_polygon<T>(
+    points: &[Vector2d<T>],
+    distance: T,
+    style: &StrokeStyle<T>,
+) -> Result<Vec<Vector2d<T>>, String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let points = dedup_points(points, true);
+    if points.len() < 3 {
+        return Err(
+            "[::ERROR::] -> Cannot offset a polygon with less than 3 vertices.".to_string(),
+        );
+    }
+    // Right-hand normals point outwards for counter-clockwise polygons.
+    let distance = if signed_area(&points) < T::zero() {
+        -distance
+    } else {
+        distance
+    };
+    let n = points.len();
+    let mut result = Vec::with_capacity(n * 2);
+    for i in 0..n {
+        let prev = points[(i + n - 1) % n];
+        let current = points[i];
+        let next = points[(i + 1) % n];
+        push_join(&mut result, prev, current, next, distance, style);
+    }
+    Ok(result)
+}
+
+/// Offsets an open polyline to one side by `distance`.
+///
+/// Positive distances move the polyline to the right of its direction of
+/// travel, negative ones to the left. The end points are moved along the
+/// normal of their single adjacent edge.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::polygon_offset_module::{offset_polyline, LineCap, LineJoin, StrokeStyle};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let line = vec![Vector2d::new(0.0, 0.0), Vector2d::new(4.0, 0.0)];
+/// let style = StrokeStyle::new(LineJoin::Bevel, LineCap::Butt);
+/// let shifted = offset_polyline(&line, 1.0, &style).unwrap();
+/// assert_eq!(shifted, vec![Vector2d::new(0.0, -1.0), Vector2d::new(4.0, -1.0)]);
+/// ```
+pub fn offset_polyline<T>(
+    points: &[Vector2d<T>],
+    distance: T,
+    style: &StrokeStyle<T>,
+) -> Result<Vec<Vector2d<T>>, String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let points = dedup_points(points, false);
+    if points.len() < 2 {
+        return Err(
+            "[::ERROR::] -> Cannot offset a polyline with less than 2 vertices.".to_string(),
+        );
+    }
+    Ok(offset_open(&points, distance, style))
+}
+
+/// Generates the outline of a polyline stroked with the given `width`.
+///
+/// For an open polyline the outline is a single closed polygon made of the
+/// right side, the end cap, the left side walked backwards and the start cap.
+/// For a closed polyline (`closed == true`) the outline is made of two rings,
+/// the outer one and the inner one, with opposite winding orders so they can
+/// be filled together with the non-zero rule.
+///
+/// # Arguments
+///
+/// * `points` - The polyline vertices.
+/// * `width` - The full stroke width.
+/// * `closed` - Whether the last vertex connects back to the first one.
+/// * `style` - The join, cap and miter settings.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::polygon_offset_module::{stroke_polyline, LineCap, LineJoin, StrokeStyle};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let line = vec![Vector2d::new(0.0, 0.0), Vector2d::new(4.0, 0.0)];
+/// let style = StrokeStyle::new(LineJoin::Miter, LineCap::Square);
+/// let outline = stroke_polyline(&line, 2.0, false, &style).unwrap();
+/// assert_eq!(outline.len(), 1);
+/// assert_eq!(outline[0].len(), 8);
+/// ```
+pub fn stroke_polyline<T>(
+    points: &[Vector2d<T>],
+    width: T,
+    closed: bool,
+    style: &StrokeStyle<T>,
+) -> Result<Vec<Vec<Vector2d<T>>>, String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    if width <= T::zero() {
+        return Err(format!(
+            "[::ERROR::] -> Stroke width must be positive, got: {}",
+            width
+        ));
+    }
+    let half = width / cast(2.0);
+    if closed {
+        let outer = offset_polygon(points, half, style)?;
+        let mut inner = offset_polygon(points, -half, style)?;
+        inner.reverse();
+        return Ok(vec![outer, inner]);
+    }
+
+    let points = dedup_points(points, false);
+    if points.len() < 2 {
+        return Err(
+            "[::ERROR::] -> Cannot stroke a polyline with less than 2 vertices.".to_string(),
+        );
+    }
+    let mut reversed = points.clone();
+    reversed.reverse();
+
+    let mut outline = offset_open(&points, half, style);
+    let n = points.len();
+    push_cap(&mut outline, points[n - 2], points[n - 1], half, style);
+    outline.extend(offset_open(&reversed, half, style));
+    push_cap(&mut outline, points[1], points[0], half, style);
+    Ok(vec![outline])
+}
+
+/// Signed area of a polygon, positive for counter-clockwise winding.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::polygon_offset_module::signed_area;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let triangle = vec![
+///     Vector2d::new(0.0, 0.0),
+///     Vector2d::new(4.0, 0.0),
+///     Vector2d::new(0.0, 3.0),
+/// ];
+/// assert_eq!(signed_area(&triangle), 6.0);
+/// ```
+pub fn signed_area<T>(points: &[Vector2d<T>]) -> T
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let n = points.len();
+    let mut twice_area = T::zero();
+    for i in 0..n {
+        twice_area += points[i].cross_product(points[(i + 1) % n]);
+    }
+    twice_area / cast(2.0)
+}
+
+// -----------------------------------------
+//        Internal helpers
+// -----------------------------------------
+
+fn dedup_points<T>(points: &[Vector2d<T>], closed: bool) -> Vec<Vector2d<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let mut result: Vec<Vector2d<T>> = Vec::with_capacity(points.len());
+    for &p in points {
+        if result.last() != Some(&p) {
+            result.push(p);
+        }
+    }
+    if closed {
+        while result.len() > 1 && result.first() == result.last() {
+            result.pop();
+        }
+    }
+    result
+}
+
+fn right_normal<T>(from: Vector2d<T>, to: Vector2d<T>) -> Vector2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let edge = to - from;
+    let length = edge.magnitude();
+    Vector2d::new(edge.y / length, -edge.x / length)
+}
+
+fn offset_open<T>(points: &[Vector2d<T>], distance: T, style: &StrokeStyle<T>) -> Vec<Vector2d<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let n = points.len();
+    let mut result = Vec::with_capacity(n * 2);
+    result.push(points[0] + right_normal(points[0], points[1]) * distance);
+    for i in 1..n - 1 {
+        push_join(
+            &mut result,
+            points[i - 1],
+            points[i],
+            points[i + 1],
+            distance,
+            style,
+        );
+    }
+    result.push(points[n - 1] + right_normal(points[n - 2], points[n - 1]) * distance);
+    result
+}
+
+fn push_join<T>(
+    out: &mut Vec<Vector2d<T>>,
+    prev: Vector2d<T>,
+    current: Vector2d<T>,
+    next: Vector2d<T>,
+    distance: T,
+    style: &StrokeStyle<T>,
+) where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let n0 = right_normal(prev, current);
+    let n1 = right_normal(current, next);
+    let start = current + n0 * distance;
+    let end = current + n1 * distance;
+    let turn = (current - prev).cross_product(next - current);
+    let cos_angle = n0.dot_product(n1);
+    let eps = cast::<T>(1e-12);
+
+    // Collinear edges going the same way need a single point.
+    if turn.abs() <= eps && cos_angle > T::zero() {
+        out.push(start);
+        return;
+    }
+
+    let outer = turn * distance > T::zero() || cos_angle <= -T::one() + eps;
+    if !outer {
+        // Inner side of the corner: both offset edges meet at the miter point.
+        let miter = (n0 + n1) * (T::one() / (T::one() + cos_angle));
+        out.push(current + miter * distance);
+        return;
+    }
+
+    match style.join {
+        LineJoin::Bevel => {
+            out.push(start);
+            out.push(end);
+        }
+        LineJoin::Miter => {
+            let denominator = T::one() + cos_angle;
+            let ratio = if denominator > eps {
+                (cast::<T>(2.0) / denominator).sqrt()
+            } else {
+                T::infinity()
+            };
+            if ratio <= style.miter_limit {
+                let miter = (n0 + n1) * (T::one() / denominator);
+                out.push(current + miter * distance);
+            } else {
+                out.push(start);
+                out.push(end);
+            }
+        }
+        LineJoin::Round => {
+            let sweep = if cos_angle <= -T::one() + eps {
+                // Full reversal: sweep half a turn around the outside.
+                pi::<T>() * distance.signum()
+            } else {
+                n0.cross_product(n1).atan2(cos_angle)
+            };
+            push_arc(out, current, n0 * distance, sweep, distance.abs(), style);
+        }
+    }
+}
+
+fn push_cap<T>(
+    out: &mut Vec<Vector2d<T>>,
+    before: Vector2d<T>,
+    end: Vector2d<T>,
+    half_width: T,
+    style: &StrokeStyle<T>,
+) where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let normal = right_normal(before, end);
+    let direction = Vector2d::new(-normal.y, normal.x);
+    match style.cap {
+        LineCap::Butt => {}
+        LineCap::Square => {
+            out.push(end + (normal + direction) * half_width);
+            out.push(end + (direction - normal) * half_width);
+        }
+        LineCap::Round => {
+            let mut arc = Vec::new();
+            push_arc(
+                &mut arc,
+                end,
+                normal * half_width,
+                pi::<T>(),
+                half_width,
+                style,
+            );
+            // The arc end points coincide with the side offsets already emitted.
+            let inner = arc.len() - 1;
+            out.extend_from_slice(&arc[1..inner]);
+        }
+    }
+}
+
+/// Pushes the points of an arc starting at `center + radius_vector` and
+/// sweeping `sweep` radians (counter-clockwise when positive), both end
+/// points included.
+fn push_arc<T>(
+    out: &mut Vec<Vector2d<T>>,
+    center: Vector2d<T>,
+    radius_vector: Vector2d<T>,
+    sweep: T,
+    radius: T,
+    style: &StrokeStyle<T>,
+) where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let steps = arc_steps(sweep, radius, style.arc_tolerance);
+    let step = sweep / T::from(steps).unwrap();
+    for i in 0..=steps {
+        let angle = step * T::from(i).unwrap();
+        out.push(center + radius_vector.rotate(angle));
+    }
+}
+
+pub(crate) fn arc_steps<T>(sweep: T, radius: T, tolerance: T) -> usize
+where
+    T: Float,
+{
+    let tolerance = tolerance.max(cast(1e-6));
+    let max_step = if tolerance >= radius {
+        pi::<T>() / cast(2.0)
+    } else {
+        (T::one() - tolerance / radius).acos() * cast(2.0)
+    };
+    (sweep.abs() / max_step)
+        .ceil()
+        .to_usize()
+        .unwrap_or(1)
+        .clamp(1, 1024)
+}
@@ -0,0 +1,580 @@
+use crate::math::scalar_module::cast;
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        Polyline Simplification
+// -----------------------------------------
+
+/// Simplifies a polyline with the Ramer–Douglas–Peucker algorithm.
+///
+/// Every removed point lies within `tolerance` of the simplified polyline.
+/// The first and last points are always kept.
+///
+/// # Arguments
+///
+/// * `points` - The polyline to simplify.
+/// * `tolerance` - The maximum allowed distance between a dropped point and the result.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::polyline_module::simplify_rdp;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let gesture = vec![
+///     Vector2d::new(0.0, 0.0),
+///     Vector2d::new(1.0, 0.05),
+///     Vector2d::new(2.0, -0.05),
+///     Vector2d::new(3.0, 0.0),
+///     Vector2d::new(3.0, 3.0),
+/// ];
+/// let simplified = simplify_rdp(&gesture, 0.1);
+/// assert_eq!(simplified.len(), 3);
+/// ```
+pub fn simplify_rdp<T>(points: &[Vector2d<T>], tolerance: T) -> Vec<Vector2d<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = stack.pop() {
+        let mut max_distance = T::zero();
+        let mut index = first;
+        for (i, &p) in points.iter().enumerate().take(last).skip(first + 1) {
+            let d = segment_distance(p, points[first], points[last]);
+            if d > max_distance {
+                max_distance = d;
+                index = i;
+            }
+        }
+        if max_distance > tolerance {
+            keep[index] = true;
+            stack.push((first, index));
+            stack.push((index, last));
+        }
+    }
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(&p, k)| if k { Some(p) } else { None })
+        .collect()
+}
+
+/// Simplifies a polyline with the Visvalingam–Whyatt algorithm.
+///
+/// Points are removed in order of increasing effective area (the area of the
+/// triangle they form with their current neighbours) until every remaining
+/// interior point has an effective area of at least `min_area`. The first and
+/// last points are always kept.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::polyline_module::simplify_visvalingam;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let path = vec![
+///     Vector2d::new(0.0, 0.0),
+///     Vector2d::new(1.0, 0.01),
+///     Vector2d::new(2.0, 0.0),
+///     Vector2d::new(2.0, 2.0),
+/// ];
+/// let simplified = simplify_visvalingam(&path, 0.1);
+/// assert_eq!(simplified.len(), 3);
+/// ```
+pub fn simplify_visvalingam<T>(points: &[Vector2d<T>], min_area: T) -> Vec<Vector2d<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let n = points.len();
+    if n < 3 {
+        return points.to_vec();
+    }
+    let mut prev: Vec<usize> = (0..n).map(|i| i.wrapping_sub(1)).collect();
+    let mut next: Vec<usize> = (1..=n).collect();
+    let mut areas = vec![T::infinity(); n];
+    let mut removed = vec![false; n];
+    let mut heap = BinaryHeap::with_capacity(n);
+
+    for i in 1..n - 1 {
+        areas[i] = triangle_area(points[i - 1], points[i], points[i + 1]);
+        heap.push(AreaEntry {
+            area: areas[i],
+            index: i,
+        });
+    }
+
+    let mut max_removed = T::zero();
+    while let Some(AreaEntry { area, index }) = heap.pop() {
+        // Skip stale entries whose area got updated after being pushed.
+        if removed[index] || area != areas[index] {
+            continue;
+        }
+        // Enforce monotonic areas so neighbours of removed points are not
+        // dropped earlier than the points that shaped them.
+        let effective = area.max(max_removed);
+        if effective >= min_area {
+            break;
+        }
+        max_removed = effective;
+        removed[index] = true;
+        let (p, q) = (prev[index], next[index]);
+        next[p] = q;
+        prev[q] = p;
+        for neighbour in [p, q] {
+            if neighbour != 0 && neighbour != n - 1 {
+                areas[neighbour] = triangle_area(
+                    points[prev[neighbour]],
+                    points[neighbour],
+                    points[next[neighbour]],
+                );
+                heap.push(AreaEntry {
+                    area: areas[neighbour],
+                    index: neighbour,
+                });
+            }
+        }
+    }
+    points
+        .iter()
+        .zip(removed)
+        .filter_map(|(&p, r)| if r { None } else { Some(p) })
+        .collect()
+}
+
+// -----------------------------------------
+//        Polyline Resampling
+// -----------------------------------------
+
+/// Returns the total length of a polyline.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::polyline_module::polyline_length;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let path = vec![Vector2d::new(0.0, 0.0), Vector2d::new(3.0, 4.0), Vector2d::new(3.0, 5.0)];
+/// assert_eq!(polyline_length(&path), 6.0);
+/// ```
+pub fn polyline_length<T>(points: &[Vector2d<T>]) -> T
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let mut length = T::zero();
+    for pair in points.windows(2) {
+        length += pair[0].distance(pair[1]);
+    }
+    length
+}
+
+/// Resamples a polyline into `count` points evenly spaced along its length.
+///
+/// The first and last points of the result coincide with those of `points`.
+///
+/// # Returns
+///
+/// The resampled points, or an `Err` when `count < 2` or `points` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::polyline_module::resample_count;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let path = vec![Vector2d::new(0.0, 0.0), Vector2d::new(4.0, 0.0)];
+/// let resampled = resample_count(&path, 5).unwrap();
+/// assert_eq!(resampled[1], Vector2d::new(1.0, 0.0));
+/// ```
+pub fn resample_count<T>(points: &[Vector2d<T>], count: usize) -> Result<Vec<Vector2d<T>>, String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    if count < 2 {
+        return Err(format!(
+            "[::ERROR::] -> Cannot resample into less than 2 points, got: {}",
+            count
+        ));
+    }
+    if points.is_empty() {
+        return Err("[::ERROR::] -> Cannot resample an empty polyline.".to_string());
+    }
+    let length = polyline_length(points);
+    let spacing = length / T::from(count - 1).unwrap();
+    let mut result = walk(points, spacing, count - 1);
+    result.truncate(count - 1);
+    while result.len() < count - 1 {
+        result.push(points[points.len() - 1]);
+    }
+    result.push(points[points.len() - 1]);
+    Ok(result)
+}
+
+/// Resamples a polyline into points separated by `spacing` along its length.
+///
+/// The result starts at the first point of `points`; the last point of
+/// `points` is appended when the remaining length is not a whole multiple
+/// of `spacing`.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::polyline_module::resample_spacing;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let path = vec![Vector2d::new(0.0, 0.0), Vector2d::new(2.5, 0.0)];
+/// let resampled = resample_spacing(&path, 1.0).unwrap();
+/// assert_eq!(resampled.len(), 4);
+/// assert_eq!(resampled[3], Vector2d::new(2.5, 0.0));
+/// ```
+pub fn resample_spacing<T>(points: &[Vector2d<T>], spacing: T) -> Result<Vec<Vector2d<T>>, String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    if spacing <= T::zero() {
+        return Err(format!(
+            "[::ERROR::] -> Resampling spacing must be positive, got: {}",
+            spacing
+        ));
+    }
+    if points.is_empty() {
+        return Err("[::ERROR::] -> Cannot resample an empty polyline.".to_string());
+    }
+    let mut result = walk(points, spacing, usize::MAX);
+    let last = points[points.len() - 1];
+    let epsilon = spacing * cast(1e-9);
+    match result.last() {
+        Some(&p) if p.distance(last) <= epsilon => {}
+        _ => result.push(last),
+    }
+    Ok(result)
+}
+
+// -----------------------------------------
+//        Polyline Smoothing
+// -----------------------------------------
+
+/// Smooths a polyline with Chaikin's corner-cutting algorithm.
+///
+/// Every iteration replaces each segment with two points at 1/4 and 3/4 of
+/// its length. Open polylines keep their end points.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::polyline_module::chaikin_smooth;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let corner = vec![Vector2d::new(0.0, 0.0), Vector2d::new(4.0, 0.0), Vector2d::new(4.0, 4.0)];
+/// let smoothed = chaikin_smooth(&corner, 1, false);
+/// assert_eq!(smoothed.len(), 6);
+/// assert_eq!(smoothed[1], Vector2d::new(1.0, 0.0));
+/// ```
+pub fn chaikin_smooth<T>(
+    points: &[Vector2d<T>],
+    iterations: usize,
+    closed: bool,
+) -> Vec<Vector2d<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let quarter: T = cast(0.25);
+    let three_quarters: T = cast(0.75);
+    let mut current = points.to_vec();
+    for _ in 0..iterations {
+        let n = current.len();
+        if n < 3 {
+            break;
+        }
+        let segments = if closed { n } else { n - 1 };
+        let mut smoothed = Vec::with_capacity(segments * 2 + 2);
+        if !closed {
+            smoothed.push(current[0]);
+        }
+        for i in 0..segments {
+            let a = current[i];
+            let b = current[(i + 1) % n];
+            smoothed.push(a * three_quarters + b * quarter);
+            smoothed.push(a * quarter + b * three_quarters);
+        }
+        if !closed {
+            smoothed.push(current[n - 1]);
+        }
+        current = smoothed;
+    }
+    current
+}
+
+/// Smooths noisy samples with a centred moving average over `window` points.
+///
+/// Near the ends the window shrinks symmetrically, so the first and last
+/// samples are kept as they are.
+///
+/// # Returns
+///
+/// The smoothed samples, or an `Err` when `window` is even or zero.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::polyline_module::moving_average;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let samples = vec![
+///     Vector2d::new(0.0, 0.0),
+///     Vector2d::new(1.0, 3.0),
+///     Vector2d::new(2.0, 0.0),
+/// ];
+/// let smoothed = moving_average(&samples, 3).unwrap();
+/// assert_eq!(smoothed[1], Vector2d::new(1.0, 1.0));
+/// ```
+pub fn moving_average<T>(points: &[Vector2d<T>], window: usize) -> Result<Vec<Vector2d<T>>, String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    if window == 0 || window.is_multiple_of(2) {
+        return Err(format!(
+            "[::ERROR::] -> Moving average window must be odd, got: {}",
+            window
+        ));
+    }
+    let half = window / 2;
+    let n = points.len();
+    let mut result = Vec::with_capacity(n);
+    for i in 0..n {
+        let reach = half.min(i).min(n - 1 - i);
+        let mut sum = Vector2d::new(T::zero(), T::zero());
+        for p in &points[i - reach..=i + reach] {
+            sum += *p;
+        }
+        result.push(sum * (T::one() / T::from(2 * reach + 1).unwrap()));
+    }
+    Ok(result)
+}
+
+/// Smooths noisy samples with a Savitzky–Golay filter.
+///
+/// Each sample is replaced by the value at its position of the least-squares
+/// polynomial of degree `order` fitted over `window` neighbouring samples.
+/// Unlike a moving average this preserves peaks and linear trends. The
+/// samples are extended past both ends by point reflection, so straight
+/// polylines are returned unchanged.
+///
+/// # Returns
+///
+/// The smoothed samples, or an `Err` when `window` is even or not larger than `order`.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::polyline_module::savitzky_golay;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// // A parabola is reproduced exactly by a quadratic filter.
+/// let samples: Vec<_> = (0..9)
+///     .map(|i| Vector2d::new(i as f64, (i * i) as f64))
+///     .collect();
+/// let smoothed = savitzky_golay(&samples, 5, 2).unwrap();
+/// assert!((smoothed[4].y - 16.0).abs() < 1e-9);
+/// ```
+pub fn savitzky_golay<T>(
+    points: &[Vector2d<T>],
+    window: usize,
+    order: usize,
+) -> Result<Vec<Vector2d<T>>, String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    if window.is_multiple_of(2) || window <= order {
+        return Err(format!(
+            "[::ERROR::] -> Savitzky-Golay window must be odd and larger than the order, got window {} and order {}",
+            window, order
+        ));
+    }
+    let n = points.len();
+    if n < 2 {
+        return Ok(points.to_vec());
+    }
+    let half = (window / 2) as isize;
+    let coefficients: Vec<T> = savitzky_golay_coefficients(half as usize, order)
+        .into_iter()
+        .map(|c| T::from(c).unwrap())
+        .collect();
+
+    let two: T = cast(2.0);
+    let sample = |i: isize| -> Vector2d<T> {
+        let last = n as isize - 1;
+        if i < 0 {
+            points[0] * two - points[(-i).min(last) as usize]
+        } else if i > last {
+            points[n - 1] * two - points[(2 * last - i).max(0) as usize]
+        } else {
+            points[i as usize]
+        }
+    };
+
+    let mut result = Vec::with_capacity(n);
+    for i in 0..n as isize {
+        let mut sum = Vector2d::new(T::zero(), T::zero());
+        for (k, &c) in coefficients.iter().enumerate() {
+            sum += sample(i + k as isize - half) * c;
+        }
+        result.push(sum);
+    }
+    Ok(result)
+}
+
+// -----------------------------------------
+//        Internal helpers
+// -----------------------------------------
+
+/// Closest point to `p` on the segment from `a` to `b`, with its parameter
+/// `t` in `[0, 1]` along the segment. A degenerate segment gives `(a, 0)`.
+pub(crate) fn closest_on_segment<T>(
+    p: Vector2d<T>,
+    a: Vector2d<T>,
+    b: Vector2d<T>,
+) -> (Vector2d<T>, T)
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let ab = b - a;
+    let length_squared = ab.dot_product(ab);
+    if length_squared == T::zero() {
+        return (a, T::zero());
+    }
+    let t = ((p - a).dot_product(ab) / length_squared)
+        .max(T::zero())
+        .min(T::one());
+    (a + ab * t, t)
+}
+
+pub(crate) fn segment_distance<T>(p: Vector2d<T>, a: Vector2d<T>, b: Vector2d<T>) -> T
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    p.distance(closest_on_segment(p, a, b).0)
+}
+
+fn triangle_area<T>(a: Vector2d<T>, b: Vector2d<T>, c: Vector2d<T>) -> T
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    ((b - a).cross_product(c - a) / cast(2.0)).abs()
+}
+
+struct AreaEntry<T> {
+    area: T,
+    index: usize,
+}
+
+impl<T: Float> PartialEq for AreaEntry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Float> Eq for AreaEntry<T> {}
+
+impl<T: Float> PartialOrd for AreaEntry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Float> Ord for AreaEntry<T> {
+    // Reversed so that `BinaryHeap` pops the smallest area first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .area
+            .partial_cmp(&self.area)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+/// Walks along the polyline emitting a point every `spacing` units, starting
+/// with the first point, until `limit` points have been emitted.
+fn walk<T>(points: &[Vector2d<T>], spacing: T, limit: usize) -> Vec<Vector2d<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let mut result = vec![points[0]];
+    if spacing <= T::zero() {
+        return result;
+    }
+    let mut travelled = T::zero();
+    let mut target = spacing;
+    let tolerance = spacing * cast(1e-9);
+    for pair in points.windows(2) {
+        let length = pair[0].distance(pair[1]);
+        while result.len() < limit && target <= travelled + length + tolerance {
+            let t = if length > T::zero() {
+                ((target - travelled) / length).min(T::one())
+            } else {
+                T::zero()
+            };
+            result.push(pair[0] + (pair[1] - pair[0]) * t);
+            target += spacing;
+        }
+        travelled += length;
+    }
+    result
+}
+
+/// Savitzky–Golay smoothing weights for `2 * half + 1` samples, computed as
+/// the first row of the least-squares pseudo-inverse of the Vandermonde matrix.
+fn savitzky_golay_coefficients(half: usize, order: usize) -> Vec<f64> {
+    let size = order + 1;
+    let offsets: Vec<f64> = (0..=2 * half).map(|i| i as f64 - half as f64).collect();
+
+    // Normal equations (AᵀA) with A[i][k] = offset_i^k.
+    let mut normal = vec![vec![0.0; size]; size];
+    for (r, row) in normal.iter_mut().enumerate() {
+        for (c, value) in row.iter_mut().enumerate() {
+            *value = offsets.iter().map(|&x| x.powi((r + c) as i32)).sum();
+        }
+    }
+    // Solve (AᵀA) w = e₀; the weights are then A w.
+    let mut rhs = vec![0.0; size];
+    rhs[0] = 1.0;
+    for col in 0..size {
+        let pivot = (col..size)
+            .max_by(|&a, &b| normal[a][col].abs().total_cmp(&normal[b][col].abs()))
+            .unwrap();
+        normal.swap(col, pivot);
+        rhs.swap(col, pivot);
+        for row in 0..size {
+            if row != col {
+                let factor = normal[row][col] / normal[col][col];
+                let pivot_row = normal[col].clone();
+                for (value, pivot_value) in normal[row].iter_mut().zip(pivot_row).skip(col) {
+                    *value -= factor * pivot_value;
+                }
+                rhs[row] -= factor * rhs[col];
+            }
+        }
+    }
+    let w: Vec<f64> = (0..size).map(|i| rhs[i] / normal[i][i]).collect();
+    offsets
+        .iter()
+        .map(|&x| {
+            w.iter()
+                .enumerate()
+                .map(|(k, wk)| wk * x.powi(k as i32))
+                .sum()
+        })
+        .collect()
+}
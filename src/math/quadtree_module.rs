@@ -0,0 +1,490 @@
+use crate::math::shape_module::Aabb;
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        Quadtree
+// -----------------------------------------
+
+#[derive(Debug, Clone)]
+struct Node<T, Id>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    bounds: Aabb<T>,
+    depth: usize,
+    parent: Option<usize>,
+    /// Index of the first of the four consecutive children (SW, SE, NW, NE).
+    children: Option<usize>,
+    items: Vec<Id>,
+}
+
+/// A node as seen by `QuadTree::nodes`, mostly useful for debug drawing.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct QuadTreeNode<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub bounds: Aabb<T>,
+    pub depth: usize,
+    /// Number of entries stored directly in this node.
+    pub item_count: usize,
+    pub is_leaf: bool,
+}
+
+/// A region quadtree over points and boxes.
+///
+/// Each entry lives in the deepest node whose region fully contains its box;
+/// a leaf splits once it holds more than `capacity` entries, unless it is
+/// already at `max_depth`. Entries outside the root region are kept in the
+/// root so they are still found, just not accelerated.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::quadtree_module::QuadTree;
+/// use vectorlib::math::shape_module::Aabb;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let region = Aabb::new(Vector2d::new(0.0, 0.0), Vector2d::new(100.0, 100.0));
+/// let mut tree: QuadTree<f64, u32> = QuadTree::new(region);
+/// tree.insert_point(1, Vector2d::new(10.0, 10.0));
+/// tree.insert_point(2, Vector2d::new(90.0, 90.0));
+/// assert_eq!(tree.query_circle(Vector2d::new(12.0, 12.0), 5.0), vec![1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct QuadTree<T, Id>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    capacity: usize,
+    max_depth: usize,
+    nodes: Vec<Node<T, Id>>,
+    free: Vec<usize>,
+    entries: HashMap<Id, (Aabb<T>, usize)>,
+}
+
+impl<T, Id> QuadTree<T, Id>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    Id: Copy + Eq + Hash,
+{
+    /// Create an empty tree over `bounds` with a capacity of 8 entries per
+    /// leaf and a maximum depth of 8.
+    pub fn new(bounds: Aabb<T>) -> Self {
+        Self {
+            capacity: 8,
+            max_depth: 8,
+            nodes: vec![Self::leaf(bounds, 0, None)],
+            free: Vec::new(),
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Create an empty tree with custom split limits.
+    ///
+    /// # Returns
+    ///
+    /// The tree, or an `Err` when `capacity` is zero.
+    pub fn with_limits(bounds: Aabb<T>, capacity: usize, max_depth: usize) -> Result<Self, String> {
+        if capacity == 0 {
+            return Err("[::ERROR::] -> The node capacity must be at least 1.".to_string());
+        }
+        let mut tree = Self::new(bounds);
+        tree.capacity = capacity;
+        tree.max_depth = max_depth;
+        Ok(tree)
+    }
+
+    /// The region covered by the root node.
+    pub fn region(&self) -> Aabb<T> {
+        self.nodes[0].bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, id: Id) -> bool {
+        self.entries.contains_key(&id)
+    }
+
+    /// The stored box of `id`, degenerate for points.
+    pub fn bounds(&self, id: Id) -> Option<Aabb<T>> {
+        self.entries.get(&id).map(|&(bounds, _)| bounds)
+    }
+
+    /// Removes every entry and collapses the tree back to its root.
+    pub fn clear(&mut self) {
+        let region = self.region();
+        self.nodes.clear();
+        self.nodes.push(Self::leaf(region, 0, None));
+        self.free.clear();
+        self.entries.clear();
+    }
+
+    /// Inserts a point, or moves it when `id` is already stored.
+    pub fn insert_point(&mut self, id: Id, point: Vector2d<T>) {
+        self.insert_aabb(
+            id,
+            Aabb {
+                min: point,
+                max: point,
+            },
+        );
+    }
+
+    /// Inserts a box, or moves it when `id` is already stored.
+    pub fn insert_aabb(&mut self, id: Id, bounds: Aabb<T>) {
+        if self.entries.contains_key(&id) {
+            self.move_aabb(id, bounds);
+            return;
+        }
+        let mut node = 0;
+        while let Some(child) = self.child_containing(node, &bounds) {
+            node = child;
+        }
+        self.nodes[node].items.push(id);
+        self.entries.insert(id, (bounds, node));
+        self.split_if_needed(node);
+    }
+
+    /// Moves a stored point, returning `false` when `id` is unknown.
+    pub fn move_point(&mut self, id: Id, point: Vector2d<T>) -> bool {
+        self.move_aabb(
+            id,
+            Aabb {
+                min: point,
+                max: point,
+            },
+        )
+    }
+
+    /// Moves a stored box, returning `false` when `id` is unknown. Entries
+    /// that stay inside their node are updated in place.
+    pub fn move_aabb(&mut self, id: Id, bounds: Aabb<T>) -> bool {
+        let Some(&(_, node)) = self.entries.get(&id) else {
+            return false;
+        };
+        let fits = node == 0 && self.nodes[0].children.is_none()
+            || self.nodes[node].bounds.contains_aabb(&bounds)
+                && self.child_containing(node, &bounds).is_none();
+        if fits {
+            self.entries.insert(id, (bounds, node));
+        } else {
+            self.remove(id);
+            self.insert_aabb(id, bounds);
+        }
+        true
+    }
+
+    /// Removes `id`, returning `false` when it was not stored.
+    pub fn remove(&mut self, id: Id) -> bool {
+        let Some((_, node)) = self.entries.remove(&id) else {
+            return false;
+        };
+        let items = &mut self.nodes[node].items;
+        if let Some(position) = items.iter().position(|&k| k == id) {
+            items.swap_remove(position);
+        }
+        self.merge_upwards(node);
+        true
+    }
+
+    /// Ids whose point or box overlaps `area`.
+    pub fn query_rect(&self, area: &Aabb<T>) -> Vec<Id> {
+        let mut found = Vec::new();
+        self.walk(
+            |bounds| bounds.overlaps(area),
+            |id, bounds| {
+                if bounds.overlaps(area) {
+                    found.push(id);
+                }
+            },
+        );
+        found
+    }
+
+    /// Ids whose point or box lies within `radius` of `center`.
+    pub fn query_circle(&self, center: Vector2d<T>, radius: T) -> Vec<Id> {
+        let mut found = Vec::new();
+        let limit = radius * radius;
+        self.walk(
+            |bounds| distance_squared(bounds, center) <= limit,
+            |id, bounds| {
+                if distance_squared(bounds, center) <= limit {
+                    found.push(id);
+                }
+            },
+        );
+        found
+    }
+
+    /// The `k` entries closest to `point` with their distances, nearest first.
+    /// Boxes are measured from their closest point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::quadtree_module::QuadTree;
+    /// use vectorlib::math::shape_module::Aabb;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let region = Aabb::new(Vector2d::new(0.0, 0.0), Vector2d::new(10.0, 10.0));
+    /// let mut tree: QuadTree<f64, char> = QuadTree::new(region);
+    /// tree.insert_point('a', Vector2d::new(1.0, 1.0));
+    /// tree.insert_point('b', Vector2d::new(4.0, 1.0));
+    /// tree.insert_point('c', Vector2d::new(9.0, 9.0));
+    /// let nearest = tree.nearest(Vector2d::new(0.0, 1.0), 2);
+    /// assert_eq!(nearest, vec![('a', 1.0), ('b', 4.0)]);
+    /// ```
+    pub fn nearest(&self, point: Vector2d<T>, k: usize) -> Vec<(Id, T)> {
+        let mut best: BinaryHeap<Ranked<T, Id>> = BinaryHeap::new();
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut frontier = BinaryHeap::new();
+        frontier.push(std::cmp::Reverse(Ranked {
+            key: T::zero(),
+            value: 0,
+        }));
+        while let Some(std::cmp::Reverse(Ranked { key, value: node })) = frontier.pop() {
+            if best.len() == k && best.peek().is_some_and(|worst| key > worst.key) {
+                break;
+            }
+            let node = &self.nodes[node];
+            for &id in &node.items {
+                let key = distance_squared(&self.entries[&id].0, point);
+                if best.len() < k {
+                    best.push(Ranked { key, value: id });
+                } else if best.peek().is_some_and(|worst| key < worst.key) {
+                    best.pop();
+                    best.push(Ranked { key, value: id });
+                }
+            }
+            if let Some(first) = node.children {
+                for child in first..first + 4 {
+                    frontier.push(std::cmp::Reverse(Ranked {
+                        key: distance_squared(&self.nodes[child].bounds, point),
+                        value: child,
+                    }));
+                }
+            }
+        }
+        best.into_sorted_vec()
+            .into_iter()
+            .map(|ranked| (ranked.value, ranked.key.sqrt()))
+            .collect()
+    }
+
+    /// Iterates over the live nodes depth first, starting at the root.
+    pub fn nodes(&self) -> Nodes<'_, T, Id> {
+        Nodes {
+            tree: self,
+            stack: vec![0],
+        }
+    }
+
+    fn leaf(bounds: Aabb<T>, depth: usize, parent: Option<usize>) -> Node<T, Id> {
+        Node {
+            bounds,
+            depth,
+            parent,
+            children: None,
+            items: Vec::new(),
+        }
+    }
+
+    fn child_containing(&self, node: usize, bounds: &Aabb<T>) -> Option<usize> {
+        let first = self.nodes[node].children?;
+        (first..first + 4).find(|&child| self.nodes[child].bounds.contains_aabb(bounds))
+    }
+
+    fn split_if_needed(&mut self, node: usize) {
+        let (region, depth) = (self.nodes[node].bounds, self.nodes[node].depth);
+        if self.nodes[node].children.is_some()
+            || self.nodes[node].items.len() <= self.capacity
+            || depth >= self.max_depth
+        {
+            return;
+        }
+        let center = region.center();
+        let quadrants = [
+            Aabb::new(region.min, center),
+            Aabb::new(
+                Vector2d::new(center.x, region.min.y),
+                Vector2d::new(region.max.x, center.y),
+            ),
+            Aabb::new(
+                Vector2d::new(region.min.x, center.y),
+                Vector2d::new(center.x, region.max.y),
+            ),
+            Aabb::new(center, region.max),
+        ];
+        let first = match self.free.pop() {
+            Some(first) => first,
+            None => {
+                self.nodes
+                    .extend(quadrants.iter().map(|q| Self::leaf(*q, 0, None)));
+                self.nodes.len() - 4
+            }
+        };
+        for (offset, quadrant) in quadrants.iter().enumerate() {
+            self.nodes[first + offset] = Self::leaf(*quadrant, depth + 1, Some(node));
+        }
+        self.nodes[node].children = Some(first);
+
+        // Push the entries that fit a quadrant one level down.
+        let items = std::mem::take(&mut self.nodes[node].items);
+        for id in items {
+            let bounds = self.entries[&id].0;
+            let target = self.child_containing(node, &bounds).unwrap_or(node);
+            self.nodes[target].items.push(id);
+            self.entries.insert(id, (bounds, target));
+        }
+        for child in first..first + 4 {
+            self.split_if_needed(child);
+        }
+    }
+
+    // Collapses every ancestor whose whole subtree fits within `capacity`.
+    fn merge_upwards(&mut self, node: usize) {
+        let mut current = if self.nodes[node].children.is_some() {
+            Some(node)
+        } else {
+            self.nodes[node].parent
+        };
+        while let Some(node) = current {
+            if self.subtree_count(node) > self.capacity {
+                break;
+            }
+            let first = self.nodes[node].children.take().unwrap_or(0);
+            for child in first..first + 4 {
+                self.collapse_into(child, node);
+            }
+            self.free.push(first);
+            current = self.nodes[node].parent;
+        }
+    }
+
+    fn collapse_into(&mut self, from: usize, into: usize) {
+        if let Some(first) = self.nodes[from].children.take() {
+            for child in first..first + 4 {
+                self.collapse_into(child, into);
+            }
+            self.free.push(first);
+        }
+        for id in std::mem::take(&mut self.nodes[from].items) {
+            if let Some(entry) = self.entries.get_mut(&id) {
+                entry.1 = into;
+            }
+            self.nodes[into].items.push(id);
+        }
+    }
+
+    fn subtree_count(&self, node: usize) -> usize {
+        let node = &self.nodes[node];
+        node.items.len()
+            + node.children.map_or(0, |first| {
+                (first..first + 4).map(|c| self.subtree_count(c)).sum()
+            })
+    }
+
+    fn walk<P, V>(&self, mut prune: P, mut visit: V)
+    where
+        P: FnMut(&Aabb<T>) -> bool,
+        V: FnMut(Id, &Aabb<T>),
+    {
+        // The root is always visited so entries outside its region are found.
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            for &id in &node.items {
+                visit(id, &self.entries[&id].0);
+            }
+            if let Some(first) = node.children {
+                stack.extend((first..first + 4).filter(|&c| prune(&self.nodes[c].bounds)));
+            }
+        }
+    }
+}
+
+/// Depth first iterator over the nodes of a `QuadTree`.
+pub struct Nodes<'a, T, Id>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    tree: &'a QuadTree<T, Id>,
+    stack: Vec<usize>,
+}
+
+impl<T, Id> Iterator for Nodes<'_, T, Id>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    type Item = QuadTreeNode<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = &self.tree.nodes[self.stack.pop()?];
+        if let Some(first) = node.children {
+            self.stack.extend((first..first + 4).rev());
+        }
+        Some(QuadTreeNode {
+            bounds: node.bounds,
+            depth: node.depth,
+            item_count: node.items.len(),
+            is_leaf: node.children.is_none(),
+        })
+    }
+}
+
+// Heap entry ordered by `key` only.
+#[derive(Debug, Copy, Clone)]
+struct Ranked<T, V> {
+    key: T,
+    value: V,
+}
+
+impl<T: PartialOrd, V> PartialEq for Ranked<T, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: PartialOrd, V> Eq for Ranked<T, V> {}
+
+impl<T: PartialOrd, V> PartialOrd for Ranked<T, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialOrd, V> Ord for Ranked<T, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.partial_cmp(&other.key).unwrap_or(Ordering::Equal)
+    }
+}
+
+// Squared distance from `point` to the closest point of `bounds`.
+fn distance_squared<T>(bounds: &Aabb<T>, point: Vector2d<T>) -> T
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let dx = (bounds.min.x - point.x)
+        .max(point.x - bounds.max.x)
+        .max(T::zero());
+    let dy = (bounds.min.y - point.y)
+        .max(point.y - bounds.max.y)
+        .max(T::zero());
+    dx * dx + dy * dy
+}
@@ -0,0 +1,470 @@
+use crate::math::polygon_offset_module::signed_area;
+use crate::math::scalar_module::{cast, pi};
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        Distributions
+// -----------------------------------------
+
+// A uniform sample in `[0, 1)`. Casting to a narrower `T` can round draws
+// just below one up to exactly one, so those are pulled back under it.
+fn unit<T: Float, R: Rng + ?Sized>(rng: &mut R) -> T {
+    let value: T = cast(rng.gen::<f64>());
+    if value < T::one() {
+        value
+    } else {
+        T::one() - T::epsilon() / cast(2.0)
+    }
+}
+
+// A uniform sample in `(0, 1]`, safe to take the logarithm of. The smallest
+// value, 2^-53, is representable in `f32` too.
+fn unit_open<T: Float, R: Rng + ?Sized>(rng: &mut R) -> T {
+    cast(1.0 - rng.gen::<f64>())
+}
+
+/// Uniform points in the rectangle spanned by `min` and `max`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct UniformRect<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub min: Vector2d<T>,
+    pub max: Vector2d<T>,
+}
+
+impl<T> UniformRect<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub fn new(min: Vector2d<T>, max: Vector2d<T>) -> Self {
+        Self { min, max }
+    }
+}
+
+impl<T> Distribution<Vector2d<T>> for UniformRect<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector2d<T> {
+        Vector2d::new(
+            self.min.x + (self.max.x - self.min.x) * unit(rng),
+            self.min.y + (self.max.y - self.min.y) * unit(rng),
+        )
+    }
+}
+
+/// Uniformly distributed unit vectors.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct UnitDirection;
+
+impl<T> Distribution<Vector2d<T>> for UnitDirection
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector2d<T> {
+        let angle = pi::<T>() * cast(2.0) * unit(rng);
+        Vector2d::new(angle.cos(), angle.sin())
+    }
+}
+
+/// Uniform points in a disk.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct UniformDisk<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub center: Vector2d<T>,
+    pub radius: T,
+}
+
+impl<T> UniformDisk<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub fn new(center: Vector2d<T>, radius: T) -> Self {
+        Self { center, radius }
+    }
+}
+
+impl<T> Distribution<Vector2d<T>> for UniformDisk<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector2d<T> {
+        // The square root keeps the density uniform over the area.
+        let direction: Vector2d<T> = UnitDirection.sample(rng);
+        self.center + direction * (self.radius * unit::<T, R>(rng).sqrt())
+    }
+}
+
+/// Uniform points in the ring between two radii.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct UniformAnnulus<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    center: Vector2d<T>,
+    inner: T,
+    outer: T,
+}
+
+impl<T> UniformAnnulus<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create the distribution.
+    ///
+    /// # Returns
+    ///
+    /// The distribution, or an `Err` when the radii are negative or `inner`
+    /// is larger than `outer`.
+    pub fn new(center: Vector2d<T>, inner: T, outer: T) -> Result<Self, String> {
+        if inner < T::zero() || outer < inner {
+            return Err(format!(
+                "[::ERROR::] -> Invalid annulus radii: {} and {}.",
+                inner, outer
+            ));
+        }
+        Ok(Self {
+            center,
+            inner,
+            outer,
+        })
+    }
+}
+
+impl<T> Distribution<Vector2d<T>> for UniformAnnulus<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector2d<T> {
+        let direction: Vector2d<T> = UnitDirection.sample(rng);
+        let (inner2, outer2) = (self.inner * self.inner, self.outer * self.outer);
+        let radius = (inner2 + (outer2 - inner2) * unit(rng)).sqrt();
+        self.center + direction * radius
+    }
+}
+
+/// Uniform points in a triangle.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct UniformTriangle<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub a: Vector2d<T>,
+    pub b: Vector2d<T>,
+    pub c: Vector2d<T>,
+}
+
+impl<T> UniformTriangle<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub fn new(a: Vector2d<T>, b: Vector2d<T>, c: Vector2d<T>) -> Self {
+        Self { a, b, c }
+    }
+}
+
+impl<T> Distribution<Vector2d<T>> for UniformTriangle<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector2d<T> {
+        let (mut u, mut v): (T, T) = (unit(rng), unit(rng));
+        // Fold the far half of the parallelogram back onto the triangle.
+        if u + v > T::one() {
+            u = T::one() - u;
+            v = T::one() - v;
+        }
+        self.a + (self.b - self.a) * u + (self.c - self.a) * v
+    }
+}
+
+/// Uniform points inside a simple polygon, convex or not.
+///
+/// The polygon is triangulated once by ear clipping; sampling picks a
+/// triangle weighted by its area, then a point inside it.
+///
+/// # Examples
+///
+/// ```
+/// use rand::distributions::Distribution;
+/// use rand::SeedableRng;
+/// use vectorlib::math::random_vector_module::UniformPolygon;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let l_shape = vec![
+///     Vector2d::new(0.0, 0.0),
+///     Vector2d::new(2.0, 0.0),
+///     Vector2d::new(2.0, 1.0),
+///     Vector2d::new(1.0, 1.0),
+///     Vector2d::new(1.0, 2.0),
+///     Vector2d::new(0.0, 2.0),
+/// ];
+/// let shape = UniformPolygon::new(&l_shape).unwrap();
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+/// for _ in 0..100 {
+///     let p: Vector2d<f64> = shape.sample(&mut rng);
+///     assert!(p.x <= 1.0 || p.y <= 1.0);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniformPolygon<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    triangles: Vec<UniformTriangle<T>>,
+    /// Running sum of the triangle areas.
+    cumulative: Vec<T>,
+}
+
+impl<T> UniformPolygon<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create the distribution from the polygon outline, in either winding.
+    ///
+    /// # Returns
+    ///
+    /// The distribution, or an `Err` when the polygon has no area or cannot
+    /// be triangulated (for example because it self-intersects).
+    pub fn new(points: &[Vector2d<T>]) -> Result<Self, String> {
+        let triangles = triangulate(points)?;
+        let mut cumulative = Vec::with_capacity(triangles.len());
+        let mut total = T::zero();
+        for triangle in &triangles {
+            total += ((triangle.b - triangle.a).cross_product(triangle.c - triangle.a) / cast(2.0))
+                .abs();
+            cumulative.push(total);
+        }
+        if total <= T::zero() {
+            return Err("[::ERROR::] -> Cannot sample a polygon without area.".to_string());
+        }
+        Ok(Self {
+            triangles,
+            cumulative,
+        })
+    }
+
+    pub fn area(&self) -> T {
+        self.cumulative.last().copied().unwrap_or(T::zero())
+    }
+}
+
+impl<T> Distribution<Vector2d<T>> for UniformPolygon<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector2d<T> {
+        let target = self.area() * unit(rng);
+        let index = self
+            .cumulative
+            .partition_point(|&area| area <= target)
+            .min(self.triangles.len() - 1);
+        self.triangles[index].sample(rng)
+    }
+}
+
+/// Normally distributed points around `mean`, with independent standard
+/// deviations per axis.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Gaussian2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub mean: Vector2d<T>,
+    pub std_dev: Vector2d<T>,
+}
+
+impl<T> Gaussian2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create an isotropic distribution.
+    pub fn new(mean: Vector2d<T>, std_dev: T) -> Self {
+        Self {
+            mean,
+            std_dev: Vector2d::new(std_dev, std_dev),
+        }
+    }
+}
+
+impl<T> Distribution<Vector2d<T>> for Gaussian2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector2d<T> {
+        // Box-Muller transform: both outputs of one draw are used.
+        let u: T = unit_open(rng);
+        let angle = pi::<T>() * cast(2.0) * unit(rng);
+        let radius = (cast::<T>(-2.0) * u.ln()).sqrt();
+        Vector2d::new(
+            self.mean.x + self.std_dev.x * radius * angle.cos(),
+            self.mean.y + self.std_dev.y * radius * angle.sin(),
+        )
+    }
+}
+
+/// Uniform vectors in the unit square `[0, 1) x [0, 1)`.
+impl<T> Distribution<Vector2d<T>> for Standard
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector2d<T> {
+        Vector2d::new(unit(rng), unit(rng))
+    }
+}
+
+// -----------------------------------------
+//        Vector2d Constructors
+// -----------------------------------------
+
+impl<T> Vector2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// A uniform random point in the rectangle spanned by `min` and `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    /// let p = Vector2d::random_in_rect(&mut rng, Vector2d::new(-1.0, 0.0), Vector2d::new(1.0, 5.0));
+    /// assert!(p.x >= -1.0 && p.x < 1.0 && p.y >= 0.0 && p.y < 5.0);
+    /// ```
+    pub fn random_in_rect<R: Rng + ?Sized>(rng: &mut R, min: Self, max: Self) -> Self {
+        UniformRect::new(min, max).sample(rng)
+    }
+
+    /// A uniform random unit vector.
+    pub fn random_unit<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        UnitDirection.sample(rng)
+    }
+
+    /// A uniform random point in the disk of `radius` around `center`.
+    pub fn random_in_disk<R: Rng + ?Sized>(rng: &mut R, center: Self, radius: T) -> Self {
+        UniformDisk::new(center, radius).sample(rng)
+    }
+
+    /// A uniform random point between the circles of radius `inner` and
+    /// `outer` around `center`.
+    ///
+    /// # Returns
+    ///
+    /// The point, or an `Err` when the radii are invalid.
+    pub fn random_in_annulus<R: Rng + ?Sized>(
+        rng: &mut R,
+        center: Self,
+        inner: T,
+        outer: T,
+    ) -> Result<Self, String> {
+        Ok(UniformAnnulus::new(center, inner, outer)?.sample(rng))
+    }
+
+    /// A uniform random point in the triangle `a`, `b`, `c`.
+    pub fn random_in_triangle<R: Rng + ?Sized>(rng: &mut R, a: Self, b: Self, c: Self) -> Self {
+        UniformTriangle::new(a, b, c).sample(rng)
+    }
+
+    /// A uniform random point inside a simple polygon. The polygon is
+    /// triangulated on every call; keep a `UniformPolygon` to draw many
+    /// points from the same shape.
+    pub fn random_in_polygon<R: Rng + ?Sized>(
+        rng: &mut R,
+        points: &[Self],
+    ) -> Result<Self, String> {
+        Ok(UniformPolygon::new(points)?.sample(rng))
+    }
+
+    /// A normally distributed point around `mean`.
+    pub fn random_gaussian<R: Rng + ?Sized>(rng: &mut R, mean: Self, std_dev: T) -> Self {
+        Gaussian2d::new(mean, std_dev).sample(rng)
+    }
+}
+
+// -----------------------------------------
+//        Triangulation
+// -----------------------------------------
+
+// Ear clipping triangulation of a simple polygon in either winding.
+pub(crate) fn triangulate<T>(points: &[Vector2d<T>]) -> Result<Vec<UniformTriangle<T>>, String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    if points.len() < 3 {
+        return Err("[::ERROR::] -> A polygon needs at least 3 points.".to_string());
+    }
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    if signed_area(points) < T::zero() {
+        remaining.reverse();
+    }
+    let mut triangles = Vec::with_capacity(points.len() - 2);
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let ear = (0..n).find(|&i| {
+            let (a, b, c) = (
+                points[remaining[(i + n - 1) % n]],
+                points[remaining[i]],
+                points[remaining[(i + 1) % n]],
+            );
+            if (b - a).cross_product(c - b) <= T::zero() {
+                return false;
+            }
+            // No other vertex may lie inside the candidate ear.
+            remaining.iter().all(|&k| {
+                let p = points[k];
+                p == a
+                    || p == b
+                    || p == c
+                    || (b - a).cross_product(p - a) < T::zero()
+                    || (c - b).cross_product(p - b) < T::zero()
+                    || (a - c).cross_product(p - c) < T::zero()
+            })
+        });
+        let Some(i) = ear else {
+            // Only collinear vertices left, or the outline self-intersects.
+            let stuck = (0..n).find(|&i| {
+                let (a, b, c) = (
+                    points[remaining[(i + n - 1) % n]],
+                    points[remaining[i]],
+                    points[remaining[(i + 1) % n]],
+                );
+                (b - a).cross_product(c - b) == T::zero()
+            });
+            match stuck {
+                Some(i) => {
+                    remaining.remove(i);
+                    continue;
+                }
+                None => {
+                    return Err(
+                        "[::ERROR::] -> Cannot triangulate a self-intersecting polygon."
+                            .to_string(),
+                    )
+                }
+            }
+        };
+        triangles.push(UniformTriangle::new(
+            points[remaining[(i + n - 1) % n]],
+            points[remaining[i]],
+            points[remaining[(i + 1) % n]],
+        ));
+        remaining.remove(i);
+    }
+    triangles.push(UniformTriangle::new(
+        points[remaining[0]],
+        points[remaining[1]],
+        points[remaining[2]],
+    ));
+    Ok(triangles)
+}
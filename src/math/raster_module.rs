@@ -0,0 +1,597 @@
+use crate::math::scalar_module::vector_to_f64;
+use crate::math::svg_path_module::{PathSegment, SvgPath};
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+/// Sub-scanlines sampled per pixel row when filling polygons.
+pub const SUBSCANLINES: usize = 8;
+
+/// Maximum distance, in pixels, between a Bezier curve and its flattening.
+pub const FLATTEN_TOLERANCE: f64 = 0.25;
+
+// -----------------------------------------
+//        Colors
+// -----------------------------------------
+
+/// A straight (not premultiplied) 8-bit RGBA color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    pub const TRANSPARENT: Rgba = Rgba::new(0, 0, 0, 0);
+    pub const BLACK: Rgba = Rgba::rgb(0, 0, 0);
+    pub const WHITE: Rgba = Rgba::rgb(255, 255, 255);
+    pub const RED: Rgba = Rgba::rgb(255, 0, 0);
+    pub const GREEN: Rgba = Rgba::rgb(0, 255, 0);
+    pub const BLUE: Rgba = Rgba::rgb(0, 0, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// An opaque color.
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::new(r, g, b, 255)
+    }
+
+    /// Composites `self` scaled by `coverage` over `destination`
+    /// (source-over).
+    pub fn over(self, destination: Rgba, coverage: f64) -> Rgba {
+        let source_alpha = self.a as f64 / 255.0 * coverage.clamp(0.0, 1.0);
+        if source_alpha <= 0.0 {
+            return destination;
+        }
+        let destination_alpha = destination.a as f64 / 255.0 * (1.0 - source_alpha);
+        let alpha = source_alpha + destination_alpha;
+        let channel = |source: u8, destination: u8| {
+            let value =
+                (source as f64 * source_alpha + destination as f64 * destination_alpha) / alpha;
+            value.round().clamp(0.0, 255.0) as u8
+        };
+        Rgba::new(
+            channel(self.r, destination.r),
+            channel(self.g, destination.g),
+            channel(self.b, destination.b),
+            (alpha * 255.0).round().clamp(0.0, 255.0) as u8,
+        )
+    }
+}
+
+/// Decides which points a self-intersecting or multi-contour polygon covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// Inside where a ray crosses the outline an odd number of times.
+    EvenOdd,
+    /// Inside where the outline winds around the point at least once.
+    #[default]
+    NonZero,
+}
+
+// -----------------------------------------
+//        Image Buffer
+// -----------------------------------------
+
+/// An RGBA image with anti-aliased drawing operations, for rendering frames
+/// without a GPU.
+///
+/// Coordinates are in pixels with `y` pointing down; the center of pixel
+/// `(i, j)` is at `(i + 0.5, j + 0.5)`. Everything drawn outside the image
+/// is clipped.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::raster_module::{FillRule, ImageBuffer, Rgba};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let mut image = ImageBuffer::filled(32, 32, Rgba::WHITE);
+/// let square = [
+///     Vector2d::new(8.0, 8.0),
+///     Vector2d::new(24.0, 8.0),
+///     Vector2d::new(24.0, 24.0),
+///     Vector2d::new(8.0, 24.0),
+/// ];
+/// image.fill_polygon(&square, FillRule::NonZero, Rgba::RED);
+/// assert_eq!(image.get(16, 16), Some(Rgba::RED));
+/// assert_eq!(image.get(2, 2), Some(Rgba::WHITE));
+/// assert!(image.to_ppm().starts_with(b"P6\n32 32\n255\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageBuffer {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgba>,
+}
+
+impl ImageBuffer {
+    /// A transparent image.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::filled(width, height, Rgba::TRANSPARENT)
+    }
+
+    pub fn filled(width: usize, height: usize, color: Rgba) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![color; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The pixels in row-major order, top row first.
+    pub fn pixels(&self) -> &[Rgba] {
+        &self.pixels
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgba> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// Overwrites a pixel; out of range coordinates are ignored.
+    pub fn set(&mut self, x: usize, y: usize, color: Rgba) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    pub fn clear(&mut self, color: Rgba) {
+        self.pixels.fill(color);
+    }
+
+    /// Composites `color` at `coverage` over pixel `(x, y)`, ignoring pixels
+    /// outside the image.
+    pub fn blend(&mut self, x: i64, y: i64, color: Rgba, coverage: f64) {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return;
+        }
+        let index = y as usize * self.width + x as usize;
+        self.pixels[index] = color.over(self.pixels[index], coverage);
+    }
+
+    // -----------------------------------------
+    //        Strokes
+    // -----------------------------------------
+
+    /// Draws a one pixel wide anti-aliased line with Xiaolin Wu's algorithm.
+    pub fn draw_line<T>(&mut self, from: Vector2d<T>, to: Vector2d<T>, color: Rgba)
+    where
+        T: Float + AddAssign + std::fmt::Display,
+    {
+        self.wu_line(vector_to_f64(from), vector_to_f64(to), color);
+    }
+
+    /// Draws lines through `points`, back to the first one when `closed`.
+    pub fn draw_polyline<T>(&mut self, points: &[Vector2d<T>], closed: bool, color: Rgba)
+    where
+        T: Float + AddAssign + std::fmt::Display,
+    {
+        let points: Vec<Vector2d<f64>> = points.iter().map(|p| vector_to_f64(*p)).collect();
+        self.stroke_points(&points, closed, color);
+    }
+
+    /// Strokes a quadratic Bezier curve, flattened to within
+    /// `FLATTEN_TOLERANCE` pixels.
+    pub fn draw_quadratic<T>(
+        &mut self,
+        from: Vector2d<T>,
+        control: Vector2d<T>,
+        to: Vector2d<T>,
+        color: Rgba,
+    ) where
+        T: Float + AddAssign + std::fmt::Display,
+    {
+        let mut points = vec![vector_to_f64(from)];
+        flatten_quadratic(&mut points, vector_to_f64(control), vector_to_f64(to));
+        self.stroke_points(&points, false, color);
+    }
+
+    /// Strokes a cubic Bezier curve, flattened to within `FLATTEN_TOLERANCE`
+    /// pixels.
+    pub fn draw_cubic<T>(
+        &mut self,
+        from: Vector2d<T>,
+        control1: Vector2d<T>,
+        control2: Vector2d<T>,
+        to: Vector2d<T>,
+        color: Rgba,
+    ) where
+        T: Float + AddAssign + std::fmt::Display,
+    {
+        let mut points = vec![vector_to_f64(from)];
+        flatten_cubic(
+            &mut points,
+            vector_to_f64(control1),
+            vector_to_f64(control2),
+            vector_to_f64(to),
+        );
+        self.stroke_points(&points, false, color);
+    }
+
+    /// Strokes every sub-path of a parsed SVG path, in pixel coordinates.
+    pub fn draw_path(&mut self, path: &SvgPath, color: Rgba) {
+        for (points, closed) in flatten_path(path) {
+            self.stroke_points(&points, closed, color);
+        }
+    }
+
+    /// Draws an anti-aliased circle outline `width` pixels wide.
+    pub fn draw_circle<T>(&mut self, center: Vector2d<T>, radius: T, width: T, color: Rgba)
+    where
+        T: Float + AddAssign + std::fmt::Display,
+    {
+        let center = vector_to_f64(center);
+        let radius = radius.to_f64().unwrap_or(0.0).abs();
+        let half = width.to_f64().unwrap_or(0.0).abs() / 2.0;
+        self.shade_disk(center, radius + half, color, |distance| {
+            (half + 0.5 - (distance - radius).abs()).clamp(0.0, 1.0)
+        });
+    }
+
+    // -----------------------------------------
+    //        Fills
+    // -----------------------------------------
+
+    /// Fills an anti-aliased disk.
+    pub fn fill_circle<T>(&mut self, center: Vector2d<T>, radius: T, color: Rgba)
+    where
+        T: Float + AddAssign + std::fmt::Display,
+    {
+        let radius = radius.to_f64().unwrap_or(0.0).abs();
+        self.shade_disk(vector_to_f64(center), radius, color, |distance| {
+            (radius + 0.5 - distance).clamp(0.0, 1.0)
+        });
+    }
+
+    /// Fills a polygon with anti-aliased edges. The outline is closed
+    /// implicitly and may self-intersect; `rule` decides what is inside.
+    pub fn fill_polygon<T>(&mut self, points: &[Vector2d<T>], rule: FillRule, color: Rgba)
+    where
+        T: Float + AddAssign + std::fmt::Display,
+    {
+        let contour: Vec<Vector2d<f64>> = points.iter().map(|p| vector_to_f64(*p)).collect();
+        self.fill_contours(&[contour], rule, color);
+    }
+
+    /// Fills all sub-paths of a parsed SVG path as one shape, so holes
+    /// follow `rule`. Open sub-paths are closed.
+    pub fn fill_path(&mut self, path: &SvgPath, rule: FillRule, color: Rgba) {
+        let contours: Vec<Vec<Vector2d<f64>>> = flatten_path(path)
+            .into_iter()
+            .map(|(points, _)| points)
+            .collect();
+        self.fill_contours(&contours, rule, color);
+    }
+
+    // -----------------------------------------
+    //        Output
+    // -----------------------------------------
+
+    /// Encodes the image as a binary PPM (`P6`). PPM has no alpha channel, so
+    /// the image is composited over `background` first.
+    pub fn to_ppm_over(&self, background: Rgba) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.reserve(self.pixels.len() * 3);
+        for pixel in &self.pixels {
+            let color = pixel.over(Rgba::rgb(background.r, background.g, background.b), 1.0);
+            bytes.extend([color.r, color.g, color.b]);
+        }
+        bytes
+    }
+
+    /// Encodes the image as a binary PPM (`P6`) over a black background.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        self.to_ppm_over(Rgba::BLACK)
+    }
+
+    /// Encodes the image as a PAM (`P7`, `RGB_ALPHA`), keeping the alpha
+    /// channel.
+    pub fn to_pam(&self) -> Vec<u8> {
+        let mut bytes = format!(
+            "P7\nWIDTH {}\nHEIGHT {}\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR\n",
+            self.width, self.height
+        )
+        .into_bytes();
+        bytes.reserve(self.pixels.len() * 4);
+        for pixel in &self.pixels {
+            bytes.extend([pixel.r, pixel.g, pixel.b, pixel.a]);
+        }
+        bytes
+    }
+
+    /// Writes the image to `path`, as PAM when the extension is `pam` and as
+    /// PPM otherwise.
+    ///
+    /// # Returns
+    ///
+    /// An `Err` when the file cannot be written.
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), String> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("pam") => self.to_pam(),
+            _ => self.to_ppm(),
+        };
+        std::fs::write(path, bytes)
+            .map_err(|error| format!("[::ERROR::] -> Cannot write {}: {}.", path.display(), error))
+    }
+
+    // -----------------------------------------
+    //        Internals
+    // -----------------------------------------
+
+    fn stroke_points(&mut self, points: &[Vector2d<f64>], closed: bool, color: Rgba) {
+        for pair in points.windows(2) {
+            self.wu_line(pair[0], pair[1], color);
+        }
+        if closed && points.len() > 2 {
+            self.wu_line(points[points.len() - 1], points[0], color);
+        }
+    }
+
+    fn wu_line(&mut self, from: Vector2d<f64>, to: Vector2d<f64>, color: Rgba) {
+        if !(from.x.is_finite() && from.y.is_finite() && to.x.is_finite() && to.y.is_finite()) {
+            return;
+        }
+        // Wu works with pixel centers on integer coordinates.
+        let (mut x0, mut y0) = (from.x - 0.5, from.y - 0.5);
+        let (mut x1, mut y1) = (to.x - 0.5, to.y - 0.5);
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+        if steep {
+            std::mem::swap(&mut x0, &mut y0);
+            std::mem::swap(&mut x1, &mut y1);
+        }
+        if x0 > x1 {
+            std::mem::swap(&mut x0, &mut x1);
+            std::mem::swap(&mut y0, &mut y1);
+        }
+        let dx = x1 - x0;
+        let gradient = if dx == 0.0 { 1.0 } else { (y1 - y0) / dx };
+        let plot = |image: &mut Self, x: f64, y: f64, coverage: f64| {
+            let (x, y) = (x as i64, y as i64);
+            if steep {
+                image.blend(y, x, color, coverage);
+            } else {
+                image.blend(x, y, color, coverage);
+            }
+        };
+
+        // End points, weighted by how much of their pixel the line covers.
+        let x_start = x0.round();
+        let y_start = y0 + gradient * (x_start - x0);
+        let gap_start = 1.0 - fract(x0 + 0.5);
+        plot(
+            self,
+            x_start,
+            y_start.floor(),
+            (1.0 - fract(y_start)) * gap_start,
+        );
+        plot(
+            self,
+            x_start,
+            y_start.floor() + 1.0,
+            fract(y_start) * gap_start,
+        );
+
+        let x_end = x1.round();
+        let y_end = y1 + gradient * (x_end - x1);
+        let gap_end = fract(x1 + 0.5);
+        plot(self, x_end, y_end.floor(), (1.0 - fract(y_end)) * gap_end);
+        plot(self, x_end, y_end.floor() + 1.0, fract(y_end) * gap_end);
+
+        // Skip the columns that lie entirely outside the image.
+        let limit = if steep { self.height } else { self.width } as f64;
+        let first = (x_start + 1.0).max(0.0);
+        let last = (x_end - 1.0).min(limit);
+        let mut y = y_start + gradient * (first - x_start);
+        let mut x = first;
+        while x <= last {
+            plot(self, x, y.floor(), 1.0 - fract(y));
+            plot(self, x, y.floor() + 1.0, fract(y));
+            y += gradient;
+            x += 1.0;
+        }
+    }
+
+    fn shade_disk<F>(&mut self, center: Vector2d<f64>, reach: f64, color: Rgba, coverage: F)
+    where
+        F: Fn(f64) -> f64,
+    {
+        if !(center.x.is_finite() && center.y.is_finite() && reach.is_finite()) {
+            return;
+        }
+        let reach = reach + 1.0;
+        let (x0, x1) = self.clip_span(center.x - reach, center.x + reach, self.width);
+        let (y0, y1) = self.clip_span(center.y - reach, center.y + reach, self.height);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let pixel = Vector2d::new(x as f64 + 0.5, y as f64 + 0.5);
+                let amount = coverage(pixel.distance(center));
+                if amount > 0.0 {
+                    self.blend(x as i64, y as i64, color, amount);
+                }
+            }
+        }
+    }
+
+    fn clip_span(&self, from: f64, to: f64, size: usize) -> (usize, usize) {
+        let from = from.floor().clamp(0.0, size as f64) as usize;
+        let to = to.ceil().clamp(0.0, size as f64) as usize;
+        (from, to)
+    }
+
+    // Scanline fill: each pixel row is sampled on `SUBSCANLINES` horizontal
+    // lines, and every inside span adds its exact horizontal overlap with a
+    // pixel to that pixel's coverage.
+    fn fill_contours(&mut self, contours: &[Vec<Vector2d<f64>>], rule: FillRule, color: Rgba) {
+        let mut edges = Vec::new();
+        for contour in contours {
+            if contour.len() < 3 {
+                continue;
+            }
+            for (index, &a) in contour.iter().enumerate() {
+                let b = contour[(index + 1) % contour.len()];
+                if !(a.x.is_finite() && a.y.is_finite() && b.x.is_finite() && b.y.is_finite()) {
+                    return;
+                }
+                if a.y != b.y {
+                    edges.push((a, b));
+                }
+            }
+        }
+        if edges.is_empty() {
+            return;
+        }
+        let top = edges
+            .iter()
+            .map(|(a, b)| a.y.min(b.y))
+            .fold(f64::INFINITY, f64::min);
+        let bottom = edges
+            .iter()
+            .map(|(a, b)| a.y.max(b.y))
+            .fold(f64::NEG_INFINITY, f64::max);
+        let (row_start, row_end) = self.clip_span(top, bottom, self.height);
+
+        let weight = 1.0 / SUBSCANLINES as f64;
+        let width = self.width as f64;
+        let mut coverage = vec![0.0; self.width];
+        let mut crossings: Vec<(f64, i32)> = Vec::new();
+        for row in row_start..row_end {
+            coverage.iter_mut().for_each(|value| *value = 0.0);
+            for sample in 0..SUBSCANLINES {
+                let y = row as f64 + (sample as f64 + 0.5) * weight;
+                crossings.clear();
+                for (a, b) in &edges {
+                    let (low, high, winding) = if a.y < b.y { (a, b, 1) } else { (b, a, -1) };
+                    if y >= low.y && y < high.y {
+                        let x = low.x + (y - low.y) * (high.x - low.x) / (high.y - low.y);
+                        crossings.push((x, winding));
+                    }
+                }
+                crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+                let mut winding = 0;
+                for pair in crossings.windows(2) {
+                    winding += pair[0].1;
+                    let inside = match rule {
+                        FillRule::EvenOdd => winding % 2 != 0,
+                        FillRule::NonZero => winding != 0,
+                    };
+                    if !inside {
+                        continue;
+                    }
+                    let start = pair[0].0.clamp(0.0, width);
+                    let end = pair[1].0.clamp(0.0, width);
+                    if end <= start {
+                        continue;
+                    }
+                    let (first, last) = (start.floor() as usize, end.floor() as usize);
+                    if first == last {
+                        coverage[first] += (end - start) * weight;
+                        continue;
+                    }
+                    coverage[first] += (first as f64 + 1.0 - start) * weight;
+                    for value in &mut coverage[first + 1..last] {
+                        *value += weight;
+                    }
+                    if last < self.width {
+                        coverage[last] += (end - last as f64) * weight;
+                    }
+                }
+            }
+            for (x, &amount) in coverage.iter().enumerate() {
+                if amount > 0.0 {
+                    self.blend(x as i64, row as i64, color, amount.min(1.0));
+                }
+            }
+        }
+    }
+}
+
+// Fractional part that stays in [0, 1) for negative values.
+fn fract(value: f64) -> f64 {
+    value - value.floor()
+}
+
+// -----------------------------------------
+//        Flattening
+// -----------------------------------------
+
+// Wang's formula: the number of uniform steps that keeps a degree `n` curve
+// within the tolerance, given the largest second difference of its points.
+fn flatten_steps(second_difference: f64, degree: f64) -> usize {
+    let steps = (degree * (degree - 1.0) / 8.0 * second_difference / FLATTEN_TOLERANCE).sqrt();
+    if steps.is_finite() {
+        (steps.ceil() as usize).clamp(1, 4096)
+    } else {
+        1
+    }
+}
+
+fn flatten_quadratic(points: &mut Vec<Vector2d<f64>>, control: Vector2d<f64>, to: Vector2d<f64>) {
+    let from = points[points.len() - 1];
+    let steps = flatten_steps((from - control * 2.0 + to).magnitude(), 2.0);
+    for step in 1..=steps {
+        let t = step as f64 / steps as f64;
+        let u = 1.0 - t;
+        points.push(from * (u * u) + control * (2.0 * u * t) + to * (t * t));
+    }
+}
+
+fn flatten_cubic(
+    points: &mut Vec<Vector2d<f64>>,
+    control1: Vector2d<f64>,
+    control2: Vector2d<f64>,
+    to: Vector2d<f64>,
+) {
+    let from = points[points.len() - 1];
+    let second_difference = (from - control1 * 2.0 + control2)
+        .magnitude()
+        .max((control1 - control2 * 2.0 + to).magnitude());
+    let steps = flatten_steps(second_difference, 3.0);
+    for step in 1..=steps {
+        let t = step as f64 / steps as f64;
+        let u = 1.0 - t;
+        points.push(
+            from * (u * u * u)
+                + control1 * (3.0 * u * u * t)
+                + control2 * (3.0 * u * t * t)
+                + to * (t * t * t),
+        );
+    }
+}
+
+fn flatten_path(path: &SvgPath) -> Vec<(Vec<Vector2d<f64>>, bool)> {
+    path.subpaths
+        .iter()
+        .map(|subpath| {
+            let mut points = vec![subpath.start];
+            for segment in &subpath.segments {
+                match *segment {
+                    PathSegment::Line { to } => points.push(to),
+                    PathSegment::Quadratic { control, to } => {
+                        flatten_quadratic(&mut points, control, to)
+                    }
+                    PathSegment::Cubic {
+                        control1,
+                        control2,
+                        to,
+                    } => flatten_cubic(&mut points, control1, control2, to),
+                }
+            }
+            (points, subpath.closed)
+        })
+        .collect()
+}
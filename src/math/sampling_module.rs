@@ -0,0 +1,383 @@
+use crate::math::random_vector_module::{UniformAnnulus, UniformPolygon, UniformRect};
+use crate::math::scalar_module::cast;
+use crate::math::shape_module::Aabb;
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+use rand::distributions::Distribution;
+use rand::Rng;
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+/// Candidates tried around an active sample before it is retired
+/// (the `k` of Bridson's algorithm).
+pub const POISSON_ATTEMPTS: usize = 30;
+
+// Cells of the background grid beyond which we refuse to sample.
+const MAX_GRID_CELLS: usize = 1 << 26;
+
+// -----------------------------------------
+//        Poisson-Disk Sampling
+// -----------------------------------------
+
+/// Bridson's Poisson-disk sampling in `bounds`: no two points are closer than
+/// `radius`, and no more points fit.
+///
+/// # Returns
+///
+/// The points, or an `Err` when `radius` is not positive or too small for
+/// the size of `bounds`.
+///
+/// # Examples
+///
+/// ```
+/// use rand::SeedableRng;
+/// use vectorlib::math::sampling_module::poisson_disk_rect;
+/// use vectorlib::math::shape_module::Aabb;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+/// let bounds = Aabb::new(Vector2d::new(0.0, 0.0), Vector2d::new(10.0, 10.0));
+/// let points = poisson_disk_rect(&mut rng, &bounds, 1.0).unwrap();
+/// for (i, a) in points.iter().enumerate() {
+///     for b in &points[i + 1..] {
+///         assert!(a.distance(*b) >= 1.0);
+///     }
+/// }
+/// ```
+pub fn poisson_disk_rect<T, R>(
+    rng: &mut R,
+    bounds: &Aabb<T>,
+    radius: T,
+) -> Result<Vec<Vector2d<T>>, String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    R: Rng + ?Sized,
+{
+    let seed = UniformRect::new(bounds.min, bounds.max).sample(rng);
+    poisson_disk(rng, bounds, seed, radius, radius, |_| radius, |_| true)
+}
+
+/// Poisson-disk sampling restricted to the inside of a simple polygon.
+///
+/// # Returns
+///
+/// The points, or an `Err` when `radius` is invalid or the polygon has no
+/// area.
+pub fn poisson_disk_polygon<T, R>(
+    rng: &mut R,
+    polygon: &[Vector2d<T>],
+    radius: T,
+) -> Result<Vec<Vector2d<T>>, String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    R: Rng + ?Sized,
+{
+    let seed = UniformPolygon::new(polygon)?.sample(rng);
+    let bounds = polygon.iter().fold(Aabb::new(seed, seed), |acc, p| {
+        acc.union(&Aabb::new(*p, *p))
+    });
+    poisson_disk(
+        rng,
+        &bounds,
+        seed,
+        radius,
+        radius,
+        |_| radius,
+        |p| point_in_polygon(p, polygon),
+    )
+}
+
+/// Poisson-disk sampling with a radius varying over `bounds`, for example to
+/// thin out foliage away from a path. Two points are at least the mean of
+/// their radii apart; `radius_at` is clamped to `[min_radius, max_radius]`.
+///
+/// # Returns
+///
+/// The points, or an `Err` when the radii are invalid.
+pub fn poisson_disk_variable<T, R, F>(
+    rng: &mut R,
+    bounds: &Aabb<T>,
+    min_radius: T,
+    max_radius: T,
+    radius_at: F,
+) -> Result<Vec<Vector2d<T>>, String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    R: Rng + ?Sized,
+    F: Fn(Vector2d<T>) -> T,
+{
+    if max_radius < min_radius {
+        return Err(format!(
+            "[::ERROR::] -> The maximum radius {} is below the minimum radius {}.",
+            max_radius, min_radius
+        ));
+    }
+    let seed = UniformRect::new(bounds.min, bounds.max).sample(rng);
+    poisson_disk(
+        rng,
+        bounds,
+        seed,
+        min_radius,
+        max_radius,
+        |p| radius_at(p).max(min_radius).min(max_radius),
+        |_| true,
+    )
+}
+
+fn poisson_disk<T, R, F, G>(
+    rng: &mut R,
+    bounds: &Aabb<T>,
+    seed: Vector2d<T>,
+    min_radius: T,
+    max_radius: T,
+    radius_at: F,
+    inside: G,
+) -> Result<Vec<Vector2d<T>>, String>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    R: Rng + ?Sized,
+    F: Fn(Vector2d<T>) -> T,
+    G: Fn(Vector2d<T>) -> bool,
+{
+    if min_radius <= T::zero() {
+        return Err("[::ERROR::] -> The sampling radius must be positive.".to_string());
+    }
+    // With cells of side r / sqrt(2) every cell holds at most one sample.
+    let cell = min_radius / cast::<T>(2.0).sqrt();
+    let size = bounds.max - bounds.min;
+    let columns = (size.x / cell)
+        .ceil()
+        .to_usize()
+        .unwrap_or(usize::MAX)
+        .max(1);
+    let rows = (size.y / cell)
+        .ceil()
+        .to_usize()
+        .unwrap_or(usize::MAX)
+        .max(1);
+    if columns.saturating_mul(rows) > MAX_GRID_CELLS {
+        return Err(format!(
+            "[::ERROR::] -> The radius {} is too small for the sampled area.",
+            min_radius
+        ));
+    }
+    let reach = (max_radius / cell).ceil().to_isize().unwrap_or(2).max(2);
+    let cell_of = |p: Vector2d<T>| {
+        let x = ((p.x - bounds.min.x) / cell)
+            .to_usize()
+            .unwrap_or(0)
+            .min(columns - 1);
+        let y = ((p.y - bounds.min.y) / cell)
+            .to_usize()
+            .unwrap_or(0)
+            .min(rows - 1);
+        (x, y)
+    };
+
+    let mut grid: Vec<Option<usize>> = vec![None; columns * rows];
+    let mut points = vec![seed];
+    let mut radii = vec![radius_at(seed)];
+    let mut active = vec![0];
+    let (x, y) = cell_of(seed);
+    grid[y * columns + x] = Some(0);
+
+    while !active.is_empty() {
+        let slot = rng.gen_range(0..active.len());
+        let parent = active[slot];
+        let parent_radius = radii[parent];
+        let ring = UniformAnnulus::new(points[parent], parent_radius, parent_radius * cast(2.0))?;
+        let mut placed = false;
+        for _ in 0..POISSON_ATTEMPTS {
+            let candidate = ring.sample(rng);
+            if !bounds.contains_point(candidate) || !inside(candidate) {
+                continue;
+            }
+            let radius = radius_at(candidate);
+            let (cx, cy) = cell_of(candidate);
+            let mut free = true;
+            'search: for dy in -reach..=reach {
+                for dx in -reach..=reach {
+                    let (Some(nx), Some(ny)) =
+                        (cx.checked_add_signed(dx), cy.checked_add_signed(dy))
+                    else {
+                        continue;
+                    };
+                    if nx >= columns || ny >= rows {
+                        continue;
+                    }
+                    if let Some(other) = grid[ny * columns + nx] {
+                        let spacing = (radius + radii[other]) / cast(2.0);
+                        if points[other].distance(candidate) < spacing {
+                            free = false;
+                            break 'search;
+                        }
+                    }
+                }
+            }
+            if free {
+                grid[cy * columns + cx] = Some(points.len());
+                active.push(points.len());
+                points.push(candidate);
+                radii.push(radius);
+                placed = true;
+                break;
+            }
+        }
+        if !placed {
+            active.swap_remove(slot);
+        }
+    }
+    Ok(points)
+}
+
+/// Even-odd test of `point` against a polygon outline.
+pub(crate) fn point_in_polygon<T>(point: Vector2d<T>, polygon: &[Vector2d<T>]) -> bool
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let mut inside = false;
+    let n = polygon.len();
+    for i in 0..n {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        if (a.y > point.y) != (b.y > point.y) {
+            let x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            if point.x < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+// -----------------------------------------
+//        Low-Discrepancy Sequences
+// -----------------------------------------
+
+/// The first `count` points of the Halton sequence in bases 2 and 3, mapped
+/// to `bounds`. The sequence starts at index 1 to skip the corner point.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::sampling_module::halton;
+/// use vectorlib::math::shape_module::Aabb;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let unit = Aabb::new(Vector2d::new(0.0, 0.0), Vector2d::new(1.0, 1.0));
+/// let points = halton(3, &unit);
+/// assert_eq!(points[0], Vector2d::new(0.5, 1.0 / 3.0));
+/// assert_eq!(points[1], Vector2d::new(0.25, 2.0 / 3.0));
+/// ```
+pub fn halton<T>(count: usize, bounds: &Aabb<T>) -> Vec<Vector2d<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    (1..=count)
+        .map(|i| to_bounds(bounds, radical_inverse(i, 2), radical_inverse(i, 3)))
+        .collect()
+}
+
+/// The first `count` points of the two dimensional Sobol sequence, mapped to
+/// `bounds`. The sequence starts at index 1 to skip the corner point.
+pub fn sobol<T>(count: usize, bounds: &Aabb<T>) -> Vec<Vector2d<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    // Direction numbers: the first dimension is the van der Corput sequence,
+    // the second comes from the primitive polynomial x + 1.
+    let mut first = [0u32; 32];
+    let mut second = [0u32; 32];
+    let mut m = 1u32;
+    for k in 0..32 {
+        first[k] = 1 << (31 - k);
+        if k > 0 {
+            m = (m << 1) ^ m;
+        }
+        second[k] = m << (31 - k);
+    }
+    let scale = 1.0 / 4_294_967_296.0;
+    (1..=count)
+        .map(|i| {
+            let gray = (i ^ (i >> 1)) as u64;
+            let (mut x, mut y) = (0u32, 0u32);
+            for k in 0..32 {
+                if gray >> k & 1 == 1 {
+                    x ^= first[k];
+                    y ^= second[k];
+                }
+            }
+            to_bounds(bounds, x as f64 * scale, y as f64 * scale)
+        })
+        .collect()
+}
+
+/// The first `count` points of Roberts' R2 sequence, built on the plastic
+/// number, mapped to `bounds`.
+pub fn r2<T>(count: usize, bounds: &Aabb<T>) -> Vec<Vector2d<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let g = 1.324_717_957_244_746_f64;
+    let (a1, a2) = (1.0 / g, 1.0 / (g * g));
+    (1..=count)
+        .map(|i| {
+            let n = i as f64;
+            to_bounds(bounds, (0.5 + a1 * n).fract(), (0.5 + a2 * n).fract())
+        })
+        .collect()
+}
+
+/// Randomises a low-discrepancy point set with a Cranley-Patterson rotation:
+/// every point is shifted by the same random offset, wrapping around
+/// `bounds`. Different seeds give different but equally well spread sets.
+pub fn cranley_patterson<T, R>(
+    points: &[Vector2d<T>],
+    bounds: &Aabb<T>,
+    rng: &mut R,
+) -> Vec<Vector2d<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    R: Rng + ?Sized,
+{
+    let size = bounds.max - bounds.min;
+    let shift = Vector2d::new(rng.gen::<f64>(), rng.gen::<f64>());
+    let wrap = |value: T, min: T, extent: T, shift: f64| {
+        if extent <= T::zero() {
+            return value;
+        }
+        let unit = ((value - min) / extent).to_f64().unwrap_or(0.0);
+        min + extent * cast((unit + shift).fract())
+    };
+    points
+        .iter()
+        .map(|p| {
+            Vector2d::new(
+                wrap(p.x, bounds.min.x, size.x, shift.x),
+                wrap(p.y, bounds.min.y, size.y, shift.y),
+            )
+        })
+        .collect()
+}
+
+fn radical_inverse(mut index: usize, base: usize) -> f64 {
+    let mut result = 0.0;
+    let mut fraction = 1.0 / base as f64;
+    while index > 0 {
+        result += (index % base) as f64 * fraction;
+        index /= base;
+        fraction /= base as f64;
+    }
+    result
+}
+
+fn to_bounds<T>(bounds: &Aabb<T>, u: f64, v: f64) -> Vector2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let size = bounds.max - bounds.min;
+    Vector2d::new(
+        bounds.min.x + size.x * cast(u),
+        bounds.min.y + size.y * cast(v),
+    )
+}
@@ -0,0 +1,292 @@
+use crate::math::polygon_offset_module::signed_area;
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        Collision Results
+// -----------------------------------------
+
+/// The minimum translation vector separating two overlapping shapes.
+///
+/// `axis` is a unit vector pointing from the first shape towards the second
+/// one; moving the second shape by `axis * depth` (or the first one by
+/// `-axis * depth`) resolves the overlap.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MinimumTranslation<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub axis: Vector2d<T>,
+    pub depth: T,
+}
+
+/// A single point of contact between two shapes.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ContactPoint<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub position: Vector2d<T>,
+    pub depth: T,
+}
+
+/// The contact information between two overlapping shapes.
+///
+/// `normal` points from the first shape towards the second one and
+/// `contacts` holds at most two points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContactManifold<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub normal: Vector2d<T>,
+    pub depth: T,
+    pub contacts: Vec<ContactPoint<T>>,
+}
+
+// -----------------------------------------
+//     Separating Axis Theorem Queries
+// -----------------------------------------
+
+/// Projects a vertex list onto `axis`, returning the `(min, max)` interval.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::sat_collision_module::project_onto_axis;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let triangle = vec![Vector2d::new(0.0, 0.0), Vector2d::new(2.0, 0.0), Vector2d::new(1.0, 3.0)];
+/// let (min, max) = project_onto_axis(&triangle, Vector2d::new(0.0, 1.0));
+/// assert_eq!((min, max), (0.0, 3.0));
+/// ```
+pub fn project_onto_axis<T>(vertices: &[Vector2d<T>], axis: Vector2d<T>) -> (T, T)
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let mut min = T::infinity();
+    let mut max = T::neg_infinity();
+    for &v in vertices {
+        let p = v.dot_product(axis);
+        min = min.min(p);
+        max = max.max(p);
+    }
+    (min, max)
+}
+
+/// Tests two convex polygons for overlap with the Separating Axis Theorem.
+///
+/// Both polygons may use either winding order. Touching polygons (zero
+/// overlap) are reported as separated.
+///
+/// # Returns
+///
+/// `None` when a separating axis exists (or when a polygon has less than two
+/// vertices), otherwise the minimum translation vector.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::sat_collision_module::sat_overlap;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let a = vec![
+///     Vector2d::new(0.0, 0.0),
+///     Vector2d::new(2.0, 0.0),
+///     Vector2d::new(2.0, 2.0),
+///     Vector2d::new(0.0, 2.0),
+/// ];
+/// let b: Vec<_> = a.iter().map(|&v| v + Vector2d::new(1.5, 0.5)).collect();
+///
+/// let mtv = sat_overlap(&a, &b).unwrap();
+/// assert_eq!(mtv.axis, Vector2d::new(1.0, 0.0));
+/// assert_eq!(mtv.depth, 0.5);
+/// ```
+pub fn sat_overlap<T>(a: &[Vector2d<T>], b: &[Vector2d<T>]) -> Option<MinimumTranslation<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    if a.len() < 2 || b.len() < 2 {
+        return None;
+    }
+    let mut best: Option<MinimumTranslation<T>> = None;
+    for polygon in [a, b] {
+        let n = polygon.len();
+        for i in 0..n {
+            let edge = polygon[(i + 1) % n] - polygon[i];
+            let Ok(axis) = Vector2d::new(edge.y, -edge.x).normalize() else {
+                continue;
+            };
+            let (min_a, max_a) = project_onto_axis(a, axis);
+            let (min_b, max_b) = project_onto_axis(b, axis);
+            let forward = max_a - min_b;
+            let backward = max_b - min_a;
+            if forward <= T::zero() || backward <= T::zero() {
+                return None;
+            }
+            let candidate = if forward <= backward {
+                MinimumTranslation {
+                    axis,
+                    depth: forward,
+                }
+            } else {
+                MinimumTranslation {
+                    axis: -axis,
+                    depth: backward,
+                }
+            };
+            if best.is_none_or(|b| candidate.depth < b.depth) {
+                best = Some(candidate);
+            }
+        }
+    }
+    best
+}
+
+/// Computes the contact manifold between two convex polygons.
+///
+/// The minimum translation vector gives the collision normal; the contact
+/// points are then found by clipping the incident edge against the side
+/// planes of the reference edge (the edge most perpendicular to the normal).
+///
+/// # Returns
+///
+/// `None` when the polygons do not overlap, otherwise a manifold with one or
+/// two contact points lying on the incident polygon.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::sat_collision_module::sat_manifold;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let ground = vec![
+///     Vector2d::new(-5.0, -1.0),
+///     Vector2d::new(5.0, -1.0),
+///     Vector2d::new(5.0, 0.0),
+///     Vector2d::new(-5.0, 0.0),
+/// ];
+/// let crate_box = vec![
+///     Vector2d::new(0.0, -0.1),
+///     Vector2d::new(1.0, -0.1),
+///     Vector2d::new(1.0, 0.9),
+///     Vector2d::new(0.0, 0.9),
+/// ];
+///
+/// let manifold = sat_manifold(&ground, &crate_box).unwrap();
+/// assert_eq!(manifold.normal, Vector2d::new(0.0, 1.0));
+/// assert_eq!(manifold.contacts.len(), 2);
+/// ```
+pub fn sat_manifold<T>(a: &[Vector2d<T>], b: &[Vector2d<T>]) -> Option<ContactManifold<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let mtv = sat_overlap(a, b)?;
+    let a = counter_clockwise(a);
+    let b = counter_clockwise(b);
+    let normal = mtv.axis;
+
+    let (a1, a2, a_normal) = best_face(&a, normal);
+    let (b1, b2, b_normal) = best_face(&b, -normal);
+    let (reference, incident, reference_normal) =
+        if a_normal.dot_product(normal) >= b_normal.dot_product(-normal) {
+            ((a1, a2), (b1, b2), a_normal)
+        } else {
+            ((b1, b2), (a1, a2), b_normal)
+        };
+
+    let Ok(tangent) = (reference.1 - reference.0).normalize() else {
+        return Some(ContactManifold {
+            normal,
+            depth: mtv.depth,
+            contacts: Vec::new(),
+        });
+    };
+    let clipped = clip_segment(incident, tangent, tangent.dot_product(reference.0))
+        .and_then(|segment| clip_segment(segment, -tangent, -tangent.dot_product(reference.1)));
+
+    let mut contacts = Vec::with_capacity(2);
+    if let Some((p, q)) = clipped {
+        for point in [p, q] {
+            let separation = reference_normal.dot_product(point - reference.0);
+            if separation <= T::zero() {
+                contacts.push(ContactPoint {
+                    position: point,
+                    depth: -separation,
+                });
+            }
+        }
+    }
+    if contacts.len() == 2 && contacts[0].position == contacts[1].position {
+        contacts.pop();
+    }
+    Some(ContactManifold {
+        normal,
+        depth: mtv.depth,
+        contacts,
+    })
+}
+
+// -----------------------------------------
+//        Internal helpers
+// -----------------------------------------
+
+pub(crate) fn counter_clockwise<T>(polygon: &[Vector2d<T>]) -> Vec<Vector2d<T>>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let mut result = polygon.to_vec();
+    if signed_area(&result) < T::zero() {
+        result.reverse();
+    }
+    result
+}
+
+/// Returns the edge of a counter-clockwise polygon whose outward normal is
+/// the most aligned with `direction`, with that normal.
+fn best_face<T>(
+    polygon: &[Vector2d<T>],
+    direction: Vector2d<T>,
+) -> (Vector2d<T>, Vector2d<T>, Vector2d<T>)
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let n = polygon.len();
+    let mut best = (polygon[0], polygon[1 % n], direction);
+    let mut best_alignment = T::neg_infinity();
+    for i in 0..n {
+        let (p, q) = (polygon[i], polygon[(i + 1) % n]);
+        let edge = q - p;
+        if let Ok(normal) = Vector2d::new(edge.y, -edge.x).normalize() {
+            let alignment = normal.dot_product(direction);
+            if alignment > best_alignment {
+                best_alignment = alignment;
+                best = (p, q, normal);
+            }
+        }
+    }
+    best
+}
+
+/// Keeps the part of the segment where `direction · p >= offset`.
+fn clip_segment<T>(
+    segment: (Vector2d<T>, Vector2d<T>),
+    direction: Vector2d<T>,
+    offset: T,
+) -> Option<(Vector2d<T>, Vector2d<T>)>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let (p, q) = segment;
+    let dp = direction.dot_product(p) - offset;
+    let dq = direction.dot_product(q) - offset;
+    match (dp >= T::zero(), dq >= T::zero()) {
+        (true, true) => Some((p, q)),
+        (false, false) => None,
+        (true, false) => Some((p, p + (q - p) * (dp / (dp - dq)))),
+        (false, true) => Some((p + (q - p) * (dp / (dp - dq)), q)),
+    }
+}
@@ -0,0 +1,30 @@
+use crate::math::vector2d_module::Vector2d;
+use num_traits::Float;
+use std::ops::AddAssign;
+
+// -----------------------------------------
+//   Scalar helpers shared by the modules
+// -----------------------------------------
+
+/// Converts an `f64` literal into the generic float type `T`.
+pub(crate) fn cast<T: Float>(value: f64) -> T {
+    T::from(value).unwrap()
+}
+
+/// The `π` constant for the generic float type `T`.
+pub(crate) fn pi<T: Float>() -> T {
+    cast(std::f64::consts::PI)
+}
+
+/// Converts a generic float into an `f64`, `NaN` when it has no `f64` value.
+pub(crate) fn to_f64<T: Float>(value: T) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Converts both components of `vector` with [`to_f64`].
+pub(crate) fn vector_to_f64<T>(vector: Vector2d<T>) -> Vector2d<f64>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    Vector2d::new(to_f64(vector.x), to_f64(vector.y))
+}
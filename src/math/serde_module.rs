@@ -0,0 +1,115 @@
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        Serde Helpers
+// -----------------------------------------
+//
+// With the `serde` feature `Vector2d` serializes in struct form,
+// `{"x": 1.0, "y": 2.0}`, and deserializes from either the struct or the
+// `[x, y]` array form. The modules below select the compact array form for a
+// field with `#[serde(with = "...")]`, for any component type `Vector2d`
+// accepts, integers included.
+
+/// Serializes a `Vector2d` field as `[x, y]`.
+///
+/// # Examples
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Sprite {
+///     #[serde(with = "vectorlib::math::serde_module::compact")]
+///     position: Vector2d<f64>,
+///     velocity: Vector2d<f64>,
+/// }
+///
+/// let sprite = Sprite {
+///     position: Vector2d::new(1.5, -2.0),
+///     velocity: Vector2d::new(0.0, 1.0),
+/// };
+/// assert_eq!(
+///     serde_json::to_string(&sprite).unwrap(),
+///     r#"{"position":[1.5,-2.0],"velocity":{"x":0.0,"y":1.0}}"#
+/// );
+/// ```
+pub mod compact {
+    use super::*;
+
+    pub fn serialize<T, S>(vector: &Vector2d<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: std::fmt::Display
+            + Copy
+            + AddAssign
+            + Add<T, Output = T>
+            + Sub<T, Output = T>
+            + Mul<T, Output = T>
+            + Div<T, Output = T>
+            + Serialize,
+        S: Serializer,
+    {
+        (vector.x, vector.y).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Vector2d<T>, D::Error>
+    where
+        T: std::fmt::Display
+            + Copy
+            + AddAssign
+            + Add<T, Output = T>
+            + Sub<T, Output = T>
+            + Mul<T, Output = T>
+            + Div<T, Output = T>
+            + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let (x, y) = <(T, T)>::deserialize(deserializer)?;
+        Ok(Vector2d::new(x, y))
+    }
+}
+
+/// Serializes a `Vec<Vector2d>` field as `[[x, y], ...]`, the usual choice
+/// for polylines and point clouds.
+pub mod compact_vec {
+    use super::*;
+
+    pub fn serialize<T, S>(vectors: &[Vector2d<T>], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: std::fmt::Display
+            + Copy
+            + AddAssign
+            + Add<T, Output = T>
+            + Sub<T, Output = T>
+            + Mul<T, Output = T>
+            + Div<T, Output = T>
+            + Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(vectors.iter().map(|v| (v.x, v.y)))
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Vec<Vector2d<T>>, D::Error>
+    where
+        T: std::fmt::Display
+            + Copy
+            + AddAssign
+            + Add<T, Output = T>
+            + Sub<T, Output = T>
+            + Mul<T, Output = T>
+            + Div<T, Output = T>
+            + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let pairs = Vec::<(T, T)>::deserialize(deserializer)?;
+        Ok(pairs
+            .into_iter()
+            .map(|(x, y)| Vector2d::new(x, y))
+            .collect())
+    }
+}
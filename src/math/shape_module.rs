@@ -0,0 +1,265 @@
+use crate::math::sat_collision_module::counter_clockwise;
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        Basic Convex Shapes
+// -----------------------------------------
+
+/// A circle given by its center and radius.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Circle<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub center: Vector2d<T>,
+    pub radius: T,
+}
+
+impl<T> Circle<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create a new `Circle`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::shape_module::Circle;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let c = Circle::new(Vector2d::new(1.0, 2.0), 0.5);
+    /// assert_eq!(c.radius, 0.5);
+    /// ```
+    pub fn new(center: Vector2d<T>, radius: T) -> Self {
+        Self { center, radius }
+    }
+}
+
+/// A capsule: every point within `radius` of the segment `a`-`b`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Capsule<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub a: Vector2d<T>,
+    pub b: Vector2d<T>,
+    pub radius: T,
+}
+
+impl<T> Capsule<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create a new `Capsule` around the segment `a`-`b`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::shape_module::Capsule;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let c = Capsule::new(Vector2d::new(0.0, 0.0), Vector2d::new(0.0, 2.0), 0.5);
+    /// assert_eq!(c.b.y, 2.0);
+    /// ```
+    pub fn new(a: Vector2d<T>, b: Vector2d<T>, radius: T) -> Self {
+        Self { a, b, radius }
+    }
+}
+
+/// A convex polygon stored with counter-clockwise winding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexPolygon<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    vertices: Vec<Vector2d<T>>,
+}
+
+impl<T> ConvexPolygon<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create a new `ConvexPolygon`, reordering the vertices counter-clockwise
+    /// when needed. Convexity itself is not checked.
+    ///
+    /// # Returns
+    ///
+    /// The polygon, or an `Err` when `vertices` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::shape_module::ConvexPolygon;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let triangle = ConvexPolygon::new(vec![
+    ///     Vector2d::new(0.0, 0.0),
+    ///     Vector2d::new(0.0, 1.0),
+    ///     Vector2d::new(1.0, 0.0),
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(triangle.vertices()[0], Vector2d::new(1.0, 0.0));
+    /// ```
+    pub fn new(vertices: Vec<Vector2d<T>>) -> Result<Self, String> {
+        if vertices.is_empty() {
+            return Err("[::ERROR::] -> Cannot create a polygon without vertices.".to_string());
+        }
+        Ok(Self {
+            vertices: counter_clockwise(&vertices),
+        })
+    }
+
+    /// Create an axis aligned rectangle centred on `center`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::shape_module::ConvexPolygon;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let rect = ConvexPolygon::rectangle(Vector2d::new(0.0, 0.0), Vector2d::new(2.0, 1.0));
+    /// assert_eq!(rect.vertices()[0], Vector2d::new(-2.0, -1.0));
+    /// ```
+    pub fn rectangle(center: Vector2d<T>, half_extents: Vector2d<T>) -> Self {
+        let (hx, hy) = (half_extents.x, half_extents.y);
+        Self {
+            vertices: vec![
+                center + Vector2d::new(-hx, -hy),
+                center + Vector2d::new(hx, -hy),
+                center + Vector2d::new(hx, hy),
+                center + Vector2d::new(-hx, hy),
+            ],
+        }
+    }
+
+    /// The counter-clockwise vertex list.
+    pub fn vertices(&self) -> &[Vector2d<T>] {
+        &self.vertices
+    }
+}
+
+/// An axis aligned bounding box given by its minimum and maximum corners.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Aabb<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub min: Vector2d<T>,
+    pub max: Vector2d<T>,
+}
+
+impl<T> Aabb<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create a new `Aabb` from two opposite corners given in any order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::shape_module::Aabb;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let b = Aabb::new(Vector2d::new(2.0, 0.0), Vector2d::new(0.0, 1.0));
+    /// assert_eq!(b.min, Vector2d::new(0.0, 0.0));
+    /// assert_eq!(b.max, Vector2d::new(2.0, 1.0));
+    /// ```
+    pub fn new(a: Vector2d<T>, b: Vector2d<T>) -> Self {
+        Self {
+            min: Vector2d::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Vector2d::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// Create an `Aabb` centred on `center`.
+    pub fn from_center(center: Vector2d<T>, half_extents: Vector2d<T>) -> Self {
+        Self::new(center - half_extents, center + half_extents)
+    }
+
+    pub fn center(&self) -> Vector2d<T> {
+        (self.min + self.max) * (T::one() / (T::one() + T::one()))
+    }
+
+    pub fn half_extents(&self) -> Vector2d<T> {
+        (self.max - self.min) * (T::one() / (T::one() + T::one()))
+    }
+
+    /// Returns `true` when both boxes overlap or touch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::shape_module::Aabb;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let a = Aabb::new(Vector2d::new(0.0, 0.0), Vector2d::new(1.0, 1.0));
+    /// let b = Aabb::new(Vector2d::new(1.0, 0.5), Vector2d::new(2.0, 2.0));
+    /// assert!(a.overlaps(&b));
+    /// ```
+    pub fn overlaps(&self, other: &Aabb<T>) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+
+    /// Returns `true` when `point` lies inside or on the border of the box.
+    pub fn contains_point(&self, point: Vector2d<T>) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+    }
+
+    /// Returns `true` when `other` lies entirely inside this box.
+    pub fn contains_aabb(&self, other: &Aabb<T>) -> bool {
+        self.contains_point(other.min) && self.contains_point(other.max)
+    }
+
+    /// The smallest box containing both boxes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::shape_module::Aabb;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let a = Aabb::new(Vector2d::new(0.0, 0.0), Vector2d::new(1.0, 1.0));
+    /// let b = Aabb::new(Vector2d::new(2.0, -1.0), Vector2d::new(3.0, 0.0));
+    /// assert_eq!(a.union(&b), Aabb::new(Vector2d::new(0.0, -1.0), Vector2d::new(3.0, 1.0)));
+    /// ```
+    pub fn union(&self, other: &Aabb<T>) -> Aabb<T> {
+        Aabb {
+            min: Vector2d::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Vector2d::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    /// The box grown by `margin` on every side.
+    pub fn inflate(&self, margin: T) -> Aabb<T> {
+        let margin = Vector2d::new(margin, margin);
+        Aabb {
+            min: self.min - margin,
+            max: self.max + margin,
+        }
+    }
+
+    pub fn perimeter(&self) -> T {
+        let size = self.max - self.min;
+        (size.x + size.y) * (T::one() + T::one())
+    }
+
+    /// The four corners in counter-clockwise order, starting at `min`.
+    pub fn corners(&self) -> [Vector2d<T>; 4] {
+        [
+            self.min,
+            Vector2d::new(self.max.x, self.min.y),
+            self.max,
+            Vector2d::new(self.min.x, self.max.y),
+        ]
+    }
+}
@@ -0,0 +1,396 @@
+use crate::math::shape_module::Aabb;
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+use std::collections::HashMap;
+use std::hash::Hash;
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+type Cell = (i64, i64);
+
+/// Entries covering more cells than this are kept in one list and checked by
+/// every query instead of being linked into each cell.
+pub const MAX_LINKED_CELLS: u128 = 256;
+
+// -----------------------------------------
+//        Spatial Hash
+// -----------------------------------------
+
+#[derive(Debug, Clone)]
+struct Entry<T, Id>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    id: Id,
+    bounds: Aabb<T>,
+    min_cell: Cell,
+    max_cell: Cell,
+}
+
+/// A uniform grid over the plane, bucketing points and boxes by cell.
+///
+/// Each entry is stored once and referenced from every cell its box touches,
+/// except boxes spanning more than `MAX_LINKED_CELLS` cells, which are kept
+/// aside. `clear` keeps the cell buckets allocated so the hash can be rebuilt
+/// every frame without reallocating; buckets emptied by moves and removals
+/// are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::spatial_hash_module::SpatialHash;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let mut grid: SpatialHash<f64, u32> = SpatialHash::new(1.0).unwrap();
+/// grid.insert_point(1, Vector2d::new(0.5, 0.5));
+/// grid.insert_point(2, Vector2d::new(3.0, 0.5));
+/// assert_eq!(grid.query_radius(Vector2d::new(0.0, 0.0), 1.0), vec![1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpatialHash<T, Id>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    cell_size: T,
+    inverse_cell_size: T,
+    cells: HashMap<Cell, Vec<usize>>,
+    // Entries too large to link into their cells.
+    oversized: Vec<usize>,
+    entries: Vec<Entry<T, Id>>,
+    lookup: HashMap<Id, usize>,
+}
+
+impl<T, Id> SpatialHash<T, Id>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    Id: Copy + Eq + Hash,
+{
+    /// Create an empty grid with square cells of side `cell_size`.
+    ///
+    /// # Returns
+    ///
+    /// The grid, or an `Err` when `cell_size` is not strictly positive.
+    pub fn new(cell_size: T) -> Result<Self, String> {
+        if cell_size <= T::zero() || !cell_size.is_finite() {
+            return Err("[::ERROR::] -> The cell size must be positive and finite.".to_string());
+        }
+        Ok(Self {
+            cell_size,
+            inverse_cell_size: T::one() / cell_size,
+            cells: HashMap::new(),
+            oversized: Vec::new(),
+            entries: Vec::new(),
+            lookup: HashMap::new(),
+        })
+    }
+
+    pub fn cell_size(&self) -> T {
+        self.cell_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of allocated cell buckets, empty ones kept by `clear` included.
+    pub fn bucket_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns `true` when `id` is stored in the grid.
+    pub fn contains(&self, id: Id) -> bool {
+        self.lookup.contains_key(&id)
+    }
+
+    /// The stored box of `id`, degenerate for points.
+    pub fn bounds(&self, id: Id) -> Option<Aabb<T>> {
+        self.lookup.get(&id).map(|&i| self.entries[i].bounds)
+    }
+
+    /// Removes every entry but keeps the cell buckets allocated.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.lookup.clear();
+        self.oversized.clear();
+        for bucket in self.cells.values_mut() {
+            bucket.clear();
+        }
+    }
+
+    /// Inserts a point, or moves it when `id` is already stored.
+    pub fn insert_point(&mut self, id: Id, point: Vector2d<T>) {
+        self.insert_aabb(
+            id,
+            Aabb {
+                min: point,
+                max: point,
+            },
+        );
+    }
+
+    /// Inserts a box, or moves it when `id` is already stored.
+    pub fn insert_aabb(&mut self, id: Id, bounds: Aabb<T>) {
+        let (min_cell, max_cell) = (self.cell_of(bounds.min), self.cell_of(bounds.max));
+        if let Some(&index) = self.lookup.get(&id) {
+            let entry = &mut self.entries[index];
+            entry.bounds = bounds;
+            if entry.min_cell == min_cell && entry.max_cell == max_cell {
+                return;
+            }
+            let (old_min, old_max) = (entry.min_cell, entry.max_cell);
+            entry.min_cell = min_cell;
+            entry.max_cell = max_cell;
+            self.unlink(index, old_min, old_max);
+            self.link(index, min_cell, max_cell);
+            return;
+        }
+        let index = self.entries.len();
+        self.entries.push(Entry {
+            id,
+            bounds,
+            min_cell,
+            max_cell,
+        });
+        self.lookup.insert(id, index);
+        self.link(index, min_cell, max_cell);
+    }
+
+    /// Moves a stored point. Same as `insert_point`.
+    pub fn update_point(&mut self, id: Id, point: Vector2d<T>) {
+        self.insert_point(id, point);
+    }
+
+    /// Moves a stored box. Same as `insert_aabb`.
+    pub fn update_aabb(&mut self, id: Id, bounds: Aabb<T>) {
+        self.insert_aabb(id, bounds);
+    }
+
+    /// Removes `id`, returning `false` when it was not stored.
+    pub fn remove(&mut self, id: Id) -> bool {
+        let Some(index) = self.lookup.remove(&id) else {
+            return false;
+        };
+        let (min_cell, max_cell) = (self.entries[index].min_cell, self.entries[index].max_cell);
+        self.unlink(index, min_cell, max_cell);
+
+        // The last entry takes the freed slot: re-point its cells.
+        let last = self.entries.len() - 1;
+        self.entries.swap_remove(index);
+        if index != last {
+            let moved = &self.entries[index];
+            self.lookup.insert(moved.id, index);
+            if is_oversized(moved.min_cell, moved.max_cell) {
+                if let Some(slot) = self.oversized.iter_mut().find(|k| **k == last) {
+                    *slot = index;
+                }
+                return true;
+            }
+            for cell in cells_between(moved.min_cell, moved.max_cell) {
+                if let Some(slot) = self
+                    .cells
+                    .get_mut(&cell)
+                    .and_then(|bucket| bucket.iter_mut().find(|k| **k == last))
+                {
+                    *slot = index;
+                }
+            }
+        }
+        true
+    }
+
+    /// Ids whose point or box lies within `radius` of `center`.
+    pub fn query_radius(&self, center: Vector2d<T>, radius: T) -> Vec<Id> {
+        let mut found = Vec::new();
+        self.query_radius_into(center, radius, &mut found);
+        found
+    }
+
+    /// Like `query_radius`, appending to `found` to reuse its allocation.
+    pub fn query_radius_into(&self, center: Vector2d<T>, radius: T, found: &mut Vec<Id>) {
+        let reach = Vector2d::new(radius, radius);
+        let area = Aabb {
+            min: center - reach,
+            max: center + reach,
+        };
+        let probe = Aabb {
+            min: center,
+            max: center,
+        };
+        self.visit(&area, |entry| {
+            if gap_squared(&entry.bounds, &probe) <= radius * radius {
+                found.push(entry.id);
+            }
+        });
+    }
+
+    /// Ids whose point or box overlaps `area`.
+    pub fn query_rect(&self, area: &Aabb<T>) -> Vec<Id> {
+        let mut found = Vec::new();
+        self.query_rect_into(area, &mut found);
+        found
+    }
+
+    /// Like `query_rect`, appending to `found` to reuse its allocation.
+    pub fn query_rect_into(&self, area: &Aabb<T>, found: &mut Vec<Id>) {
+        self.visit(area, |entry| {
+            if entry.bounds.overlaps(area) {
+                found.push(entry.id);
+            }
+        });
+    }
+
+    /// Calls `callback` once for every unordered pair of entries closer than
+    /// `radius` (the gap between boxes, or the distance between points).
+    pub fn for_each_pair_within<F>(&self, radius: T, mut callback: F)
+    where
+        F: FnMut(Id, Id),
+    {
+        let reach = Vector2d::new(radius, radius);
+        for (i, entry) in self.entries.iter().enumerate() {
+            let area = Aabb {
+                min: entry.bounds.min - reach,
+                max: entry.bounds.max + reach,
+            };
+            self.visit_indexed(&area, |j, other| {
+                if j > i && gap_squared(&entry.bounds, &other.bounds) <= radius * radius {
+                    callback(entry.id, other.id);
+                }
+            });
+        }
+    }
+
+    /// Every unordered pair of entries closer than `radius`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::spatial_hash_module::SpatialHash;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let mut grid: SpatialHash<f64, char> = SpatialHash::new(0.5).unwrap();
+    /// grid.insert_point('a', Vector2d::new(0.0, 0.0));
+    /// grid.insert_point('b', Vector2d::new(0.3, 0.0));
+    /// grid.insert_point('c', Vector2d::new(5.0, 0.0));
+    /// assert_eq!(grid.pairs_within(0.5), vec![('a', 'b')]);
+    /// ```
+    pub fn pairs_within(&self, radius: T) -> Vec<(Id, Id)> {
+        let mut pairs = Vec::new();
+        self.for_each_pair_within(radius, |a, b| pairs.push((a, b)));
+        pairs
+    }
+
+    // Coordinates beyond the `i64` cell range saturate to the outermost cell.
+    fn cell_of(&self, point: Vector2d<T>) -> Cell {
+        let coordinate = |v: T| {
+            let cell = (v * self.inverse_cell_size).floor();
+            cell.to_i64().unwrap_or(if cell < T::zero() {
+                i64::MIN
+            } else if cell > T::zero() {
+                i64::MAX
+            } else {
+                0
+            })
+        };
+        (coordinate(point.x), coordinate(point.y))
+    }
+
+    fn link(&mut self, index: usize, min_cell: Cell, max_cell: Cell) {
+        if is_oversized(min_cell, max_cell) {
+            self.oversized.push(index);
+            return;
+        }
+        for cell in cells_between(min_cell, max_cell) {
+            self.cells.entry(cell).or_default().push(index);
+        }
+    }
+
+    fn unlink(&mut self, index: usize, min_cell: Cell, max_cell: Cell) {
+        if is_oversized(min_cell, max_cell) {
+            if let Some(position) = self.oversized.iter().position(|&k| k == index) {
+                self.oversized.swap_remove(position);
+            }
+            return;
+        }
+        for cell in cells_between(min_cell, max_cell) {
+            if let Some(bucket) = self.cells.get_mut(&cell) {
+                if let Some(position) = bucket.iter().position(|&k| k == index) {
+                    bucket.swap_remove(position);
+                }
+                if bucket.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+    }
+
+    fn visit<F>(&self, area: &Aabb<T>, mut visitor: F)
+    where
+        F: FnMut(&Entry<T, Id>),
+    {
+        self.visit_indexed(area, |_, entry| visitor(entry));
+    }
+
+    // Visits every entry sharing a cell with `area` exactly once: an entry is
+    // only reported from the first cell of the overlap of both cell ranges.
+    // Oversized entries, and every entry when `area` spans more cells than
+    // there are entries, are visited without the cell test; callers apply
+    // their own exact test anyway.
+    fn visit_indexed<F>(&self, area: &Aabb<T>, mut visitor: F)
+    where
+        F: FnMut(usize, &Entry<T, Id>),
+    {
+        let (min_cell, max_cell) = (self.cell_of(area.min), self.cell_of(area.max));
+        if cell_count(min_cell, max_cell) > self.entries.len() as u128 {
+            for (index, entry) in self.entries.iter().enumerate() {
+                visitor(index, entry);
+            }
+            return;
+        }
+        for &index in &self.oversized {
+            visitor(index, &self.entries[index]);
+        }
+        for cell in cells_between(min_cell, max_cell) {
+            let Some(bucket) = self.cells.get(&cell) else {
+                continue;
+            };
+            for &index in bucket {
+                let entry = &self.entries[index];
+                let first = (
+                    entry.min_cell.0.max(min_cell.0),
+                    entry.min_cell.1.max(min_cell.1),
+                );
+                if first == cell {
+                    visitor(index, entry);
+                }
+            }
+        }
+    }
+}
+
+fn cell_count(min_cell: Cell, max_cell: Cell) -> u128 {
+    let span = |min: i64, max: i64| (max as i128 - min as i128 + 1).max(0) as u128;
+    span(min_cell.0, max_cell.0).saturating_mul(span(min_cell.1, max_cell.1))
+}
+
+fn is_oversized(min_cell: Cell, max_cell: Cell) -> bool {
+    cell_count(min_cell, max_cell) > MAX_LINKED_CELLS
+}
+
+fn cells_between(min_cell: Cell, max_cell: Cell) -> impl Iterator<Item = Cell> {
+    (min_cell.1..=max_cell.1).flat_map(move |y| (min_cell.0..=max_cell.0).map(move |x| (x, y)))
+}
+
+// Squared distance between the two boxes, zero when they overlap.
+fn gap_squared<T>(a: &Aabb<T>, b: &Aabb<T>) -> T
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let dx = (a.min.x - b.max.x).max(b.min.x - a.max.x).max(T::zero());
+    let dy = (a.min.y - b.max.y).max(b.min.y - a.max.y).max(T::zero());
+    dx * dx + dy * dy
+}
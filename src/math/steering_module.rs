@@ -0,0 +1,418 @@
+use crate::math::polyline_module::closest_on_segment;
+use crate::math::scalar_module::{cast, pi};
+use crate::math::shape_module::Circle;
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+use rand::Rng;
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        Helpers
+// -----------------------------------------
+
+/// Shortens `vector` to `max` when it is longer, keeping its direction.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::steering_module::truncate;
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// assert_eq!(truncate(Vector2d::new(3.0, 4.0), 2.5), Vector2d::new(1.5, 2.0));
+/// assert_eq!(truncate(Vector2d::new(3.0, 4.0), 10.0), Vector2d::new(3.0, 4.0));
+/// ```
+pub fn truncate<T>(vector: Vector2d<T>, max: T) -> Vector2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let length = vector.magnitude();
+    if length > max && length > T::zero() {
+        vector * (max / length)
+    } else {
+        vector
+    }
+}
+
+/// `vector` rescaled to `length`, or the zero vector when it has none.
+pub fn with_magnitude<T>(vector: Vector2d<T>, length: T) -> Vector2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    vector
+        .normalize()
+        .map(|direction| direction * length)
+        .unwrap_or(Vector2d::new(T::zero(), T::zero()))
+}
+
+/// Sums `(force, weight)` pairs and truncates the result to `max_force`.
+pub fn weighted_sum<T>(forces: &[(Vector2d<T>, T)], max_force: T) -> Vector2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let mut total = Vector2d::new(T::zero(), T::zero());
+    for &(force, weight) in forces {
+        total += force * weight;
+    }
+    truncate(total, max_force)
+}
+
+/// Adds the weighted forces in order until `max_force` is used up; the force
+/// that overflows the budget is clipped and the rest are dropped.
+pub fn prioritized_sum<T>(forces: &[(Vector2d<T>, T)], max_force: T) -> Vector2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    let mut total = Vector2d::new(T::zero(), T::zero());
+    for &(force, weight) in forces {
+        let remaining = max_force - total.magnitude();
+        if remaining <= T::zero() {
+            break;
+        }
+        total += truncate(force * weight, remaining);
+    }
+    total
+}
+
+// -----------------------------------------
+//        Agent
+// -----------------------------------------
+
+/// A point-mass vehicle driven by steering forces.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Agent<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub position: Vector2d<T>,
+    pub velocity: Vector2d<T>,
+    pub mass: T,
+    pub max_speed: T,
+    pub max_force: T,
+}
+
+impl<T> Agent<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create an agent at rest with unit mass.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::steering_module::Agent;
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let mut agent = Agent::new(Vector2d::new(0.0, 0.0), 2.0, 1.0);
+    /// let force = agent.seek(Vector2d::new(10.0, 0.0));
+    /// agent.apply_force(force, 0.5);
+    /// assert!(agent.velocity.x > 0.0);
+    /// ```
+    pub fn new(position: Vector2d<T>, max_speed: T, max_force: T) -> Self {
+        Self {
+            position,
+            velocity: Vector2d::new(T::zero(), T::zero()),
+            mass: T::one(),
+            max_speed,
+            max_force,
+        }
+    }
+
+    /// Unit vector along the velocity, if the agent is moving.
+    pub fn heading(&self) -> Option<Vector2d<T>> {
+        self.velocity.normalize().ok()
+    }
+
+    /// Integrates `force` over `dt`, truncating it to `max_force` and the
+    /// resulting velocity to `max_speed`.
+    pub fn apply_force(&mut self, force: Vector2d<T>, dt: T) {
+        let acceleration = truncate(force, self.max_force) * (T::one() / self.mass);
+        self.velocity = truncate(self.velocity + acceleration * dt, self.max_speed);
+        self.position += self.velocity * dt;
+    }
+
+    /// Steers towards `target` at full speed.
+    pub fn seek(&self, target: Vector2d<T>) -> Vector2d<T> {
+        let desired = with_magnitude(target - self.position, self.max_speed);
+        truncate(desired - self.velocity, self.max_force)
+    }
+
+    /// Steers away from `threat`; ignored when the threat is farther than
+    /// `panic_distance`.
+    pub fn flee(&self, threat: Vector2d<T>, panic_distance: T) -> Vector2d<T> {
+        if self.position.distance(threat) > panic_distance {
+            return Vector2d::new(T::zero(), T::zero());
+        }
+        let desired = with_magnitude(self.position - threat, self.max_speed);
+        truncate(desired - self.velocity, self.max_force)
+    }
+
+    /// Like `seek`, but slows down linearly inside `slowing_radius` to stop
+    /// on `target`.
+    pub fn arrive(&self, target: Vector2d<T>, slowing_radius: T) -> Vector2d<T> {
+        let offset = target - self.position;
+        let distance = offset.magnitude();
+        let speed = if distance < slowing_radius {
+            self.max_speed * distance / slowing_radius
+        } else {
+            self.max_speed
+        };
+        truncate(
+            with_magnitude(offset, speed) - self.velocity,
+            self.max_force,
+        )
+    }
+
+    /// Seeks the predicted position of a moving target.
+    pub fn pursue(
+        &self,
+        target_position: Vector2d<T>,
+        target_velocity: Vector2d<T>,
+    ) -> Vector2d<T> {
+        self.seek(self.predict(target_position, target_velocity))
+    }
+
+    /// Flees the predicted position of a moving threat.
+    pub fn evade(
+        &self,
+        threat_position: Vector2d<T>,
+        threat_velocity: Vector2d<T>,
+        panic_distance: T,
+    ) -> Vector2d<T> {
+        let predicted = self.predict(threat_position, threat_velocity);
+        self.flee(predicted, panic_distance)
+    }
+
+    /// Steers away from the most threatening circle lying ahead within
+    /// `look_ahead`, along the perpendicular of the heading.
+    pub fn avoid_obstacles(
+        &self,
+        obstacles: &[Circle<T>],
+        look_ahead: T,
+        radius: T,
+    ) -> Vector2d<T> {
+        let zero = Vector2d::new(T::zero(), T::zero());
+        let Some(heading) = self.heading() else {
+            return zero;
+        };
+        let side = heading.perpendicular();
+        let mut closest: Option<(T, T)> = None;
+        for obstacle in obstacles {
+            // Obstacle centre in the agent's local frame.
+            let local = obstacle.center - self.position;
+            let ahead = local.dot_product(heading);
+            let lateral = local.dot_product(side);
+            let reach = obstacle.radius + radius;
+            if ahead < T::zero() || ahead > look_ahead + reach || lateral.abs() >= reach {
+                continue;
+            }
+            if closest.is_none_or(|(best, _)| ahead < best) {
+                closest = Some((ahead, lateral));
+            }
+        }
+        let Some((ahead, lateral)) = closest else {
+            return zero;
+        };
+        // Push harder the closer the obstacle is.
+        let urgency = T::one() - (ahead / (look_ahead + radius)).min(T::one());
+        let away = if lateral > T::zero() { -side } else { side };
+        let brake = -heading * (urgency * self.max_force * cast(0.5));
+        truncate(
+            away * (self.max_force * (urgency + cast(0.2))) + brake,
+            self.max_force,
+        )
+    }
+
+    /// Keeps the agent within `path_radius` of the polyline `path`, heading
+    /// towards its end. The agent's position is predicted `look_ahead`
+    /// seconds ahead; when that point leaves the corridor, or the agent is
+    /// not moving forward, it seeks the projection shifted forward along the
+    /// path. Near the end of the path it arrives on the last point.
+    pub fn follow_path(&self, path: &[Vector2d<T>], path_radius: T, look_ahead: T) -> Vector2d<T> {
+        let zero = Vector2d::new(T::zero(), T::zero());
+        match path.len() {
+            0 => return zero,
+            1 => return self.arrive(path[0], path_radius),
+            _ => {}
+        }
+        let future = self.position + self.velocity * look_ahead;
+        let mut best: Option<(T, Vector2d<T>, Vector2d<T>)> = None;
+        for segment in path.windows(2) {
+            let (a, b) = (segment[0], segment[1]);
+            let (closest, _) = closest_on_segment(future, a, b);
+            let distance = future.distance(closest);
+            if best.is_none_or(|(d, _, _)| distance < d) {
+                best = Some((distance, closest, b - a));
+            }
+        }
+        let Some((distance, normal_point, edge)) = best else {
+            return zero;
+        };
+        let end = path[path.len() - 1];
+        let slowing_radius = path_radius + self.max_speed * look_ahead;
+        if self.position.distance(end) <= slowing_radius {
+            return self.arrive(end, slowing_radius);
+        }
+        let Ok(direction) = edge.normalize() else {
+            return zero;
+        };
+        if distance <= path_radius && self.velocity.dot_product(direction) > T::zero() {
+            return zero;
+        }
+        self.seek(normal_point + direction * path_radius)
+    }
+
+    /// Steers away from neighbours closer than `radius`, weighted by the
+    /// inverse of their distance.
+    pub fn separation(&self, neighbors: &[Agent<T>], radius: T) -> Vector2d<T> {
+        let mut push = Vector2d::new(T::zero(), T::zero());
+        let mut count = 0;
+        for other in self.neighbors(neighbors, radius) {
+            let offset = self.position - other.position;
+            let distance = offset.magnitude();
+            push += offset * (T::one() / (distance * distance));
+            count += 1;
+        }
+        if count == 0 {
+            return push;
+        }
+        let desired = with_magnitude(push, self.max_speed);
+        truncate(desired - self.velocity, self.max_force)
+    }
+
+    /// Steers towards the average heading of neighbours within `radius`.
+    pub fn alignment(&self, neighbors: &[Agent<T>], radius: T) -> Vector2d<T> {
+        let mut sum = Vector2d::new(T::zero(), T::zero());
+        let mut count = 0;
+        for other in self.neighbors(neighbors, radius) {
+            sum += other.velocity;
+            count += 1;
+        }
+        if count == 0 {
+            return sum;
+        }
+        let desired = with_magnitude(sum, self.max_speed);
+        truncate(desired - self.velocity, self.max_force)
+    }
+
+    /// Steers towards the centre of the neighbours within `radius`.
+    pub fn cohesion(&self, neighbors: &[Agent<T>], radius: T) -> Vector2d<T> {
+        let mut sum = Vector2d::new(T::zero(), T::zero());
+        let mut count = T::zero();
+        for other in self.neighbors(neighbors, radius) {
+            sum += other.position;
+            count += T::one();
+        }
+        if count == T::zero() {
+            return sum;
+        }
+        self.seek(sum * (T::one() / count))
+    }
+
+    /// Reynolds flocking: the weighted sum of separation, alignment and
+    /// cohesion, truncated to `max_force`.
+    pub fn flock(&self, neighbors: &[Agent<T>], weights: &FlockWeights<T>) -> Vector2d<T> {
+        weighted_sum(
+            &[
+                (
+                    self.separation(neighbors, weights.separation_radius),
+                    weights.separation,
+                ),
+                (
+                    self.alignment(neighbors, weights.neighbor_radius),
+                    weights.alignment,
+                ),
+                (
+                    self.cohesion(neighbors, weights.neighbor_radius),
+                    weights.cohesion,
+                ),
+            ],
+            self.max_force,
+        )
+    }
+
+    // Where a moving target will be once the agent could reach it.
+    fn predict(&self, position: Vector2d<T>, velocity: Vector2d<T>) -> Vector2d<T> {
+        let speed = self.max_speed.max(T::epsilon());
+        let time = self.position.distance(position) / speed;
+        position + velocity * time
+    }
+
+    // Other agents within `radius`, skipping any agent sharing our position
+    // (typically ourselves when the whole flock is passed in).
+    fn neighbors<'a>(
+        &'a self,
+        agents: &'a [Agent<T>],
+        radius: T,
+    ) -> impl Iterator<Item = &'a Agent<T>> {
+        agents.iter().filter(move |other| {
+            let distance = self.position.distance(other.position);
+            distance > T::zero() && distance < radius
+        })
+    }
+}
+
+/// Weights and radii used by `Agent::flock`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FlockWeights<T> {
+    pub separation: T,
+    pub alignment: T,
+    pub cohesion: T,
+    pub separation_radius: T,
+    pub neighbor_radius: T,
+}
+
+impl<T> FlockWeights<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Weights of 1.5, 1 and 1 with the separation radius a third of
+    /// `neighbor_radius`.
+    pub fn new(neighbor_radius: T) -> Self {
+        Self {
+            separation: cast(1.5),
+            alignment: T::one(),
+            cohesion: T::one(),
+            separation_radius: neighbor_radius / cast(3.0),
+            neighbor_radius,
+        }
+    }
+}
+
+/// State of the wander behaviour: a target jittering on a circle projected
+/// in front of the agent.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Wander<T> {
+    pub distance: T,
+    pub radius: T,
+    /// Largest change of the wander angle per call, in radians.
+    pub jitter: T,
+    pub angle: T,
+}
+
+impl<T> Wander<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub fn new(distance: T, radius: T, jitter: T) -> Self {
+        Self {
+            distance,
+            radius,
+            jitter,
+            angle: T::zero(),
+        }
+    }
+
+    /// Jitters the wander target with `rng` and returns the force seeking it.
+    pub fn steer<R: Rng + ?Sized>(&mut self, agent: &Agent<T>, rng: &mut R) -> Vector2d<T> {
+        let jitter: T = cast(rng.gen_range(-1.0..=1.0));
+        self.angle = (self.angle + jitter * self.jitter) % (pi::<T>() * cast(2.0));
+        let heading = agent
+            .heading()
+            .unwrap_or(Vector2d::new(T::one(), T::zero()));
+        let circle_center = agent.position + heading * self.distance;
+        let offset = heading.rotate(self.angle) * self.radius;
+        agent.seek(circle_center + offset)
+    }
+}
@@ -0,0 +1,504 @@
+use crate::math::scalar_module::vector_to_f64;
+use crate::math::shape_module::Aabb;
+use crate::math::svg_path_module::{PathSegment, SvgPath};
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+use std::fmt::Write as _;
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// -----------------------------------------
+//        Styles
+// -----------------------------------------
+
+/// How a primitive is drawn. Widths and sizes are in output pixels, so they
+/// do not depend on the extent of the drawing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Style {
+    /// Any SVG color, e.g. `"crimson"` or `"#3366ff"`.
+    pub stroke: String,
+    pub fill: Option<String>,
+    pub stroke_width: f64,
+    pub opacity: f64,
+    /// Font size of text labels and radius of points.
+    pub size: f64,
+}
+
+impl Style {
+    /// An outline in `color`.
+    pub fn new(color: &str) -> Self {
+        Self {
+            stroke: color.to_string(),
+            fill: None,
+            stroke_width: 1.5,
+            opacity: 1.0,
+            size: 12.0,
+        }
+    }
+
+    /// A shape filled and outlined in `color`.
+    pub fn filled(color: &str) -> Self {
+        Self {
+            fill: Some(color.to_string()),
+            ..Self::new(color)
+        }
+    }
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self::new("black")
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Primitive {
+    Arrow(Vector2d<f64>, Vector2d<f64>),
+    Point(Vector2d<f64>),
+    Segment(Vector2d<f64>, Vector2d<f64>),
+    Polyline(Vec<Vector2d<f64>>, bool),
+    Circle(Vector2d<f64>, f64),
+    Text(Vector2d<f64>, String),
+    Path(SvgPath),
+}
+
+impl Primitive {
+    // Points whose box contains the primitive; the control polygon of a path
+    // contains its curves.
+    fn extent(&self) -> Vec<Vector2d<f64>> {
+        match self {
+            Primitive::Arrow(a, b) | Primitive::Segment(a, b) => vec![*a, *b],
+            Primitive::Point(p) | Primitive::Text(p, _) => vec![*p],
+            Primitive::Polyline(list, _) => list.clone(),
+            Primitive::Circle(center, radius) => {
+                let r = Vector2d::new(*radius, *radius);
+                vec![*center - r, *center + r]
+            }
+            Primitive::Path(path) => {
+                let mut points = Vec::new();
+                for subpath in &path.subpaths {
+                    points.push(subpath.start);
+                    for segment in &subpath.segments {
+                        match *segment {
+                            PathSegment::Line { to } => points.push(to),
+                            PathSegment::Quadratic { control, to } => points.extend([control, to]),
+                            PathSegment::Cubic {
+                                control1,
+                                control2,
+                                to,
+                            } => points.extend([control1, control2, to]),
+                        }
+                    }
+                }
+                points
+            }
+        }
+    }
+
+    fn is_finite(&self) -> bool {
+        self.extent()
+            .iter()
+            .all(|p| p.x.is_finite() && p.y.is_finite())
+    }
+}
+
+// -----------------------------------------
+//        Debug Canvas
+// -----------------------------------------
+
+/// Collects debug primitives and writes them as a standalone SVG whose
+/// viewBox fits everything drawn.
+///
+/// Coordinates are in world units with `y` pointing up unless `y_up` is
+/// cleared, in which case they are used as SVG coordinates directly.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::svg_debug_module::{Style, SvgDebugCanvas};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// let mut canvas = SvgDebugCanvas::new();
+/// let velocity = Vector2d::new(3.0, 4.0);
+/// canvas.arrow(Vector2d::new(0.0, 0.0), velocity, &Style::new("crimson"));
+/// canvas.text(velocity, &format!("|v| = {}", velocity.magnitude()), &Style::default());
+/// let svg = canvas.to_svg();
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.contains("|v| = 5"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SvgDebugCanvas {
+    primitives: Vec<(Primitive, Style)>,
+    /// Width of the output in pixels; the height follows the aspect ratio.
+    pub width: f64,
+    /// Space around the drawing, as a fraction of its larger side.
+    pub margin: f64,
+    pub y_up: bool,
+    pub background: Option<String>,
+}
+
+impl Default for SvgDebugCanvas {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SvgDebugCanvas {
+    /// An empty 800 pixel wide canvas with a white background.
+    pub fn new() -> Self {
+        Self {
+            primitives: Vec::new(),
+            width: 800.0,
+            margin: 0.05,
+            y_up: true,
+            background: Some("white".to_string()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.primitives.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.primitives.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.primitives.clear();
+    }
+
+    /// Draws `vector` as an arrow starting at `origin`.
+    pub fn arrow<T>(&mut self, origin: Vector2d<T>, vector: Vector2d<T>, style: &Style) -> &mut Self
+    where
+        T: Float + AddAssign + std::fmt::Display,
+    {
+        let origin = vector_to_f64(origin);
+        self.push(
+            Primitive::Arrow(origin, origin + vector_to_f64(vector)),
+            style,
+        )
+    }
+
+    /// Draws a dot of radius `style.size / 3` pixels.
+    pub fn point<T>(&mut self, point: Vector2d<T>, style: &Style) -> &mut Self
+    where
+        T: Float + AddAssign + std::fmt::Display,
+    {
+        self.push(Primitive::Point(vector_to_f64(point)), style)
+    }
+
+    pub fn segment<T>(&mut self, a: Vector2d<T>, b: Vector2d<T>, style: &Style) -> &mut Self
+    where
+        T: Float + AddAssign + std::fmt::Display,
+    {
+        self.push(
+            Primitive::Segment(vector_to_f64(a), vector_to_f64(b)),
+            style,
+        )
+    }
+
+    /// Draws an open chain of segments.
+    pub fn polyline<T>(&mut self, points: &[Vector2d<T>], style: &Style) -> &mut Self
+    where
+        T: Float + AddAssign + std::fmt::Display,
+    {
+        let points = points.iter().map(|p| vector_to_f64(*p)).collect();
+        self.push(Primitive::Polyline(points, false), style)
+    }
+
+    /// Draws a closed polygon, filled when the style has a fill.
+    pub fn polygon<T>(&mut self, points: &[Vector2d<T>], style: &Style) -> &mut Self
+    where
+        T: Float + AddAssign + std::fmt::Display,
+    {
+        let points = points.iter().map(|p| vector_to_f64(*p)).collect();
+        self.push(Primitive::Polyline(points, true), style)
+    }
+
+    pub fn circle<T>(&mut self, center: Vector2d<T>, radius: T, style: &Style) -> &mut Self
+    where
+        T: Float + AddAssign + std::fmt::Display,
+    {
+        let radius = radius.to_f64().unwrap_or(0.0).abs();
+        self.push(Primitive::Circle(vector_to_f64(center), radius), style)
+    }
+
+    /// Writes `label` with its baseline starting at `position`.
+    pub fn text<T>(&mut self, position: Vector2d<T>, label: &str, style: &Style) -> &mut Self
+    where
+        T: Float + AddAssign + std::fmt::Display,
+    {
+        self.push(
+            Primitive::Text(vector_to_f64(position), label.to_string()),
+            style,
+        )
+    }
+
+    /// Draws a parsed SVG path given in world coordinates.
+    pub fn path(&mut self, path: &SvgPath, style: &Style) -> &mut Self {
+        self.push(Primitive::Path(path.clone()), style)
+    }
+
+    fn push(&mut self, primitive: Primitive, style: &Style) -> &mut Self {
+        self.primitives.push((primitive, style.clone()));
+        self
+    }
+
+    /// The world-space box around every primitive, text anchors included.
+    /// Primitives with non-finite coordinates are left out.
+    pub fn bounds(&self) -> Option<Aabb<f64>> {
+        let mut points = Vec::new();
+        for (primitive, _) in &self.primitives {
+            let extent = primitive.extent();
+            if extent.iter().all(|p| p.x.is_finite() && p.y.is_finite()) {
+                points.extend(extent);
+            }
+        }
+        let first = *points.first()?;
+        Some(points.iter().fold(Aabb::new(first, first), |acc, p| {
+            acc.union(&Aabb::new(*p, *p))
+        }))
+    }
+
+    /// Renders the standalone SVG document.
+    pub fn to_svg(&self) -> String {
+        let view = self
+            .bounds()
+            .unwrap_or_else(|| Aabb::new(Vector2d::new(-1.0, -1.0), Vector2d::new(1.0, 1.0)));
+        // Give degenerate drawings some extent, then add the margin.
+        let size = view.max - view.min;
+        let side = size.x.max(size.y).max(1e-9);
+        let pad = Vector2d::new(
+            (side * 0.01 - size.x).max(0.0) / 2.0 + side * self.margin,
+            (side * 0.01 - size.y).max(0.0) / 2.0 + side * self.margin,
+        );
+        let (min, max) = (view.min - pad, view.max + pad);
+        let extent = max - min;
+        // World units per output pixel.
+        let unit = extent.x / self.width;
+        let height = extent.y / unit;
+        let top = if self.y_up { -max.y } else { min.y };
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+            num(self.width),
+            num(height),
+            num(min.x),
+            num(top),
+            num(extent.x),
+            num(extent.y)
+        );
+        if let Some(background) = &self.background {
+            let _ = writeln!(
+                svg,
+                r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                num(min.x),
+                num(top),
+                num(extent.x),
+                num(extent.y),
+                escape(background)
+            );
+        }
+        // Skipped like in `bounds`, as they would make the document invalid.
+        for (primitive, style) in &self.primitives {
+            if primitive.is_finite() {
+                self.render(&mut svg, primitive, style, unit);
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Writes the SVG document to `path`.
+    ///
+    /// # Returns
+    ///
+    /// An `Err` when the file cannot be written.
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), String> {
+        std::fs::write(path.as_ref(), self.to_svg()).map_err(|error| {
+            format!(
+                "[::ERROR::] -> Cannot write {}: {}.",
+                path.as_ref().display(),
+                error
+            )
+        })
+    }
+
+    // World to SVG coordinates.
+    fn screen(&self, p: Vector2d<f64>) -> Vector2d<f64> {
+        if self.y_up {
+            Vector2d::new(p.x, -p.y)
+        } else {
+            p
+        }
+    }
+
+    fn map(&self, p: Vector2d<f64>) -> String {
+        let p = self.screen(p);
+        format!("{},{}", num(p.x), num(p.y))
+    }
+
+    fn render(&self, svg: &mut String, primitive: &Primitive, style: &Style, unit: f64) {
+        let stroke = format!(
+            r#"stroke="{}" stroke-width="{}" stroke-opacity="{}" vector-effect="non-scaling-stroke""#,
+            escape(&style.stroke),
+            num(style.stroke_width),
+            num(style.opacity)
+        );
+        let fill = match &style.fill {
+            Some(color) => format!(
+                r#"fill="{}" fill-opacity="{}""#,
+                escape(color),
+                num(style.opacity)
+            ),
+            None => r#"fill="none""#.to_string(),
+        };
+        let solid = format!(
+            r#"fill="{}" fill-opacity="{}""#,
+            escape(style.fill.as_deref().unwrap_or(&style.stroke)),
+            num(style.opacity)
+        );
+        let _ = match primitive {
+            Primitive::Arrow(from, to) => {
+                let direction = *to - *from;
+                let length = direction.magnitude();
+                let head = (length * 0.3).min(style.size * unit);
+                let (a, b) = (self.screen(*from), self.screen(*to));
+                let mut line = format!(
+                    r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" {} stroke-linecap="round"/>"#,
+                    num(a.x),
+                    num(a.y),
+                    num(b.x),
+                    num(b.y),
+                    stroke
+                );
+                if length > 0.0 {
+                    let back = direction * (head / length);
+                    let side = back.perpendicular() * 0.4;
+                    let _ = write!(
+                        line,
+                        "\n  <polygon points=\"{} {} {}\" {}/>",
+                        self.map(*to),
+                        self.map(*to - back + side),
+                        self.map(*to - back - side),
+                        solid
+                    );
+                }
+                writeln!(svg, "{}", line)
+            }
+            Primitive::Point(p) => {
+                let p = self.screen(*p);
+                writeln!(
+                    svg,
+                    r#"  <circle cx="{}" cy="{}" r="{}" {}/>"#,
+                    num(p.x),
+                    num(p.y),
+                    num(style.size / 3.0 * unit),
+                    solid
+                )
+            }
+            Primitive::Segment(a, b) => {
+                let (a, b) = (self.screen(*a), self.screen(*b));
+                writeln!(
+                    svg,
+                    r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" {}/>"#,
+                    num(a.x),
+                    num(a.y),
+                    num(b.x),
+                    num(b.y),
+                    stroke
+                )
+            }
+            Primitive::Polyline(points, closed) => {
+                let list: Vec<String> = points.iter().map(|p| self.map(*p)).collect();
+                let (tag, fill) = if *closed {
+                    ("polygon", fill.as_str())
+                } else {
+                    ("polyline", r#"fill="none""#)
+                };
+                writeln!(
+                    svg,
+                    r#"  <{} points="{}" {} {} stroke-linejoin="round"/>"#,
+                    tag,
+                    list.join(" "),
+                    fill,
+                    stroke
+                )
+            }
+            Primitive::Circle(center, radius) => {
+                let center = self.screen(*center);
+                writeln!(
+                    svg,
+                    r#"  <circle cx="{}" cy="{}" r="{}" {} {}/>"#,
+                    num(center.x),
+                    num(center.y),
+                    num(*radius),
+                    fill,
+                    stroke
+                )
+            }
+            Primitive::Text(p, label) => {
+                let p = self.screen(*p);
+                writeln!(
+                    svg,
+                    r#"  <text x="{}" y="{}" font-family="monospace" font-size="{}" fill="{}" fill-opacity="{}">{}</text>"#,
+                    num(p.x),
+                    num(p.y),
+                    num(style.size * unit),
+                    escape(&style.stroke),
+                    num(style.opacity),
+                    escape(label)
+                )
+            }
+            Primitive::Path(path) => {
+                let mut mapped = path.clone();
+                for subpath in &mut mapped.subpaths {
+                    subpath.start = self.screen(subpath.start);
+                    for segment in &mut subpath.segments {
+                        *segment = match *segment {
+                            PathSegment::Line { to } => PathSegment::Line {
+                                to: self.screen(to),
+                            },
+                            PathSegment::Quadratic { control, to } => PathSegment::Quadratic {
+                                control: self.screen(control),
+                                to: self.screen(to),
+                            },
+                            PathSegment::Cubic {
+                                control1,
+                                control2,
+                                to,
+                            } => PathSegment::Cubic {
+                                control1: self.screen(control1),
+                                control2: self.screen(control2),
+                                to: self.screen(to),
+                            },
+                        };
+                    }
+                }
+                writeln!(svg, r#"  <path d="{}" {} {}/>"#, mapped, fill, stroke)
+            }
+        };
+    }
+}
+
+// Shortest text that parses back to the same value, so drawings keep their
+// detail at any scale.
+fn num(value: f64) -> String {
+    if value == 0.0 || !value.is_finite() {
+        // Avoids "-0" from flipped coordinates, and "NaN" or "inf" from a
+        // degenerate width or style, which no SVG reader accepts.
+        "0".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,567 @@
+use crate::math::vector2d_module::Vector2d;
+pub use core::fmt;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+use std::str::FromStr;
+
+// -----------------------------------------
+//        Path Model
+// -----------------------------------------
+
+/// One segment of a sub-path. It starts where the previous one ends (or at
+/// the sub-path start) and ends at `to`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PathSegment {
+    Line {
+        to: Vector2d<f64>,
+    },
+    Quadratic {
+        control: Vector2d<f64>,
+        to: Vector2d<f64>,
+    },
+    Cubic {
+        control1: Vector2d<f64>,
+        control2: Vector2d<f64>,
+        to: Vector2d<f64>,
+    },
+}
+
+impl PathSegment {
+    pub fn end(&self) -> Vector2d<f64> {
+        match *self {
+            PathSegment::Line { to }
+            | PathSegment::Quadratic { to, .. }
+            | PathSegment::Cubic { to, .. } => to,
+        }
+    }
+}
+
+/// A connected run of segments started by a moveto.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubPath {
+    pub start: Vector2d<f64>,
+    pub segments: Vec<PathSegment>,
+    /// Closed by `Z`: an implicit line leads back to `start`.
+    pub closed: bool,
+}
+
+impl SubPath {
+    pub fn new(start: Vector2d<f64>) -> Self {
+        Self {
+            start,
+            segments: Vec::new(),
+            closed: false,
+        }
+    }
+
+    /// The current point after the last segment.
+    pub fn end(&self) -> Vector2d<f64> {
+        self.segments.last().map_or(self.start, PathSegment::end)
+    }
+}
+
+/// The geometry of an SVG `d` attribute: sub-paths of lines and quadratic and
+/// cubic Bezier curves in absolute coordinates. Arcs are converted to cubics
+/// while parsing.
+///
+/// `Display` writes the path back as absolute `M`/`L`/`Q`/`C`/`Z` commands,
+/// honouring a precision such as `{:.2}`; without one the shortest exact
+/// representation of every number is used, so the output parses back to the
+/// same path.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::svg_path_module::SvgPath;
+///
+/// let path: SvgPath = "m10 10 h 20 v20 z".parse().unwrap();
+/// assert_eq!(path.subpaths[0].segments.len(), 2);
+/// assert_eq!(path.to_string(), "M10 10 L30 10 L30 30 Z");
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SvgPath {
+    pub subpaths: Vec<SubPath>,
+}
+
+/// A malformed path: `offset` is the byte offset of the offending input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgPathError {
+    pub offset: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SvgPathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[::ERROR::] -> {} at offset {}.",
+            self.message, self.offset
+        )
+    }
+}
+
+impl std::error::Error for SvgPathError {}
+
+impl SvgPath {
+    /// Parses an SVG `d` attribute, absolute and relative commands included.
+    ///
+    /// # Returns
+    ///
+    /// The path, or an `SvgPathError` pointing at the first byte that does
+    /// not fit the path grammar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::svg_path_module::SvgPath;
+    ///
+    /// let error = SvgPath::parse("M 0 0 L 10 x").unwrap_err();
+    /// assert_eq!(error.offset, 11);
+    /// ```
+    pub fn parse(data: &str) -> Result<Self, SvgPathError> {
+        Parser::new(data).parse()
+    }
+}
+
+impl FromStr for SvgPath {
+    type Err = SvgPathError;
+
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        Self::parse(data)
+    }
+}
+
+impl fmt::Display for SvgPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision();
+        let point = |f: &mut fmt::Formatter, p: Vector2d<f64>| match precision {
+            Some(digits) => write!(f, "{:.*} {:.*}", digits, p.x, digits, p.y),
+            None => write!(f, "{} {}", p.x, p.y),
+        };
+        let mut first = true;
+        for subpath in &self.subpaths {
+            if !first {
+                write!(f, " ")?;
+            }
+            first = false;
+            write!(f, "M")?;
+            point(f, subpath.start)?;
+            for segment in &subpath.segments {
+                match *segment {
+                    PathSegment::Line { to } => {
+                        write!(f, " L")?;
+                        point(f, to)?;
+                    }
+                    PathSegment::Quadratic { control, to } => {
+                        write!(f, " Q")?;
+                        point(f, control)?;
+                        write!(f, " ")?;
+                        point(f, to)?;
+                    }
+                    PathSegment::Cubic {
+                        control1,
+                        control2,
+                        to,
+                    } => {
+                        write!(f, " C")?;
+                        point(f, control1)?;
+                        write!(f, " ")?;
+                        point(f, control2)?;
+                        write!(f, " ")?;
+                        point(f, to)?;
+                    }
+                }
+            }
+            if subpath.closed {
+                write!(f, " Z")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// -----------------------------------------
+//        Elliptical Arcs
+// -----------------------------------------
+
+/// Converts the SVG elliptical arc from `from` to `to` into cubic Bezier
+/// segments, each spanning at most 90°. Out-of-range radii are handled as the
+/// SVG specification requires: zero radii give a straight line and radii too
+/// small to reach `to` are scaled up.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::svg_path_module::{arc_to_cubics, PathSegment};
+/// use vectorlib::math::vector2d_module::Vector2d;
+///
+/// // Half of the unit circle, drawn counter-clockwise on screen.
+/// let segments = arc_to_cubics(
+///     Vector2d::new(1.0, 0.0),
+///     Vector2d::new(1.0, 1.0),
+///     0.0,
+///     false,
+///     false,
+///     Vector2d::new(-1.0, 0.0),
+/// );
+/// assert_eq!(segments.len(), 2);
+/// assert!((segments[0].end() - Vector2d::new(0.0, -1.0)).magnitude() < 1e-12);
+/// ```
+pub fn arc_to_cubics(
+    from: Vector2d<f64>,
+    radii: Vector2d<f64>,
+    x_axis_rotation: f64,
+    large_arc: bool,
+    sweep: bool,
+    to: Vector2d<f64>,
+) -> Vec<PathSegment> {
+    if from == to {
+        return Vec::new();
+    }
+    let (mut rx, mut ry) = (radii.x.abs(), radii.y.abs());
+    if rx == 0.0 || ry == 0.0 {
+        return vec![PathSegment::Line { to }];
+    }
+    // Endpoint to centre parameterisation (SVG 1.1, appendix F.6.5).
+    let (sin_phi, cos_phi) = x_axis_rotation.to_radians().sin_cos();
+    let half = (from - to) * 0.5;
+    let x1 = cos_phi * half.x + sin_phi * half.y;
+    let y1 = -sin_phi * half.x + cos_phi * half.y;
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut factor = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        factor = -factor;
+    }
+    let cx1 = factor * rx * y1 / ry;
+    let cy1 = -factor * ry * x1 / rx;
+    let mid = (from + to) * 0.5;
+    let center = Vector2d::new(
+        cos_phi * cx1 - sin_phi * cy1 + mid.x,
+        sin_phi * cx1 + cos_phi * cy1 + mid.y,
+    );
+    let angle = |ux: f64, uy: f64| uy.atan2(ux);
+    let theta1 = angle((x1 - cx1) / rx, (y1 - cy1) / ry);
+    let mut delta = angle((-x1 - cx1) / rx, (-y1 - cy1) / ry) - theta1;
+    let tau = std::f64::consts::TAU;
+    if sweep && delta < 0.0 {
+        delta += tau;
+    } else if !sweep && delta > 0.0 {
+        delta -= tau;
+    }
+
+    let pieces = (delta.abs() / std::f64::consts::FRAC_PI_2 - 1e-9)
+        .ceil()
+        .max(1.0) as usize;
+    let step = delta / pieces as f64;
+    let handle = 4.0 / 3.0 * (step / 4.0).tan();
+    let on_ellipse = |t: f64| {
+        let (sin_t, cos_t) = t.sin_cos();
+        let (ex, ey) = (rx * cos_t, ry * sin_t);
+        let (dx, dy) = (-rx * sin_t, ry * cos_t);
+        (
+            center + Vector2d::new(cos_phi * ex - sin_phi * ey, sin_phi * ex + cos_phi * ey),
+            Vector2d::new(cos_phi * dx - sin_phi * dy, sin_phi * dx + cos_phi * dy),
+        )
+    };
+    let mut segments = Vec::with_capacity(pieces);
+    let (mut start, mut start_tangent) = (from, on_ellipse(theta1).1);
+    for piece in 1..=pieces {
+        let (mut end, end_tangent) = on_ellipse(theta1 + step * piece as f64);
+        if piece == pieces {
+            end = to;
+        }
+        segments.push(PathSegment::Cubic {
+            control1: start + start_tangent * handle,
+            control2: end - end_tangent * handle,
+            to: end,
+        });
+        start = end;
+        start_tangent = end_tangent;
+    }
+    segments
+}
+
+// -----------------------------------------
+//        Parser
+// -----------------------------------------
+
+struct Parser<'a> {
+    data: &'a str,
+    bytes: &'a [u8],
+    position: usize,
+    path: SvgPath,
+    current: Vector2d<f64>,
+    // The control point to reflect for `S` and `T`, if the previous segment
+    // was of the matching kind.
+    last_cubic_control: Option<Vector2d<f64>>,
+    last_quadratic_control: Option<Vector2d<f64>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(data: &'a str) -> Self {
+        Self {
+            data,
+            bytes: data.as_bytes(),
+            position: 0,
+            path: SvgPath::default(),
+            current: Vector2d::new(0.0, 0.0),
+            last_cubic_control: None,
+            last_quadratic_control: None,
+        }
+    }
+
+    fn error<V>(&self, offset: usize, message: impl Into<String>) -> Result<V, SvgPathError> {
+        Err(SvgPathError {
+            offset,
+            message: message.into(),
+        })
+    }
+
+    fn parse(mut self) -> Result<SvgPath, SvgPathError> {
+        let mut command: Option<u8> = None;
+        loop {
+            self.skip_whitespace();
+            let Some(&byte) = self.bytes.get(self.position) else {
+                break;
+            };
+            let letter = if byte.is_ascii_alphabetic() {
+                if !b"MmLlHhVvCcSsQqTtAaZz".contains(&byte) {
+                    return self
+                        .error(self.position, format!("Unknown command '{}'", byte as char));
+                }
+                self.position += 1;
+                byte
+            } else if let Some(previous) = command.filter(|c| !matches!(c, b'Z' | b'z')) {
+                self.skip_separator();
+                if !self.at_number() {
+                    return self.error(
+                        self.position,
+                        format!("Expected a number, {}", self.unexpected()),
+                    );
+                }
+                // Extra coordinates repeat the command; after a moveto they
+                // are linetos.
+                match previous {
+                    b'M' => b'L',
+                    b'm' => b'l',
+                    other => other,
+                }
+            } else {
+                return self.error(
+                    self.position,
+                    format!("Expected a command, {}", self.unexpected()),
+                );
+            };
+            if command.is_none() && !matches!(letter, b'M' | b'm') {
+                return self.error(self.position - 1, "A path must start with a moveto");
+            }
+            command = Some(letter);
+            self.command(letter)?;
+        }
+        Ok(self.path)
+    }
+
+    fn command(&mut self, letter: u8) -> Result<(), SvgPathError> {
+        let relative = letter.is_ascii_lowercase();
+        let origin = if relative {
+            self.current
+        } else {
+            Vector2d::new(0.0, 0.0)
+        };
+        let mut cubic_control = None;
+        let mut quadratic_control = None;
+        match letter.to_ascii_uppercase() {
+            b'M' => {
+                let to = origin + self.point()?;
+                self.path.subpaths.push(SubPath::new(to));
+                self.current = to;
+            }
+            b'L' => {
+                let to = origin + self.point()?;
+                self.push(PathSegment::Line { to });
+            }
+            b'H' => {
+                let x = self.number()?;
+                let to = Vector2d::new(origin.x + x, self.current.y);
+                self.push(PathSegment::Line { to });
+            }
+            b'V' => {
+                let y = self.number()?;
+                let to = Vector2d::new(self.current.x, origin.y + y);
+                self.push(PathSegment::Line { to });
+            }
+            b'C' | b'S' => {
+                let control1 = if letter.eq_ignore_ascii_case(&b'C') {
+                    origin + self.point()?
+                } else {
+                    self.reflect(self.last_cubic_control)
+                };
+                let control2 = origin + self.point()?;
+                let to = origin + self.point()?;
+                self.push(PathSegment::Cubic {
+                    control1,
+                    control2,
+                    to,
+                });
+                cubic_control = Some(control2);
+            }
+            b'Q' | b'T' => {
+                let control = if letter.eq_ignore_ascii_case(&b'Q') {
+                    origin + self.point()?
+                } else {
+                    self.reflect(self.last_quadratic_control)
+                };
+                let to = origin + self.point()?;
+                self.push(PathSegment::Quadratic { control, to });
+                quadratic_control = Some(control);
+            }
+            b'A' => {
+                let radii = Vector2d::new(self.number()?, self.number()?);
+                let rotation = self.number()?;
+                let large_arc = self.flag()?;
+                let sweep = self.flag()?;
+                let to = origin + self.point()?;
+                let from = self.current;
+                for segment in arc_to_cubics(from, radii, rotation, large_arc, sweep, to) {
+                    self.push(segment);
+                }
+                self.current = to;
+            }
+            _ => {
+                // `Z`: close and return to the start, where a command without
+                // its own moveto continues.
+                if let Some(subpath) = self.path.subpaths.last_mut() {
+                    subpath.closed = true;
+                    self.current = subpath.start;
+                }
+            }
+        }
+        self.last_cubic_control = cubic_control;
+        self.last_quadratic_control = quadratic_control;
+        Ok(())
+    }
+
+    fn push(&mut self, segment: PathSegment) {
+        let needs_subpath = self.path.subpaths.last().is_none_or(|s| s.closed);
+        if needs_subpath {
+            self.path.subpaths.push(SubPath::new(self.current));
+        }
+        if let Some(subpath) = self.path.subpaths.last_mut() {
+            subpath.segments.push(segment);
+        }
+        self.current = segment.end();
+    }
+
+    fn reflect(&self, control: Option<Vector2d<f64>>) -> Vector2d<f64> {
+        match control {
+            Some(control) => self.current * 2.0 - control,
+            None => self.current,
+        }
+    }
+
+    fn point(&mut self) -> Result<Vector2d<f64>, SvgPathError> {
+        Ok(Vector2d::new(self.number()?, self.number()?))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .bytes
+            .get(self.position)
+            .is_some_and(|b| b.is_ascii_whitespace())
+        {
+            self.position += 1;
+        }
+    }
+
+    // Whitespace with at most one comma between two arguments.
+    fn skip_separator(&mut self) {
+        self.skip_whitespace();
+        if self.bytes.get(self.position) == Some(&b',') {
+            self.position += 1;
+            self.skip_whitespace();
+        }
+    }
+
+    fn at_number(&self) -> bool {
+        self.bytes
+            .get(self.position)
+            .is_some_and(|b| b.is_ascii_digit() || matches!(b, b'+' | b'-' | b'.'))
+    }
+
+    fn unexpected(&self) -> String {
+        match self.data[self.position..].chars().next() {
+            Some(c) => format!("found '{}'", c),
+            None => "found the end of the data".to_string(),
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, SvgPathError> {
+        self.skip_separator();
+        let start = self.position;
+        let digits = |parser: &mut Self| {
+            let from = parser.position;
+            while parser
+                .bytes
+                .get(parser.position)
+                .is_some_and(u8::is_ascii_digit)
+            {
+                parser.position += 1;
+            }
+            parser.position - from
+        };
+        if matches!(self.bytes.get(self.position), Some(b'+' | b'-')) {
+            self.position += 1;
+        }
+        let mut count = digits(self);
+        if self.bytes.get(self.position) == Some(&b'.') {
+            self.position += 1;
+            count += digits(self);
+        }
+        if count == 0 {
+            self.position = start;
+            return self.error(start, format!("Expected a number, {}", self.unexpected()));
+        }
+        if matches!(self.bytes.get(self.position), Some(b'e' | b'E')) {
+            let exponent = self.position;
+            self.position += 1;
+            if matches!(self.bytes.get(self.position), Some(b'+' | b'-')) {
+                self.position += 1;
+            }
+            if digits(self) == 0 {
+                self.position = exponent;
+                return self.error(exponent, "Expected digits in the exponent");
+            }
+        }
+        let text = &self.data[start..self.position];
+        match text.parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(value),
+            _ => self.error(start, format!("Number '{}' is out of range", text)),
+        }
+    }
+
+    fn flag(&mut self) -> Result<bool, SvgPathError> {
+        self.skip_separator();
+        match self.bytes.get(self.position) {
+            Some(b'0') => {
+                self.position += 1;
+                Ok(false)
+            }
+            Some(b'1') => {
+                self.position += 1;
+                Ok(true)
+            }
+            _ => self.error(
+                self.position,
+                format!("Expected an arc flag (0 or 1), {}", self.unexpected()),
+            ),
+        }
+    }
+}
@@ -6,6 +6,7 @@ use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
 use std::ops::{Index, IndexMut};
 
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector2d<T>
 where
     T: Add<T, Output = T>
@@ -232,6 +233,27 @@ where
     }
 }
 
+impl<T> Neg for Vector2d<T>
+where
+    T: Add<T, Output = T>
+        + Copy
+        + AddAssign
+        + std::fmt::Display
+        + Neg<Output = T>
+        + Sub<T, Output = T>
+        + Div<T, Output = T>
+        + Mul<T, Output = T>,
+{
+    type Output = Vector2d<T>;
+
+    fn neg(self) -> Vector2d<T> {
+        Vector2d {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
 impl<T> Vector2d<T>
 where
     T: std::fmt::Display
@@ -415,8 +437,8 @@ where
     /// # Type Constraints
     ///
     /// - `T`: Must implement `std::fmt::Display`, `Copy`, `Float`, `AddAssign`,
-    ///        `Add<T, Output = T>`, `Sub<T, Output = T>`, `Mul<T, Output = T>`,
-    ///        and `Div<T, Output = T>`.
+    ///   `Add<T, Output = T>`, `Sub<T, Output = T>`, `Mul<T, Output = T>`,
+    ///   and `Div<T, Output = T>`.
     ///
     /// # Examples
     ///
@@ -429,7 +451,6 @@ where
     /// let distance = v1.distance(v2);
     /// println!("Distance: {}", distance);
     /// ```
-
     pub fn distance(self, other: Vector2d<T>) -> T
     where
         T: std::fmt::Display
@@ -663,8 +684,8 @@ where
     /// # Type Constraints
     ///
     /// - `T`: Must implement `std::fmt::Display`, `Copy`, `Float`, `Zero`,
-    ///        `AddAssign`, `Add<T, Output = T>`, `Sub<T, Output = T>`,
-    ///        `Mul<T, Output = T>`, and `Div<T, Output = T>`.
+    ///   `AddAssign`, `Add<T, Output = T>`, `Sub<T, Output = T>`,
+    ///   `Mul<T, Output = T>`, and `Div<T, Output = T>`.
     ///
     /// # Examples
     ///
@@ -720,7 +741,7 @@ where
     /// # Type Constraints
     ///
     /// - `T`: Must implement `std::fmt::Display`, `Copy`, `Add<T, Output = T>`,
-    ///        `Sub<T, Output = T>`, and `Mul<T, Output = T>`.
+    ///   `Sub<T, Output = T>`, and `Mul<T, Output = T>`.
     ///
     /// # Examples
     ///
@@ -782,7 +803,7 @@ where
     /// # Type Constraints
     ///
     /// - `T`: Must implement `std::fmt::Display`, `Copy`, `Add<T, Output = T>`,
-    ///        `Sub<T, Output = T>`, and `Mul<T, Output = T>`.
+    ///   `Sub<T, Output = T>`, and `Mul<T, Output = T>`.
     ///
     /// # Examples
     ///
@@ -807,6 +828,46 @@ where
         let y = self.y.round();
         Self { x, y }
     }
+
+    /// Computes the 2D cross product (the `z` component of the 3D cross product).
+    ///
+    /// The result is positive when `other` lies counter-clockwise from `self`,
+    /// negative when it lies clockwise and zero when both vectors are parallel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let v1 = Vector2d::new(1.0, 0.0);
+    /// let v2 = Vector2d::new(0.0, 1.0);
+    ///
+    /// assert_eq!(v1.cross_product(v2), 1.0);
+    /// assert_eq!(v2.cross_product(v1), -1.0);
+    /// ```
+    pub fn cross_product(self, other: Vector2d<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Returns the vector rotated by 90 degrees counter-clockwise, `(-y, x)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let v = Vector2d::new(3.0, 4.0);
+    /// assert_eq!(v.perpendicular(), Vector2d::new(-4.0, 3.0));
+    /// ```
+    pub fn perpendicular(self) -> Self
+    where
+        T: Neg<Output = T>,
+    {
+        Self {
+            x: -self.y,
+            y: self.x,
+        }
+    }
 }
 
 // -----------------------------------------
@@ -856,6 +917,214 @@ where
 //      Implementing the fmt Display
 //-----------------------------------------
 impl<T> fmt::Display for Vector2d<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + AddAssign
+        + Copy
+        + std::fmt::Display,
+{
+    /// Writes `< x, y >`. Precision defaults to three decimals; precision,
+    /// sign, width, fill and alignment flags apply to each component.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let v = Vector2d::new(1.0, -0.5);
+    /// assert_eq!(format!("{}", v), "< 1.000, -0.500 >");
+    /// assert_eq!(format!("{:.1}", v), "< 1.0, -0.5 >");
+    /// assert_eq!(format!("{:+.2}", v), "< +1.00, -0.50 >");
+    /// assert_eq!(format!("{:>6.1}", v), "<    1.0,   -0.5 >");
+    /// assert_eq!(format!("{:06.2}", v), "< 001.00, -00.50 >");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        let render = |value: &T, plus: bool| match plus {
+            true => format!("{:+.*}", precision, value),
+            false => format!("{:.*}", precision, value),
+        };
+        write_components(f, [&self.x, &self.y], render)
+    }
+}
+
+impl<T> fmt::LowerExp for Vector2d<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + AddAssign
+        + Copy
+        + std::fmt::Display
+        + fmt::LowerExp,
+{
+    /// Writes `< x, y >` in scientific notation, honoring the same flags as
+    /// `Display`. Without a precision the shortest exact form is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let v = Vector2d::new(1500.0, -0.025);
+    /// assert_eq!(format!("{:e}", v), "< 1.5e3, -2.5e-2 >");
+    /// assert_eq!(format!("{:+.2e}", v), "< +1.50e3, -2.50e-2 >");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision();
+        let render = |value: &T, plus: bool| match (precision, plus) {
+            (Some(digits), true) => format!("{:+.*e}", digits, value),
+            (Some(digits), false) => format!("{:.*e}", digits, value),
+            (None, true) => format!("{:+e}", value),
+            (None, false) => format!("{:e}", value),
+        };
+        write_components(f, [&self.x, &self.y], render)
+    }
+}
+
+impl<T> fmt::UpperExp for Vector2d<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + AddAssign
+        + Copy
+        + std::fmt::Display
+        + fmt::UpperExp,
+{
+    /// Same as `LowerExp` with an upper case `E`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let v = Vector2d::new(1500.0, -0.025);
+    /// assert_eq!(format!("{:.1E}", v), "< 1.5E3, -2.5E-2 >");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision();
+        let render = |value: &T, plus: bool| match (precision, plus) {
+            (Some(digits), true) => format!("{:+.*E}", digits, value),
+            (Some(digits), false) => format!("{:.*E}", digits, value),
+            (None, true) => format!("{:+E}", value),
+            (None, false) => format!("{:E}", value),
+        };
+        write_components(f, [&self.x, &self.y], render)
+    }
+}
+
+// Writes `< x, y >` with each component rendered by `render` (which handles
+// precision and the `+` flag) and then padded to the formatter's width.
+fn write_components<T, F>(f: &mut fmt::Formatter, components: [&T; 2], render: F) -> fmt::Result
+where
+    F: Fn(&T, bool) -> String,
+{
+    f.write_str("< ")?;
+    for (index, value) in components.into_iter().enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        let text = render(value, f.sign_plus());
+        let padding = f.width().unwrap_or(0).saturating_sub(text.chars().count());
+        if f.sign_aware_zero_pad() {
+            // Zeros go between the sign and the digits, like `{:08.3}` on f64.
+            let digits = text.trim_start_matches(['+', '-']);
+            f.write_str(&text[..text.len() - digits.len()])?;
+            f.write_str(&"0".repeat(padding))?;
+            f.write_str(digits)?;
+            continue;
+        }
+        let (before, after) = match f.align() {
+            Some(fmt::Alignment::Left) => (0, padding),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            Some(fmt::Alignment::Right) | None => (padding, 0),
+        };
+        let fill = f.fill().to_string();
+        f.write_str(&fill.repeat(before))?;
+        f.write_str(&text)?;
+        f.write_str(&fill.repeat(after))?;
+    }
+    f.write_str(" >")
+}
+
+//-----------------------------------------
+//      Colored terminal output
+//-----------------------------------------
+
+impl<T> Vector2d<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + AddAssign
+        + Copy
+        + std::fmt::Display,
+{
+    /// Renders the vector for terminal debug output: `x` in red, `y` in green
+    /// and the magnitude in cyan, e.g. `< 3.00, 4.00 > |5.00|`. Components
+    /// whose magnitude is at least `highlight` are shown in bold, which makes
+    /// runaway values stand out in long logs.
+    ///
+    /// Colors follow the `colored` crate's global switch, so they disappear
+    /// when `NO_COLOR` is set or output is forced plain with
+    /// `colored::control::set_override(false)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// colored::control::set_override(false);
+    /// let v = Vector2d::new(3.0, 4.0);
+    /// assert_eq!(v.to_colored_string(2, 100.0), "< 3.00, 4.00 > |5.00|");
+    /// ```
+    pub fn to_colored_string(&self, precision: usize, highlight: T) -> String
+    where
+        T: Float,
+    {
+        use colored::Colorize;
+
+        let component = |value: T, color: colored::Color| {
+            let text = format!("{:.*}", precision, value).color(color);
+            if value.abs() >= highlight {
+                text.bold()
+            } else {
+                text
+            }
+        };
+        format!(
+            "< {}, {} > {}",
+            component(self.x, colored::Color::Red),
+            component(self.y, colored::Color::Green),
+            format!("|{:.*}|", precision, self.magnitude()).cyan()
+        )
+    }
+}
+
+/// Displays a `Vector2d` as `< x, y >` with every digit needed to parse the
+/// components back exactly, see `Vector2d::lossless`.
+#[derive(Debug, Clone, Copy)]
+pub struct LosslessDisplay<'a, T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + AddAssign
+        + Copy
+        + std::fmt::Display,
+{
+    vector: &'a Vector2d<T>,
+}
+
+impl<T> fmt::Display for LosslessDisplay<'_, T>
 where
     T: Add<T, Output = T>
         + Sub<T, Output = T>
@@ -866,6 +1135,146 @@ where
         + std::fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "< {:.3}, {:.3} >", self.x, self.y)
+        // Without a precision, float `Display` prints the shortest text that
+        // parses back to the same value.
+        write!(f, "< {}, {} >", self.vector.x, self.vector.y)
+    }
+}
+
+impl<T> Vector2d<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + AddAssign
+        + Copy
+        + std::fmt::Display,
+{
+    /// Returns a wrapper that displays the vector at full precision, so that
+    /// parsing the output gives back the same vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let v = Vector2d::new(0.1 + 0.2, -1.0 / 3.0);
+    /// let text = v.lossless().to_string();
+    /// assert_eq!(text, "< 0.30000000000000004, -0.3333333333333333 >");
+    /// assert_eq!(text.parse::<Vector2d<f64>>(), Ok(v));
+    /// ```
+    pub fn lossless(&self) -> LosslessDisplay<'_, T> {
+        LosslessDisplay { vector: self }
+    }
+}
+
+//-----------------------------------------
+//      Parsing from strings
+//-----------------------------------------
+
+/// Why a string could not be parsed as a `Vector2d`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseVector2dError {
+    /// The input was empty or only whitespace.
+    Empty,
+    /// An opening `<`, `(` or `[` without its matching closing delimiter, or
+    /// the other way around.
+    UnbalancedDelimiters,
+    /// The input did not hold exactly two comma separated components.
+    ComponentCount(usize),
+    /// Component `index` (0 for `x`, 1 for `y`) is not a valid number.
+    InvalidComponent { index: usize, text: String },
+}
+
+impl fmt::Display for ParseVector2dError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(
+                f,
+                "[::ERROR::] -> Cannot parse a vector from an empty string."
+            ),
+            Self::UnbalancedDelimiters => {
+                write!(f, "[::ERROR::] -> Unbalanced delimiters around the vector.")
+            }
+            Self::ComponentCount(count) => write!(
+                f,
+                "[::ERROR::] -> Expected 2 vector components, found {}.",
+                count
+            ),
+            Self::InvalidComponent { index, text } => write!(
+                f,
+                "[::ERROR::] -> Invalid {} component {:?}.",
+                if *index == 0 { "x" } else { "y" },
+                text
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseVector2dError {}
+
+/// Parses `< x, y >`, `(x, y)`, `[x, y]` and bare `x, y`, the first being
+/// what `Display` prints. Whitespace around delimiters and components is
+/// ignored.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::vector2d_module::{ParseVector2dError, Vector2d};
+///
+/// let v: Vector2d<f64> = "(1.5, -2)".parse().unwrap();
+/// assert_eq!(v, Vector2d::new(1.5, -2.0));
+/// assert_eq!("[3,4]".parse(), Ok(Vector2d::new(3.0f32, 4.0)));
+/// assert_eq!(
+///     "< 1, 2, 3 >".parse::<Vector2d<f64>>(),
+///     Err(ParseVector2dError::ComponentCount(3))
+/// );
+/// ```
+impl<T> std::str::FromStr for Vector2d<T>
+where
+    T: Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + AddAssign
+        + Copy
+        + std::fmt::Display
+        + std::str::FromStr,
+{
+    type Err = ParseVector2dError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(ParseVector2dError::Empty);
+        }
+        let inner = [('<', '>'), ('(', ')'), ('[', ']')]
+            .iter()
+            .find_map(
+                |&(open, close)| match (text.starts_with(open), text.ends_with(close)) {
+                    (false, false) => None,
+                    (true, true) if text.len() > 1 => Some(Ok(&text[1..text.len() - 1])),
+                    _ => Some(Err(ParseVector2dError::UnbalancedDelimiters)),
+                },
+            )
+            .unwrap_or(Ok(text))?;
+
+        let components: Vec<&str> = inner.split(',').map(str::trim).collect();
+        if components.len() != 2 {
+            return Err(ParseVector2dError::ComponentCount(components.len()));
+        }
+        let parse = |index: usize| {
+            components[index]
+                .parse::<T>()
+                .map_err(|_| ParseVector2dError::InvalidComponent {
+                    index,
+                    text: components[index].to_string(),
+                })
+        };
+        Ok(Self {
+            x: parse(0)?,
+            y: parse(1)?,
+        })
     }
 }
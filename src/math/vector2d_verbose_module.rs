@@ -4,25 +4,83 @@ pub use core::fmt;
 use num_traits::{Float, FromPrimitive, Num, Zero};
 #[allow(unused_imports)]
 use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+use std::panic::Location;
+use std::sync::{PoisonError, RwLock};
 
+// -----------------------------------------
+//       Logging configuration
+// -----------------------------------------
 
+/// Decides which `VerboseVector2d` values report through `log`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerbosePolicy {
+    /// Each vector's own `verbose` flag decides.
+    #[default]
+    PerInstance,
+    /// Every vector reports, whatever its flag.
+    Always,
+    /// Nothing is reported.
+    Never,
+}
 
-macro_rules! function_location {
-    () => {{
-        fn f() {}
-        fn type_name_of<T>(_: T) -> &'static str {
-            std::any::type_name::<T>()
-        }
-        let name = type_name_of(f);
-        name.strip_suffix("::f").unwrap()
-    }}
+/// Where and how verbose vectors report. Records carry the structured fields
+/// `event` (`display`, `into_inner` or `drop`), `address`, `value` and
+/// `call_site`, so the application's logger can filter on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerboseConfig {
+    pub target: &'static str,
+    pub level: log::Level,
+    pub policy: VerbosePolicy,
+}
+
+impl VerboseConfig {
+    pub const DEFAULT: VerboseConfig = VerboseConfig {
+        target: "vectorlib::verbose",
+        level: log::Level::Debug,
+        policy: VerbosePolicy::PerInstance,
+    };
+}
+
+impl Default for VerboseConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+static CONFIG: RwLock<VerboseConfig> = RwLock::new(VerboseConfig::DEFAULT);
+
+/// Replaces the process wide configuration of verbose vectors.
+///
+/// # Examples
+///
+/// ```
+/// use vectorlib::math::vector2d_verbose_module::{
+///     set_verbose_config, verbose_config, VerboseConfig, VerbosePolicy,
+/// };
+///
+/// // Silence every verbose vector, e.g. in release builds.
+/// set_verbose_config(VerboseConfig {
+///     policy: VerbosePolicy::Never,
+///     ..VerboseConfig::default()
+/// });
+/// assert_eq!(verbose_config().policy, VerbosePolicy::Never);
+/// ```
+pub fn set_verbose_config(config: VerboseConfig) {
+    *CONFIG.write().unwrap_or_else(PoisonError::into_inner) = config;
+}
+
+pub fn verbose_config() -> VerboseConfig {
+    *CONFIG.read().unwrap_or_else(PoisonError::into_inner)
 }
 
 // -----------------------------------------
 //       Verbose Vector Implemnetation
 // -----------------------------------------
+/// A `Vector2d` that reports through `log` when it is displayed, moved out or
+/// dropped, subject to its `verbose` flag and the global `VerboseConfig`.
 #[allow(dead_code)]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VerboseVector2d<T>
 where
     T: Add<T, Output = T>
@@ -35,6 +93,9 @@ where
 {
     vector: Option<Vector2d<T>>,
     verbose: bool,
+    // Where the vector was created, reported as the call site on drop.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    created_at: Option<&'static Location<'static>>,
 }
 
 #[allow(dead_code)]
@@ -48,33 +109,86 @@ where
         + Div<T, Output = T>
         + AddAssign,
 {
+    #[track_caller]
     pub fn new(vector: Vector2d<T>, verbose: bool) -> Self {
         VerboseVector2d {
             vector: Some(vector),
             verbose,
+            created_at: Some(Location::caller()),
         }
     }
 
+    pub fn is_verbose(&self) -> bool {
+        self.verbose
+    }
+
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+    }
+
+    /// Logs the vector with its address and the caller's location.
+    #[track_caller]
     pub fn display_if_verbose(&self)
     where
         T: std::fmt::Display,
     {
-        if self.verbose {
-            if let Some(vector) = self.vector {
-                println!("Vector2d: ({}, {})", vector.x, vector.y);
-            }
+        if let Some(vector) = self.vector {
+            self.report("display", vector, Some(Location::caller()));
         }
     }
 
+    /// Moves the vector out, logging it first. Nothing is logged when the
+    /// wrapper is dropped afterwards.
+    #[track_caller]
     pub fn into_inner(mut self) -> Option<Vector2d<T>> {
         let vector = self.vector.take();
-        if self.verbose && vector.is_some() {
-            if let Some(vector) = vector {
-                println!("Vector2d: ({}, {})", vector.x, vector.y);
-            }
+        if let Some(vector) = vector {
+            self.report("into_inner", vector, Some(Location::caller()));
         }
         vector
     }
+
+    fn report(&self, event: &str, vector: Vector2d<T>, call_site: Option<&Location>) {
+        let config = verbose_config();
+        let enabled = match config.policy {
+            VerbosePolicy::PerInstance => self.verbose,
+            VerbosePolicy::Always => true,
+            VerbosePolicy::Never => false,
+        };
+        if !enabled || !log::log_enabled!(target: config.target, config.level) {
+            return;
+        }
+        let call_site = call_site.map_or_else(|| "unknown".to_string(), |site| site.to_string());
+        log::log!(
+            target: config.target,
+            config.level,
+            event = event,
+            address:? = self as *const Self,
+            value:% = vector,
+            call_site = call_site.as_str();
+            "Vector2d: {} ({}) at {}",
+            vector,
+            event,
+            call_site
+        );
+    }
+}
+
+// Equality ignores where the vectors were created.
+impl<T> PartialEq for VerboseVector2d<T>
+where
+    T: Add<T, Output = T>
+        + Copy
+        + std::fmt::Display
+        + Sub<T, Output = T>
+        + Mul<T, Output = T>
+        + Div<T, Output = T>
+        + AddAssign
+        + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.vector == other.vector && self.verbose == other.verbose
+    }
 }
 
 impl<T> Drop for VerboseVector2d<T>
@@ -88,15 +202,8 @@ where
         + AddAssign,
 {
     fn drop(&mut self) {
-        if self.verbose {
-            if let Some(vector) = self.vector.take() {
-                let function_call = function_location!();
-                println!(
-                    "Vector2d: <{:.3}, {:.3}> has been droped from memeory",
-                    vector.x, vector.y
-                );
-                println!("Last call before dropped: {function_call}")
-            }
+        if let Some(vector) = self.vector.take() {
+            self.report("drop", vector, self.created_at);
         }
     }
 }
@@ -121,5 +228,3 @@ where
         write!(f, " - Verbose: {}", self.verbose)
     }
 }
-
-
@@ -0,0 +1,379 @@
+use crate::math::noise_module::Noise2d;
+use crate::math::pathfinding_module::OccupancyGrid;
+use crate::math::scalar_module::cast;
+use crate::math::vector2d_module::Vector2d;
+#[allow(unused_imports)]
+use num_traits::{Float, FromPrimitive, Num, Zero};
+#[allow(unused_imports)]
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+
+// Step of the central differences used for divergence and curl.
+const DIFFERENCE_STEP: f64 = 1e-3;
+
+// -----------------------------------------
+//        Vector Field Trait
+// -----------------------------------------
+
+/// A field assigning a `Vector2d` to every point of the plane.
+///
+/// Only `sample` has to be implemented; divergence, curl and streamlines are
+/// derived from it.
+pub trait VectorField2d<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn sample(&self, point: Vector2d<T>) -> Vector2d<T>;
+
+    /// The divergence `∂u/∂x + ∂v/∂y` at `point`: positive where the field
+    /// spreads out, negative where it converges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::vector_field_module::{FnField, VectorField2d};
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let source = FnField::new(|p: Vector2d<f64>| p);
+    /// assert!((source.divergence(Vector2d::new(3.0, 1.0)) - 2.0).abs() < 1e-9);
+    /// ```
+    fn divergence(&self, point: Vector2d<T>) -> T {
+        let (dx, dy) = self.partials(point);
+        dx.x + dy.y
+    }
+
+    /// The scalar curl `∂v/∂x - ∂u/∂y` at `point`: positive where the field
+    /// turns counter-clockwise.
+    fn curl(&self, point: Vector2d<T>) -> T {
+        let (dx, dy) = self.partials(point);
+        dx.y - dy.x
+    }
+
+    /// The partial derivatives `(∂F/∂x, ∂F/∂y)` at `point`.
+    fn partials(&self, point: Vector2d<T>) -> (Vector2d<T>, Vector2d<T>) {
+        let h = cast::<T>(DIFFERENCE_STEP);
+        let scale = T::one() / (h + h);
+        let ex = Vector2d::new(h, T::zero());
+        let ey = Vector2d::new(T::zero(), h);
+        (
+            (self.sample(point + ex) - self.sample(point - ex)) * scale,
+            (self.sample(point + ey) - self.sample(point - ey)) * scale,
+        )
+    }
+
+    /// Traces the streamline through `start` with classic fourth order
+    /// Runge-Kutta steps of duration `step` (negative to go upstream).
+    ///
+    /// # Returns
+    ///
+    /// The visited points starting with `start`. Tracing stops after
+    /// `max_steps` steps or where the field vanishes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::vector_field_module::{FnField, VectorField2d};
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// // A rigid rotation keeps every point on its circle.
+    /// let vortex = FnField::new(|p: Vector2d<f64>| Vector2d::new(-p.y, p.x));
+    /// let line = vortex.streamline(Vector2d::new(1.0, 0.0), 0.05, 200);
+    /// assert_eq!(line.len(), 201);
+    /// assert!(line.iter().all(|p| (p.magnitude() - 1.0).abs() < 1e-6));
+    /// ```
+    fn streamline(&self, start: Vector2d<T>, step: T, max_steps: usize) -> Vec<Vector2d<T>> {
+        let half = step * cast(0.5);
+        let sixth = step / cast(6.0);
+        let mut line = vec![start];
+        let mut point = start;
+        for _ in 0..max_steps {
+            let k1 = self.sample(point);
+            if k1.magnitude() <= T::epsilon() {
+                break;
+            }
+            let k2 = self.sample(point + k1 * half);
+            let k3 = self.sample(point + k2 * half);
+            let k4 = self.sample(point + k3 * step);
+            point += (k1 + (k2 + k3) * cast::<T>(2.0) + k4) * sixth;
+            line.push(point);
+        }
+        line
+    }
+}
+
+/// A vector field given by a closure.
+#[derive(Debug, Copy, Clone)]
+pub struct FnField<F> {
+    pub function: F,
+}
+
+impl<F> FnField<F> {
+    pub fn new(function: F) -> Self {
+        Self { function }
+    }
+}
+
+impl<T, F> VectorField2d<T> for FnField<F>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    F: Fn(Vector2d<T>) -> Vector2d<T>,
+{
+    fn sample(&self, point: Vector2d<T>) -> Vector2d<T> {
+        (self.function)(point)
+    }
+}
+
+/// The divergence free field obtained by taking the curl of a noise used as
+/// a stream function, scaled by `frequency` and `amplitude`. Particles
+/// advected through it swirl without collecting in sinks.
+#[derive(Debug, Clone)]
+pub struct CurlNoiseField<T, N>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    pub noise: N,
+    pub frequency: T,
+    pub amplitude: T,
+}
+
+impl<T, N> CurlNoiseField<T, N>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    N: Noise2d<T>,
+{
+    pub fn new(noise: N, frequency: T, amplitude: T) -> Self {
+        Self {
+            noise,
+            frequency,
+            amplitude,
+        }
+    }
+}
+
+impl<T, N> VectorField2d<T> for CurlNoiseField<T, N>
+where
+    T: Float + AddAssign + std::fmt::Display,
+    N: Noise2d<T>,
+{
+    fn sample(&self, point: Vector2d<T>) -> Vector2d<T> {
+        self.noise.curl(point * self.frequency) * self.amplitude
+    }
+}
+
+// -----------------------------------------
+//        Grid Field
+// -----------------------------------------
+
+/// A vector field stored at the centres of a regular grid and sampled with
+/// bilinear interpolation. Cell `(x, y)` covers the same area as in an
+/// `OccupancyGrid` with the same origin and cell size; points past the outer
+/// centres take the value of the nearest border.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridField<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    width: usize,
+    height: usize,
+    values: Vec<Vector2d<T>>,
+    pub origin: Vector2d<T>,
+    pub cell_size: T,
+}
+
+impl<T> GridField<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    /// Create a grid field with every value zero.
+    ///
+    /// # Returns
+    ///
+    /// The field, or an `Err` when it has no cells or `cell_size` is not
+    /// positive.
+    pub fn new(
+        width: usize,
+        height: usize,
+        origin: Vector2d<T>,
+        cell_size: T,
+    ) -> Result<Self, String> {
+        if width == 0 || height == 0 {
+            return Err("[::ERROR::] -> The field needs at least one cell.".to_string());
+        }
+        if cell_size <= T::zero() {
+            return Err("[::ERROR::] -> The cell size must be positive.".to_string());
+        }
+        Ok(Self {
+            width,
+            height,
+            values: vec![Vector2d::new(T::zero(), T::zero()); width * height],
+            origin,
+            cell_size,
+        })
+    }
+
+    /// Bakes `field` at the centre of every cell.
+    pub fn from_field<F>(
+        width: usize,
+        height: usize,
+        origin: Vector2d<T>,
+        cell_size: T,
+        field: &F,
+    ) -> Result<Self, String>
+    where
+        F: VectorField2d<T> + ?Sized,
+    {
+        let mut grid = Self::new(width, height, origin, cell_size)?;
+        for y in 0..height {
+            for x in 0..width {
+                let value = field.sample(grid.cell_center(x, y));
+                grid.set(x, y, value);
+            }
+        }
+        Ok(grid)
+    }
+
+    /// A crowd flow field: every cell points to the neighbour that is the
+    /// closest to the goal according to `distances`, a distance map laid out
+    /// like `OccupancyGrid::distance_map`. Diagonals are only taken when both
+    /// orthogonal cells are reachable; goal, blocked and unreachable cells get
+    /// a zero vector.
+    ///
+    /// # Returns
+    ///
+    /// The field, or an `Err` when the grid is invalid or `distances` does not
+    /// have one entry per cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vectorlib::math::pathfinding_module::OccupancyGrid;
+    /// use vectorlib::math::vector_field_module::{GridField, VectorField2d};
+    /// use vectorlib::math::vector2d_module::Vector2d;
+    ///
+    /// let grid = OccupancyGrid::from_rows(&["....", "##.#", "...."], Vector2d::new(0.0, 0.0), 1.0).unwrap();
+    /// let distances = grid.distance_map(&[Vector2d::new(0.5, 2.5)]);
+    /// let flow = GridField::from_distance_map(grid.width(), grid.height(), grid.origin, grid.cell_size, &distances).unwrap();
+    /// // The top left cell heads for the gap in the wall.
+    /// assert_eq!(flow.get(0, 0), Vector2d::new(1.0, 0.0));
+    /// ```
+    pub fn from_distance_map(
+        width: usize,
+        height: usize,
+        origin: Vector2d<T>,
+        cell_size: T,
+        distances: &[Option<T>],
+    ) -> Result<Self, String> {
+        let mut grid = Self::new(width, height, origin, cell_size)?;
+        if distances.len() != width * height {
+            return Err(format!(
+                "[::ERROR::] -> The distance map has {} entries instead of {}.",
+                distances.len(),
+                width * height
+            ));
+        }
+        let distance = |x: isize, y: isize| {
+            if x < 0 || y < 0 || x >= width as isize || y >= height as isize {
+                None
+            } else {
+                distances[y as usize * width + x as usize]
+            }
+        };
+        for y in 0..height as isize {
+            for x in 0..width as isize {
+                let Some(mut best) = distance(x, y) else {
+                    continue;
+                };
+                let mut direction = None;
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        if dx == 0 && dy == 0 {
+                            continue;
+                        }
+                        let Some(candidate) = distance(x + dx, y + dy) else {
+                            continue;
+                        };
+                        let diagonal_open = dx == 0
+                            || dy == 0
+                            || (distance(x + dx, y).is_some() && distance(x, y + dy).is_some());
+                        if diagonal_open && candidate < best {
+                            best = candidate;
+                            direction = Some((dx, dy));
+                        }
+                    }
+                }
+                if let Some((dx, dy)) = direction {
+                    let step = Vector2d::new(cast::<T>(dx as f64), cast(dy as f64));
+                    let value = step * (T::one() / step.magnitude());
+                    grid.set(x as usize, y as usize, value);
+                }
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Shortcut for `from_distance_map` over `grid.distance_map(goals)`.
+    ///
+    /// # Returns
+    ///
+    /// An `Err` when the grid's `cell_size` is not positive.
+    pub fn flow_field(grid: &OccupancyGrid<T>, goals: &[Vector2d<T>]) -> Result<Self, String> {
+        Self::from_distance_map(
+            grid.width(),
+            grid.height(),
+            grid.origin,
+            grid.cell_size,
+            &grid.distance_map(goals),
+        )
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The value stored for cell `(x, y)`; cells outside the grid are clamped
+    /// to the border.
+    pub fn get(&self, x: usize, y: usize) -> Vector2d<T> {
+        self.values[y.min(self.height - 1) * self.width + x.min(self.width - 1)]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: Vector2d<T>) {
+        if x < self.width && y < self.height {
+            self.values[y * self.width + x] = value;
+        }
+    }
+
+    /// The world position of the centre of a cell.
+    pub fn cell_center(&self, x: usize, y: usize) -> Vector2d<T> {
+        let half: T = cast(0.5);
+        self.origin
+            + Vector2d::new(
+                (T::from(x).unwrap() + half) * self.cell_size,
+                (T::from(y).unwrap() + half) * self.cell_size,
+            )
+    }
+}
+
+impl<T> VectorField2d<T> for GridField<T>
+where
+    T: Float + AddAssign + std::fmt::Display,
+{
+    fn sample(&self, point: Vector2d<T>) -> Vector2d<T> {
+        let half: T = cast(0.5);
+        let local = (point - self.origin) * (T::one() / self.cell_size) - half;
+        let max_x = T::from(self.width - 1).unwrap();
+        let max_y = T::from(self.height - 1).unwrap();
+        let (lx, ly) = (
+            local.x.max(T::zero()).min(max_x),
+            local.y.max(T::zero()).min(max_y),
+        );
+        let (x0, y0) = (lx.floor(), ly.floor());
+        let (tx, ty) = (lx - x0, ly - y0);
+        let (x0, y0) = (x0.to_usize().unwrap_or(0), y0.to_usize().unwrap_or(0));
+        let bottom = self.get(x0, y0) * (T::one() - tx) + self.get(x0 + 1, y0) * tx;
+        let top = self.get(x0, y0 + 1) * (T::one() - tx) + self.get(x0 + 1, y0 + 1) * tx;
+        bottom * (T::one() - ty) + top * ty
+    }
+}
@@ -0,0 +1,150 @@
+// -----------------------------------------
+//     Testing Dynamic AABB Tree Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use rand::{Rng, SeedableRng};
+use vectorlib::math::aabb_tree_module::{DynamicAabbTree, ProxyId};
+use vectorlib::math::shape_module::Aabb;
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn random_boxes(count: usize, seed: u64) -> Vec<Aabb<f64>> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+    (0..count)
+        .map(|_| {
+            let center = Vector2d::new(rng.gen_range(0.0..100.0), rng.gen_range(0.0..100.0));
+            let half = Vector2d::new(rng.gen_range(0.2..2.0), rng.gen_range(0.2..2.0));
+            Aabb::from_center(center, half)
+        })
+        .collect()
+}
+
+fn brute_pairs(tree: &DynamicAabbTree<f64, usize>, proxies: &[ProxyId]) -> Vec<(ProxyId, ProxyId)> {
+    let mut pairs = Vec::new();
+    for (i, a) in proxies.iter().enumerate() {
+        for b in &proxies[i + 1..] {
+            if tree
+                .fat_aabb(*a)
+                .unwrap()
+                .overlaps(&tree.fat_aabb(*b).unwrap())
+            {
+                pairs.push((*a.min(b), *a.max(b)));
+            }
+        }
+    }
+    pairs.sort();
+    pairs
+}
+
+#[test]
+fn sorted_insertion_stays_balanced() {
+    let mut tree: DynamicAabbTree<f64, usize> = DynamicAabbTree::new(0.1);
+    // Inserting along a line is the worst case for an unbalanced tree.
+    for i in 0..1024 {
+        let min = Vector2d::new(i as f64, 0.0);
+        tree.create_proxy(Aabb::new(min, min + Vector2d::new(0.5, 0.5)), i);
+    }
+    assert_eq!(tree.len(), 1024);
+    assert!(tree.height() <= 20, "height {}", tree.height());
+}
+
+#[test]
+fn overlap_pairs_match_brute_force_after_moves() {
+    let boxes = random_boxes(300, 4);
+    let mut tree: DynamicAabbTree<f64, usize> = DynamicAabbTree::new(0.2);
+    let mut proxies: Vec<ProxyId> = boxes
+        .iter()
+        .enumerate()
+        .map(|(i, b)| tree.create_proxy(*b, i))
+        .collect();
+    let mut initial = Vec::new();
+    tree.update_pairs(|a, b| initial.push((a, b)));
+    initial.sort();
+    assert_eq!(initial, brute_pairs(&tree, &proxies));
+
+    // Move a third of the boxes, destroy a few and check the incremental
+    // pairs are exactly the overlaps involving reinserted proxies.
+    let mut rng = rand::rngs::StdRng::seed_from_u64(8);
+    for proxy in proxies.iter().step_by(3) {
+        let shift = Vector2d::new(rng.gen_range(-5.0..5.0), rng.gen_range(-5.0..5.0));
+        let index = *tree.user_data(*proxy).unwrap();
+        let moved = Aabb::new(boxes[index].min + shift, boxes[index].max + shift);
+        tree.move_proxy(*proxy, moved, shift);
+        assert!(tree.fat_aabb(*proxy).unwrap().contains_aabb(&moved));
+    }
+    for proxy in proxies.drain(..10) {
+        assert!(tree.destroy_proxy(proxy).is_some());
+        assert!(tree.destroy_proxy(proxy).is_none());
+    }
+    let mut all = tree.overlap_pairs();
+    all.sort();
+    assert_eq!(all, brute_pairs(&tree, &proxies));
+
+    let mut incremental = Vec::new();
+    tree.update_pairs(|a, b| incremental.push((a, b)));
+    let mut deduped = incremental.clone();
+    deduped.sort();
+    deduped.dedup();
+    assert_eq!(deduped.len(), incremental.len());
+    assert!(deduped.iter().all(|pair| all.contains(pair)));
+}
+
+#[test]
+fn small_moves_keep_the_fat_box() {
+    let mut tree: DynamicAabbTree<f64, ()> = DynamicAabbTree::new(0.5);
+    let unit = Aabb::new(Vector2d::new(0.0, 0.0), Vector2d::new(1.0, 1.0));
+    let proxy = tree.create_proxy(unit, ());
+    tree.update_pairs(|_, _| {});
+    let nudged = Aabb::new(Vector2d::new(0.2, 0.0), Vector2d::new(1.2, 1.0));
+    assert!(!tree.move_proxy(proxy, nudged, Vector2d::new(0.2, 0.0)));
+    let far = Aabb::new(Vector2d::new(5.0, 0.0), Vector2d::new(6.0, 1.0));
+    assert!(tree.move_proxy(proxy, far, Vector2d::new(1.0, 0.0)));
+    let fat = tree.fat_aabb(proxy).unwrap();
+    assert_approx_eq!(fat.min.x, 4.5, 1e-12);
+    assert_approx_eq!(fat.max.x, 8.5, 1e-12);
+}
+
+#[test]
+fn queries_and_clipped_ray_casts() {
+    let boxes = random_boxes(200, 12);
+    let mut tree: DynamicAabbTree<f64, usize> = DynamicAabbTree::new(0.0);
+    for (i, b) in boxes.iter().enumerate() {
+        tree.create_proxy(*b, i);
+    }
+    let area = Aabb::new(Vector2d::new(20.0, 20.0), Vector2d::new(40.0, 35.0));
+    let mut found: Vec<usize> = tree
+        .query_aabb(&area)
+        .into_iter()
+        .map(|p| *tree.user_data(p).unwrap())
+        .collect();
+    found.sort();
+    let expected: Vec<usize> = (0..boxes.len())
+        .filter(|&i| boxes[i].overlaps(&area))
+        .collect();
+    assert_eq!(found, expected);
+
+    // Clip the ray at every box entry; the survivor is the first box hit.
+    let (origin, translation) = (Vector2d::new(0.0, 50.0), Vector2d::new(100.0, 0.0));
+    let entry = |b: &Aabb<f64>| {
+        if b.min.y <= 50.0 && b.max.y >= 50.0 {
+            Some((b.min.x / 100.0).max(0.0))
+        } else {
+            None
+        }
+    };
+    let mut closest: Option<(usize, f64)> = None;
+    tree.ray_cast(origin, translation, |proxy, max_fraction| {
+        let index = *tree.user_data(proxy).unwrap();
+        match entry(&boxes[index]) {
+            Some(t) if t < max_fraction => {
+                closest = Some((index, t));
+                t
+            }
+            _ => -1.0,
+        }
+    });
+    let expected = (0..boxes.len())
+        .filter_map(|i| entry(&boxes[i]).map(|t| (i, t)))
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    assert_eq!(closest, expected);
+}
@@ -0,0 +1,188 @@
+// -----------------------------------------
+//     Testing Binary Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use vectorlib::math::binary_module::{
+    crc32, decode, encode, ElementType, EncodeOptions, Endianness, VectorReader, VectorWriter,
+};
+use vectorlib::math::shape_module::Aabb;
+use vectorlib::math::vector2d_module::Vector2d;
+
+// A smooth animation curve, like the samples of a baked position track.
+fn track(count: usize, dt: f64) -> Vec<Vector2d<f64>> {
+    (0..count)
+        .map(|i| {
+            let t = i as f64 * dt;
+            Vector2d::new(40.0 * t.cos() + 3.0 * t, 25.0 * (1.3 * t).sin())
+        })
+        .collect()
+}
+
+#[test]
+fn lossless_modes_round_trip_exactly() {
+    let points = track(500, 0.01);
+    let mut sizes = Vec::new();
+    for endianness in [Endianness::Little, Endianness::Big] {
+        for delta in [false, true] {
+            let options = EncodeOptions {
+                endianness,
+                delta,
+                ..Default::default()
+            };
+            let bytes = encode(&points, &options).unwrap();
+            assert_eq!(decode::<f64>(&bytes).unwrap(), points);
+            sizes.push(bytes.len());
+        }
+    }
+    // 4 + 4 + 8 header bytes, 16 bytes per vector and the checksum.
+    assert_eq!(sizes[0], 16 + 500 * 16 + 4);
+    assert_eq!(sizes[0], sizes[2]);
+
+    let singles: Vec<Vector2d<f32>> = points
+        .iter()
+        .map(|p| Vector2d::new(p.x as f32, p.y as f32))
+        .collect();
+    let bytes = encode(&singles, &EncodeOptions::default()).unwrap();
+    assert_eq!(bytes.len(), 16 + 500 * 8 + 4);
+    assert_eq!(decode::<f32>(&bytes).unwrap(), singles);
+    // An f32 buffer widens losslessly into f64.
+    let widened = decode::<f64>(&bytes).unwrap();
+    assert_eq!(widened[7].x, singles[7].x as f64);
+    assert_eq!(
+        VectorReader::<_, f64>::new(bytes.as_slice())
+            .unwrap()
+            .header()
+            .element_type,
+        ElementType::F32
+    );
+    assert!(encode::<f64>(&[], &EncodeOptions::default()).is_ok());
+}
+
+#[test]
+fn quantization_bounds_the_error_and_delta_shrinks_it() {
+    let points = track(2_000, 0.001);
+    let area = Aabb::new(Vector2d::new(-50.0, -30.0), Vector2d::new(100.0, 30.0));
+    let quantized = EncodeOptions {
+        quantize: Some(area),
+        ..Default::default()
+    };
+    let bytes = encode(&points, &quantized).unwrap();
+    assert_eq!(bytes.len(), 16 + 32 + 2_000 * 4 + 4);
+    let decoded = decode::<f64>(&bytes).unwrap();
+    let step = Vector2d::new(150.0 / 65535.0, 60.0 / 65535.0);
+    for (a, b) in points.iter().zip(&decoded) {
+        assert!((a.x - b.x).abs() <= step.x / 2.0 + 1e-12);
+        assert!((a.y - b.y).abs() <= step.y / 2.0 + 1e-12);
+    }
+
+    let packed = encode(
+        &points,
+        &EncodeOptions {
+            delta: true,
+            ..quantized
+        },
+    )
+    .unwrap();
+    assert_eq!(decode::<f64>(&packed).unwrap(), decoded);
+    // Neighbouring samples differ by a few steps: one byte per component.
+    assert!(packed.len() < bytes.len() * 2 / 3);
+
+    let outside = [Vector2d::new(0.0, 0.0), Vector2d::new(101.0, 0.0)];
+    assert!(encode(&outside, &quantized).is_err());
+    let flat = EncodeOptions {
+        quantize: Some(Aabb::new(Vector2d::new(0.0, 0.0), Vector2d::new(1.0, 0.0))),
+        ..Default::default()
+    };
+    assert!(encode(&outside, &flat).is_err());
+}
+
+#[test]
+fn streaming_reader_and_writer() {
+    let points = track(64, 0.01);
+    let mut writer = VectorWriter::new(
+        Vec::new(),
+        64,
+        &EncodeOptions {
+            delta: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    for p in &points {
+        writer.write(*p).unwrap();
+    }
+    assert!(writer.write(points[0]).is_err());
+    let bytes = writer.finish().unwrap();
+
+    let mut reader = VectorReader::<_, f64>::new(std::io::Cursor::new(&bytes)).unwrap();
+    assert_eq!(reader.header().count, 64);
+    assert!(reader.header().delta);
+    assert_eq!(reader.next().unwrap().unwrap(), points[0]);
+    let rest: Result<Vec<_>, _> = reader.by_ref().collect();
+    assert_eq!(rest.unwrap(), points[1..]);
+    assert!(reader.next().is_none());
+
+    let short = VectorWriter::<_, f64>::new(Vec::new(), 2, &EncodeOptions::default()).unwrap();
+    assert!(short.finish().is_err());
+}
+
+#[test]
+fn rejected_write_leaves_delta_state_intact() {
+    let options = EncodeOptions {
+        quantize: Some(Aabb::new(
+            Vector2d::new(0.0, 0.0),
+            Vector2d::new(10.0, 10.0),
+        )),
+        delta: true,
+        ..Default::default()
+    };
+    let mut writer = VectorWriter::new(Vec::new(), 2, &options).unwrap();
+    writer.write(Vector2d::new(1.0, 1.0)).unwrap();
+    // x is in range but y is not, so nothing may change.
+    assert!(writer.write(Vector2d::new(9.0, 50.0)).is_err());
+    writer.write(Vector2d::new(2.0, 2.0)).unwrap();
+    let bytes = writer.finish().unwrap();
+
+    let decoded: Vec<Vector2d<f64>> = decode(&bytes).unwrap();
+    assert_eq!(decoded.len(), 2);
+    for (decoded, expected) in decoded.iter().zip([(1.0, 1.0), (2.0, 2.0)]) {
+        assert_approx_eq!(decoded.x, expected.0, 1e-3);
+        assert_approx_eq!(decoded.y, expected.1, 1e-3);
+    }
+}
+
+#[test]
+fn corruption_is_detected() {
+    assert_eq!(crc32(b""), 0);
+    assert_eq!(
+        crc32(b"The quick brown fox jumps over the lazy dog"),
+        0x414f_a339
+    );
+
+    let points = track(100, 0.01);
+    let mut rng = StdRng::seed_from_u64(4);
+    for delta in [false, true] {
+        let bytes = encode(
+            &points,
+            &EncodeOptions {
+                delta,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        for _ in 0..50 {
+            let mut corrupted = bytes.clone();
+            let index = rng.gen_range(0..corrupted.len());
+            corrupted[index] ^= 1 << rng.gen_range(0..8);
+            assert!(decode::<f64>(&corrupted).is_err());
+        }
+        assert!(decode::<f64>(&bytes[..bytes.len() - 1]).is_err());
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(decode::<f64>(&trailing).is_err());
+    }
+    assert!(decode::<f64>(b"PNG\x89 not a buffer").is_err());
+}
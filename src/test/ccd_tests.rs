@@ -0,0 +1,115 @@
+// -----------------------------------------
+//   Testing Continuous Collision Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use vectorlib::math::ccd_module::{
+    circle_aabb_toi, circle_polygon_toi, circle_segment_toi, conservative_advancement, swept_aabb,
+};
+use vectorlib::math::shape_module::{Aabb, Circle, ConvexPolygon};
+use vectorlib::math::vector2d_module::Vector2d;
+
+#[test]
+fn fast_circle_does_not_tunnel() {
+    // A thin wall that a discrete step of dt = 1/60 would jump over.
+    let wall_a = Vector2d::new(10.0, -1.0);
+    let wall_b = Vector2d::new(10.0, 1.0);
+    let bullet: Circle<f64> = Circle::new(Vector2d::new(0.0, 0.0), 0.05);
+    let velocity = Vector2d::new(1200.0, 0.0);
+    let dt = 1.0 / 60.0;
+    let hit = circle_segment_toi(&bullet, velocity, wall_a, wall_b, dt).unwrap();
+    assert_approx_eq!(hit.time, 9.95 / 1200.0, 1e-12);
+    assert_vec2_equal!(hit.normal, Vector2d::new(-1.0, 0.0));
+
+    // Missing the wall, and hitting it too late.
+    assert!(
+        circle_segment_toi(&bullet, Vector2d::new(1200.0, 300.0), wall_a, wall_b, dt).is_none()
+    );
+    assert!(circle_segment_toi(&bullet, velocity, wall_a, wall_b, 0.001).is_none());
+}
+
+#[test]
+fn circle_hits_segment_end_point() {
+    let ball: Circle<f64> = Circle::new(Vector2d::new(-2.0, 1.5), 1.0);
+    let hit = circle_segment_toi(
+        &ball,
+        Vector2d::new(4.0, 0.0),
+        Vector2d::new(0.0, 0.0),
+        Vector2d::new(0.0, 1.0),
+        1.0,
+    )
+    .unwrap();
+    // The center reaches (x, 1.5) at distance 1 from (0, 1).
+    let expected_x = -(1.0_f64 - 0.25).sqrt();
+    assert_approx_eq!(hit.time, (expected_x + 2.0) / 4.0, 1e-12);
+    assert_vec2_equal!(hit.normal, Vector2d::new(expected_x, 0.5));
+
+    let overlapping = circle_segment_toi(
+        &ball,
+        Vector2d::new(0.0, 0.0),
+        Vector2d::new(-2.0, 0.0),
+        Vector2d::new(-2.0, 1.0),
+        1.0,
+    )
+    .unwrap();
+    assert_eq!(overlapping.time, 0.0);
+}
+
+#[test]
+fn circle_against_polygons_and_boxes() {
+    let ball: Circle<f64> = Circle::new(Vector2d::new(0.0, 0.0), 0.5);
+    let triangle = vec![
+        Vector2d::new(3.0, -1.0),
+        Vector2d::new(5.0, 0.0),
+        Vector2d::new(3.0, 1.0),
+    ];
+    let hit = circle_polygon_toi(&ball, Vector2d::new(10.0, 0.0), &triangle, 1.0).unwrap();
+    assert_approx_eq!(hit.time, 0.25, 1e-12);
+
+    let block = Aabb::new(Vector2d::new(-1.0, 4.0), Vector2d::new(1.0, 5.0));
+    let hit = circle_aabb_toi(&ball, Vector2d::new(0.0, 7.0), &block, 1.0).unwrap();
+    assert_approx_eq!(hit.time, 0.5, 1e-12);
+    assert_vec2_equal!(hit.normal, Vector2d::new(0.0, -1.0));
+    assert!(circle_aabb_toi(&ball, Vector2d::new(0.0, -7.0), &block, 1.0).is_none());
+}
+
+#[test]
+fn swept_boxes() {
+    let a: Aabb<f64> = Aabb::new(Vector2d::new(0.0, 0.0), Vector2d::new(1.0, 1.0));
+    let b = Aabb::new(Vector2d::new(0.0, 5.0), Vector2d::new(1.0, 6.0));
+    let up = Vector2d::new(0.0, 4.0);
+    let down = Vector2d::new(0.0, -4.0);
+    let hit = swept_aabb(&a, up, &b, down, 1.0).unwrap();
+    assert_approx_eq!(hit.time, 0.5, 1e-12);
+    assert_vec2_equal!(hit.normal, Vector2d::new(0.0, -1.0));
+
+    let far = Aabb::new(Vector2d::new(3.0, 5.0), Vector2d::new(4.0, 6.0));
+    assert!(swept_aabb(&a, up, &far, down, 1.0).is_none());
+    let inside = Aabb::new(Vector2d::new(0.5, 0.5), Vector2d::new(2.0, 2.0));
+    assert_eq!(swept_aabb(&a, up, &inside, down, 1.0).unwrap().time, 0.0);
+}
+
+#[test]
+fn conservative_advancement_between_polygons() {
+    let a: ConvexPolygon<f64> = ConvexPolygon::new(vec![
+        Vector2d::new(0.0, 0.0),
+        Vector2d::new(1.0, 0.0),
+        Vector2d::new(0.5, 1.0),
+    ])
+    .unwrap();
+    let b = ConvexPolygon::rectangle(Vector2d::new(6.0, 0.5), Vector2d::new(0.5, 2.0));
+    let hit = conservative_advancement(
+        &a,
+        Vector2d::new(3.0, 0.0),
+        &b,
+        Vector2d::new(-1.5, 0.0),
+        2.0,
+        1e-7,
+    )
+    .unwrap();
+    assert_approx_eq!(hit.time, 4.5 / 4.5, 1e-6);
+    assert_vec2_equal!(hit.normal, Vector2d::new(-1.0, 0.0));
+
+    let zero = Vector2d::new(0.0, 0.0);
+    assert!(conservative_advancement(&a, Vector2d::new(0.0, 3.0), &b, zero, 2.0, 1e-7).is_none());
+}
@@ -0,0 +1,91 @@
+// -----------------------------------------
+//       Testing GJK / EPA Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use vectorlib::math::gjk_module::{epa_penetration, gjk_distance, gjk_intersects, SupportMap};
+use vectorlib::math::sat_collision_module::sat_overlap;
+use vectorlib::math::shape_module::{Capsule, Circle, ConvexPolygon};
+use vectorlib::math::vector2d_module::Vector2d;
+
+#[test]
+fn gjk_distance_between_shapes() {
+    let a = ConvexPolygon::rectangle(Vector2d::new(0.0, 0.0), Vector2d::new(1.0, 1.0));
+    let b = ConvexPolygon::rectangle(Vector2d::new(4.0, 3.0), Vector2d::new(1.0, 1.0));
+    let closest = gjk_distance(&a, &b).unwrap();
+    assert_approx_eq!(closest.distance, 5.0_f64.sqrt(), 1e-9);
+    assert_vec2_equal!(closest.point_a, Vector2d::new(1.0, 1.0));
+    assert_vec2_equal!(closest.point_b, Vector2d::new(3.0, 2.0));
+
+    let capsule: Capsule<f64> =
+        Capsule::new(Vector2d::new(0.0, -2.0), Vector2d::new(0.0, 2.0), 0.5);
+    let circle = Circle::new(Vector2d::new(3.0, 1.0), 1.0);
+    let closest = gjk_distance(&capsule, &circle).unwrap();
+    assert_approx_eq!(closest.distance, 1.5, 1e-4);
+    assert!(gjk_intersects(&capsule, &Vector2d::new(0.4, 2.2)));
+    assert!(!gjk_intersects(&capsule, &Vector2d::new(0.4, 2.4)));
+}
+
+#[test]
+fn epa_matches_sat_for_polygons() {
+    let a: ConvexPolygon<f64> = ConvexPolygon::new(vec![
+        Vector2d::new(0.0, 0.0),
+        Vector2d::new(3.0, 0.5),
+        Vector2d::new(2.0, 2.5),
+        Vector2d::new(-0.5, 1.5),
+    ])
+    .unwrap();
+    for offset in [
+        Vector2d::new(1.0, 1.0),
+        Vector2d::new(2.0, -0.5),
+        Vector2d::new(-0.3, 0.7),
+    ] {
+        let b_vertices: Vec<_> = [
+            Vector2d::new(0.0, 0.0),
+            Vector2d::new(1.5, 0.0),
+            Vector2d::new(0.5, 1.2),
+        ]
+        .iter()
+        .map(|&v| v + offset)
+        .collect();
+        let b = ConvexPolygon::new(b_vertices.clone()).unwrap();
+        let mtv = sat_overlap(a.vertices(), &b_vertices).unwrap();
+        let penetration = epa_penetration(&a, &b).unwrap();
+        assert_approx_eq!(penetration.depth, mtv.depth, 1e-6);
+        assert_vec2_equal!(penetration.normal, mtv.axis);
+    }
+}
+
+#[test]
+fn epa_for_round_shapes() {
+    let a: Circle<f64> = Circle::new(Vector2d::new(0.0, 0.0), 1.0);
+    let b = Circle::new(Vector2d::new(1.5, 0.0), 1.0);
+    let penetration = epa_penetration(&a, &b).unwrap();
+    assert_approx_eq!(penetration.depth, 0.5, 1e-3);
+    assert_vec2_equal!(penetration.normal, Vector2d::new(1.0, 0.0));
+    assert!(epa_penetration(&a, &Circle::new(Vector2d::new(2.5, 0.0), 0.4)).is_none());
+}
+
+/// A shape only known through its support function.
+struct Ellipse {
+    radii: Vector2d<f64>,
+}
+
+impl SupportMap<f64> for Ellipse {
+    fn support(&self, direction: Vector2d<f64>) -> Vector2d<f64> {
+        let scaled = direction * self.radii;
+        let n = scaled.magnitude();
+        Vector2d::new(scaled.x * self.radii.x / n, scaled.y * self.radii.y / n)
+    }
+}
+
+#[test]
+fn custom_support_maps() {
+    let ellipse = Ellipse {
+        radii: Vector2d::new(3.0, 1.0),
+    };
+    assert!(gjk_intersects(&ellipse, &Vector2d::new(2.9, 0.0)));
+    assert!(!gjk_intersects(&ellipse, &Vector2d::new(0.0, 1.1)));
+    let closest = gjk_distance(&ellipse, &Vector2d::new(0.0, 4.0)).unwrap();
+    assert_approx_eq!(closest.distance, 3.0, 1e-4);
+}
@@ -0,0 +1,93 @@
+// -----------------------------------------
+//     Testing K-d Tree Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use rand::{Rng, SeedableRng};
+use vectorlib::math::kdtree_module::KdTree2d;
+use vectorlib::math::shape_module::Aabb;
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn random_points(count: usize, seed: u64) -> Vec<Vector2d<f64>> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+    (0..count)
+        .map(|_| Vector2d::new(rng.gen_range(0.0..100.0), rng.gen_range(0.0..100.0)))
+        .collect()
+}
+
+fn brute_force(points: &[Vector2d<f64>], query: Vector2d<f64>) -> Vec<(usize, f64)> {
+    let mut all: Vec<(usize, f64)> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i, (p.x - query.x).powi(2) + (p.y - query.y).powi(2)))
+        .collect();
+    all.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    all
+}
+
+#[test]
+fn nearest_and_k_nearest_match_brute_force() {
+    let points = random_points(2000, 1);
+    let tree = KdTree2d::new(&points);
+    assert_eq!(tree.len(), 2000);
+    let mut found = Vec::new();
+    for query in random_points(50, 2) {
+        let expected = brute_force(&points, query);
+        assert_eq!(tree.nearest(query), Some(expected[0]));
+        tree.k_nearest_into(query, 8, &mut found);
+        assert_eq!(found, expected[..8].to_vec());
+    }
+}
+
+#[test]
+fn radius_and_bbox_queries_match_brute_force() {
+    let points = random_points(1500, 3);
+    let tree = KdTree2d::new(&points);
+    let center = Vector2d::new(40.0, 60.0);
+    let mut within = tree.within_radius(center, 12.0);
+    within.sort_by_key(|&(i, _)| i);
+    let mut expected: Vec<(usize, f64)> = brute_force(&points, center)
+        .into_iter()
+        .filter(|&(_, d)| d <= 144.0)
+        .collect();
+    expected.sort_by_key(|&(i, _)| i);
+    assert_eq!(within, expected);
+
+    let area = Aabb::new(Vector2d::new(10.0, 20.0), Vector2d::new(35.0, 30.0));
+    let mut inside = tree.in_bbox(&area);
+    inside.sort();
+    let expected: Vec<usize> = (0..points.len())
+        .filter(|&i| area.contains_point(points[i]))
+        .collect();
+    assert_eq!(inside, expected);
+}
+
+#[test]
+fn duplicates_and_degenerate_inputs() {
+    let empty: KdTree2d<f64> = KdTree2d::new(&[]);
+    assert!(empty.is_empty());
+    assert_eq!(empty.nearest(Vector2d::new(0.0, 0.0)), None);
+    assert!(empty.k_nearest(Vector2d::new(0.0, 0.0), 3).is_empty());
+
+    let points = vec![Vector2d::new(1.0, 1.0); 5];
+    let tree = KdTree2d::new(&points);
+    let mut all = tree.within_radius(Vector2d::new(1.0, 1.0), 0.0);
+    all.sort_by_key(|&(i, _)| i);
+    assert_eq!(all, (0..5).map(|i| (i, 0.0)).collect::<Vec<_>>());
+    assert_eq!(tree.k_nearest(Vector2d::new(0.0, 0.0), 10).len(), 5);
+    assert_eq!(tree.k_nearest(Vector2d::new(0.0, 0.0), 0), vec![]);
+}
+
+#[test]
+fn oversized_k_is_clamped_to_point_count() {
+    let points = random_points(20, 5);
+    let tree = KdTree2d::new(&points);
+    let query = Vector2d::new(0.5, 0.5);
+    let found = tree.k_nearest(query, usize::MAX);
+    assert_eq!(found, brute_force(&points, query));
+
+    let mut buffer = Vec::new();
+    tree.k_nearest_into(query, usize::MAX, &mut buffer);
+    assert_eq!(buffer, found);
+    assert!(buffer.capacity() <= points.len() + 1);
+}
@@ -1,3 +1,60 @@
+macro_rules! assert_vec2_equal {
+    ($expected:expr, $actual:expr) => {
+        let tolerance = 0.0001;
+        assert_approx_eq!($expected.x, $actual.x, tolerance);
+        assert_approx_eq!($expected.y, $actual.y, tolerance);
+    };
+}
+
 #[cfg(test)]
 mod tests;
-
+#[cfg(test)]
+mod polygon_offset_tests;
+#[cfg(test)]
+mod polyline_tests;
+#[cfg(test)]
+mod sat_collision_tests;
+#[cfg(test)]
+mod gjk_tests;
+#[cfg(test)]
+mod ccd_tests;
+#[cfg(test)]
+mod physics_world_tests;
+#[cfg(test)]
+mod particle_constraints_tests;
+#[cfg(test)]
+mod spatial_hash_tests;
+#[cfg(test)]
+mod quadtree_tests;
+#[cfg(test)]
+mod kdtree_tests;
+#[cfg(test)]
+mod aabb_tree_tests;
+#[cfg(test)]
+mod steering_tests;
+#[cfg(test)]
+mod pathfinding_tests;
+#[cfg(test)]
+mod random_vector_tests;
+#[cfg(test)]
+mod sampling_tests;
+#[cfg(test)]
+mod noise_tests;
+#[cfg(test)]
+mod vector_field_tests;
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests;
+#[cfg(test)]
+mod binary_tests;
+#[cfg(test)]
+mod svg_path_tests;
+#[cfg(test)]
+mod svg_debug_tests;
+#[cfg(test)]
+mod raster_tests;
+#[cfg(test)]
+mod vector2d_parse_tests;
+#[cfg(test)]
+mod vector2d_format_tests;
+#[cfg(test)]
+mod verbose_log_tests;
@@ -0,0 +1,145 @@
+// -----------------------------------------
+//     Testing Noise Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use vectorlib::math::noise_module::{
+    DomainWarp, Fractal, Noise2d, OpenSimplex, Perlin, Worley, WorleyDistance, WorleyFeature,
+};
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn probes(seed: u64, count: usize) -> Vec<Vector2d<f64>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..count)
+        .map(|_| Vector2d::new(rng.gen_range(-50.0..50.0), rng.gen_range(-50.0..50.0)))
+        .collect()
+}
+
+#[test]
+fn gradient_noises_are_seeded_bounded_and_continuous() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let perlin = Perlin::new(&mut rng);
+    let simplex = OpenSimplex::new(&mut rng);
+    let other = Perlin::new(&mut StdRng::seed_from_u64(6));
+    let noises: [&dyn Noise2d<f64>; 2] = [&perlin, &simplex];
+    let mut differs = false;
+    for p in probes(1, 2_000) {
+        for noise in noises {
+            let value = noise.sample(p);
+            assert!(value.abs() <= 1.0);
+            let nudge = noise.sample(p + Vector2d::new(1e-4, -1e-4));
+            assert!((value - nudge).abs() < 1e-2);
+        }
+        differs |= perlin.sample(p) != Noise2d::<f64>::sample(&other, p);
+    }
+    assert!(differs);
+    assert_eq!(
+        Noise2d::<f64>::sample(&perlin, Vector2d::new(-7.0, 12.0)),
+        0.0
+    );
+
+    let clone = Perlin::new(&mut StdRng::seed_from_u64(5));
+    let p = Vector2d::new(0.3f32, 0.9);
+    assert_eq!(perlin.sample(p), clone.sample(p));
+}
+
+#[test]
+fn gradient_matches_finite_differences_and_curl_is_rotated() {
+    let perlin = Perlin::new(&mut StdRng::seed_from_u64(2));
+    for p in probes(3, 200) {
+        let gradient = perlin.gradient(p);
+        let h = 1e-6;
+        let dx = (perlin.sample(p + Vector2d::new(h, 0.0))
+            - perlin.sample(p - Vector2d::new(h, 0.0)))
+            / (2.0 * h);
+        assert_approx_eq!(gradient.x, dx, 1e-4);
+        let curl = perlin.curl(p);
+        assert_approx_eq!(curl.dot_product(gradient), 0.0, 1e-9);
+        assert_approx_eq!(curl.x, gradient.y, 1e-12);
+    }
+}
+
+#[test]
+fn worley_features_match_brute_force_ordering() {
+    let mut worley = Worley::new(&mut StdRng::seed_from_u64(9));
+    for p in probes(4, 500) {
+        worley.feature = WorleyFeature::F1;
+        let f1: f64 = worley.sample(p);
+        worley.feature = WorleyFeature::F2;
+        let f2: f64 = worley.sample(p);
+        worley.feature = WorleyFeature::F2MinusF1;
+        let border: f64 = worley.sample(p);
+        assert!(0.0 <= f1 && f1 <= f2);
+        // A feature point lies in the cell of `p`, so F1 is at most the cell diagonal.
+        assert!(f1 <= 2f64.sqrt());
+        assert_approx_eq!(border, f2 - f1, 1e-12);
+    }
+    worley.feature = WorleyFeature::F1;
+    let p = Vector2d::new(3.3, -1.2);
+    let euclidean: f64 = worley.sample(p);
+    worley.distance = WorleyDistance::Manhattan;
+    let manhattan: f64 = worley.sample(p);
+    worley.distance = WorleyDistance::Chebyshev;
+    let chebyshev: f64 = worley.sample(p);
+    assert!(chebyshev <= euclidean && euclidean <= manhattan);
+}
+
+#[test]
+fn far_away_samples_stay_finite() {
+    let mut rng = StdRng::seed_from_u64(12);
+    let perlin = Perlin::new(&mut rng);
+    let simplex = OpenSimplex::new(&mut rng);
+    let worley = Worley::new(&mut rng);
+    for coordinate in [9.3e18, -9.3e18, 1.0e300, -1.0e300, f64::MAX, f64::MIN] {
+        let p = Vector2d::new(coordinate, -coordinate);
+        let values: [f64; 3] = [perlin.sample(p), simplex.sample(p), worley.sample(p)];
+        assert!(
+            values.iter().all(|v| v.is_finite()),
+            "{coordinate}: {values:?}"
+        );
+    }
+    // Lattice hashing repeats every 256 cells.
+    let (near, far) = (
+        Vector2d::new(1.25, -3.5),
+        Vector2d::new(1.25 + 512.0, -3.5 - 256.0),
+    );
+    assert_approx_eq!(
+        Noise2d::<f64>::sample(&perlin, near),
+        Noise2d::<f64>::sample(&perlin, far),
+        1e-12
+    );
+    assert_approx_eq!(
+        Noise2d::<f64>::sample(&worley, near),
+        Noise2d::<f64>::sample(&worley, far),
+        1e-12
+    );
+}
+
+#[test]
+fn fractal_sums_and_domain_warp() {
+    let mut rng = StdRng::seed_from_u64(8);
+    let perlin = Perlin::new(&mut rng);
+    let single = Fractal::fbm(&perlin, 1);
+    let fbm = Fractal::fbm(&perlin, 6);
+    let turbulence = Fractal::turbulence(&perlin, 6);
+    let warp = DomainWarp::new(&perlin, OpenSimplex::new(&mut rng), 0.0);
+    let mut roughness = (0.0, 0.0);
+    for p in probes(2, 1_000) {
+        assert_eq!(single.sample(p), perlin.sample(p));
+        assert!(fbm.sample(p).abs() <= 1.0);
+        let t = turbulence.sample(p);
+        assert!((0.0..=1.0).contains(&t));
+        assert_eq!(warp.sample(p), perlin.sample(p));
+        let step = Vector2d::new(0.01, 0.0);
+        roughness.0 += (single.sample(p + step) - single.sample(p)).abs();
+        roughness.1 += (fbm.sample(p + step) - fbm.sample(p)).abs();
+    }
+    // Higher octaves add small scale detail.
+    assert!(roughness.1 > roughness.0);
+
+    let warped = DomainWarp::new(&perlin, OpenSimplex::new(&mut rng), 3.0);
+    let p = Vector2d::new(1.7, 2.2);
+    assert_eq!(warped.sample(p), perlin.sample(p + warped.displacement(p)));
+}
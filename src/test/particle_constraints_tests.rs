@@ -0,0 +1,133 @@
+// -----------------------------------------
+//     Testing Particle Constraints Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use vectorlib::math::particle_constraints_module::{Constraint, Particle, ParticleSystem};
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn run(system: &mut ParticleSystem<f64>, steps: usize) {
+    for _ in 0..steps {
+        system.step(1.0 / 60.0);
+    }
+}
+
+#[test]
+fn rigid_chain_keeps_its_length() {
+    let mut system: ParticleSystem<f64> = ParticleSystem::new(Vector2d::new(0.0, -9.81));
+    system.iterations = 20;
+    let anchor = system.add_particle(Particle::fixed(Vector2d::new(0.0, 0.0)));
+    let mut previous = anchor;
+    for i in 1..=5 {
+        let next = system.add_particle(Particle::new(Vector2d::new(i as f64 * 0.5, 0.0), 1.0));
+        system
+            .add_constraint(Constraint::Distance {
+                a: previous,
+                b: next,
+                rest_length: 0.5,
+                stiffness: 1.0,
+            })
+            .unwrap();
+        previous = next;
+    }
+    run(&mut system, 180);
+    let particles = system.particles();
+    assert_eq!(particles[anchor].position, Vector2d::new(0.0, 0.0));
+    for pair in particles.windows(2) {
+        assert_approx_eq!(pair[0].position.distance(pair[1].position), 0.5, 0.02);
+    }
+    assert!(particles[previous].position.y < -1.0);
+}
+
+#[test]
+fn soft_spring_stretches_more_than_rigid_link() {
+    let stretch = |stiffness: f64| {
+        let mut system: ParticleSystem<f64> = ParticleSystem::new(Vector2d::new(0.0, -9.81));
+        let anchor = system.add_particle(Particle::fixed(Vector2d::new(0.0, 0.0)));
+        let weight = system.add_particle(Particle::new(Vector2d::new(0.0, -1.0), 1.0));
+        system.damping = 0.98;
+        system
+            .add_constraint(Constraint::Distance {
+                a: anchor,
+                b: weight,
+                rest_length: 1.0,
+                stiffness,
+            })
+            .unwrap();
+        run(&mut system, 120);
+        system.particles()[weight].position.magnitude()
+    };
+    let rigid = stretch(1.0);
+    let soft = stretch(0.05);
+    assert_approx_eq!(rigid, 1.0, 1e-6);
+    assert!(soft > rigid + 1e-3, "soft {soft}");
+}
+
+#[test]
+fn rope_only_resists_stretching_and_pins_hold() {
+    let mut system: ParticleSystem<f64> = ParticleSystem::new(Vector2d::new(0.0, -9.81));
+    let top = system.add_particle(Particle::new(Vector2d::new(0.0, 0.0), 1.0));
+    let bottom = system.add_particle(Particle::new(Vector2d::new(0.0, -0.5), 1.0));
+    system
+        .add_constraint(Constraint::Pin {
+            particle: top,
+            target: Vector2d::new(0.0, 0.0),
+        })
+        .unwrap();
+    system
+        .add_constraint(Constraint::Rope {
+            a: top,
+            b: bottom,
+            min_length: 0.0,
+            max_length: 2.0,
+        })
+        .unwrap();
+
+    // Slack rope: the bottom particle falls freely for a moment.
+    run(&mut system, 10);
+    let slack = system.particles()[bottom].position.y;
+    assert!(slack < -0.5 && slack > -2.0);
+
+    run(&mut system, 120);
+    assert_eq!(system.particles()[top].position, Vector2d::new(0.0, 0.0));
+    assert_approx_eq!(system.particles()[bottom].position.magnitude(), 2.0, 1e-6);
+}
+
+#[test]
+fn angle_limit_is_enforced() {
+    let mut system: ParticleSystem<f64> = ParticleSystem::new(Vector2d::new(0.0, 0.0));
+    system.substeps = 1;
+    let a = system.add_particle(Particle::fixed(Vector2d::new(1.0, 0.0)));
+    let center = system.add_particle(Particle::fixed(Vector2d::new(0.0, 0.0)));
+    let c = system.add_particle(Particle::new(Vector2d::new(1.0, 0.1), 1.0));
+    system
+        .add_constraint(Constraint::Angle {
+            a,
+            center,
+            c,
+            min_angle: std::f64::consts::FRAC_PI_2,
+            max_angle: std::f64::consts::PI,
+        })
+        .unwrap();
+    system.step(1.0 / 60.0);
+    let p = system.particles()[c].position;
+    assert_approx_eq!(p.y.atan2(p.x), std::f64::consts::FRAC_PI_2, 1e-9);
+    assert!(system
+        .add_constraint(Constraint::Pin {
+            particle: 7,
+            target: Vector2d::new(0.0, 0.0),
+        })
+        .is_err());
+}
+
+#[test]
+fn particles_rest_on_static_segments() {
+    let mut system: ParticleSystem<f64> = ParticleSystem::new(Vector2d::new(0.0, -9.81));
+    system.collision_radius = 0.1;
+    system.add_segment(Vector2d::new(-5.0, 0.0), Vector2d::new(5.0, 0.0));
+    let fast = system.add_particle(Particle::new(Vector2d::new(0.0, 1.0), 1.0));
+    // Moving fast enough to tunnel through the segment in a single substep.
+    system.particles_mut()[fast].previous_position = Vector2d::new(0.0, 3.0);
+    run(&mut system, 120);
+    assert_approx_eq!(system.particles()[fast].position.y, 0.1, 1e-6);
+}
@@ -0,0 +1,183 @@
+// -----------------------------------------
+//     Testing Pathfinding Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use rand::{Rng, SeedableRng};
+use vectorlib::math::pathfinding_module::{string_pull, NavMesh, OccupancyGrid};
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn length(path: &[Vector2d<f64>]) -> f64 {
+    path.windows(2).map(|w| w[0].distance(w[1])).sum()
+}
+
+// Every waypoint step must be a straight or diagonal run over open cells.
+fn assert_walkable(grid: &OccupancyGrid<f64>, path: &[Vector2d<f64>]) {
+    for w in path.windows(2) {
+        let (a, b) = (grid.cell_at(w[0]).unwrap(), grid.cell_at(w[1]).unwrap());
+        let (dx, dy) = (b.0 as isize - a.0 as isize, b.1 as isize - a.1 as isize);
+        assert!(dx == 0 || dy == 0 || dx.abs() == dy.abs(), "{a:?} -> {b:?}");
+        let steps = dx.abs().max(dy.abs());
+        for s in 0..=steps {
+            let x = (a.0 as isize + dx.signum() * s) as usize;
+            let y = (a.1 as isize + dy.signum() * s) as usize;
+            assert!(grid.is_walkable(x, y));
+        }
+    }
+}
+
+#[test]
+fn astar_routes_around_walls() {
+    let grid = OccupancyGrid::from_rows(
+        &[
+            "..........",
+            "..#######.",
+            "..#.......",
+            "..#.#####.",
+            "....#.....",
+        ],
+        Vector2d::new(0.0, 0.0),
+        1.0,
+    )
+    .unwrap();
+    let start = Vector2d::new(3.5, 2.5);
+    let goal = Vector2d::new(5.5, 4.5);
+    let path = grid.astar(start, goal).unwrap();
+    assert_eq!(path[0], start);
+    assert_eq!(*path.last().unwrap(), goal);
+    assert_walkable(&grid, &path);
+    // Along row 2 to the right, down the last column and back along row 4;
+    // the corners of the walls cannot be cut diagonally.
+    assert_approx_eq!(length(&path), 6.0 + 2.0 + 4.0, 1e-9);
+
+    let jps = grid.jps(start, goal).unwrap();
+    assert_walkable(&grid, &jps);
+    assert_approx_eq!(length(&jps), length(&path), 1e-9);
+    assert!(jps.len() < path.len());
+}
+
+#[test]
+fn jps_matches_astar_on_random_grids() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(17);
+    for _ in 0..30 {
+        let mut grid: OccupancyGrid<f64> =
+            OccupancyGrid::new(24, 18, Vector2d::new(-3.0, 2.0), 0.5).unwrap();
+        for y in 0..18 {
+            for x in 0..24 {
+                grid.set_blocked(x, y, rng.gen_bool(0.3));
+            }
+        }
+        grid.set_blocked(0, 0, false);
+        grid.set_blocked(23, 17, false);
+        let start = grid.cell_center(0, 0);
+        let goal = grid.cell_center(23, 17);
+        let astar = grid.astar(start, goal);
+        let jps = grid.jps(start, goal);
+        assert_eq!(astar.is_some(), jps.is_some());
+        if let (Some(astar), Some(jps)) = (astar, jps) {
+            assert_walkable(&grid, &jps);
+            assert_approx_eq!(length(&jps), length(&astar), 1e-9);
+        }
+    }
+}
+
+#[test]
+fn blocked_or_unreachable_ends_have_no_path() {
+    let grid = OccupancyGrid::from_rows(&["..#..", "..#..", "..#.."], Vector2d::new(0.0, 0.0), 1.0)
+        .unwrap();
+    assert!(grid
+        .astar(Vector2d::new(0.5, 0.5), Vector2d::new(4.5, 0.5))
+        .is_none());
+    assert!(grid
+        .jps(Vector2d::new(0.5, 0.5), Vector2d::new(4.5, 0.5))
+        .is_none());
+    assert!(grid
+        .astar(Vector2d::new(0.5, 0.5), Vector2d::new(2.5, 0.5))
+        .is_none());
+    assert!(grid
+        .astar(Vector2d::new(-1.0, 0.5), Vector2d::new(1.5, 0.5))
+        .is_none());
+    assert!(OccupancyGrid::<f64>::from_rows(&["..", "..."], Vector2d::new(0.0, 0.0), 1.0).is_err());
+}
+
+#[test]
+fn start_and_goal_in_the_same_cell() {
+    let grid = OccupancyGrid::from_rows(&["...", "..."], Vector2d::new(0.0, 0.0), 1.0).unwrap();
+    let start = Vector2d::new(1.2, 0.3);
+    let goal = Vector2d::new(1.8, 0.9);
+    assert_eq!(grid.astar(start, goal), Some(vec![start, goal]));
+    assert_eq!(grid.jps(start, goal), Some(vec![start, goal]));
+    assert_eq!(grid.astar(start, start), Some(vec![start]));
+    assert_eq!(grid.jps(start, start), Some(vec![start]));
+}
+
+#[test]
+fn navmesh_path_is_pulled_tight_around_corners() {
+    // A U-shaped mesh around a hole between x in [1, 2] and y in [0, 2].
+    let vertices = vec![
+        Vector2d::new(0.0, 0.0), // 0
+        Vector2d::new(1.0, 0.0), // 1
+        Vector2d::new(0.0, 2.0), // 2
+        Vector2d::new(1.0, 2.0), // 3
+        Vector2d::new(0.0, 3.0), // 4
+        Vector2d::new(1.0, 3.0), // 5
+        Vector2d::new(2.0, 2.0), // 6
+        Vector2d::new(2.0, 3.0), // 7
+        Vector2d::new(3.0, 2.0), // 8
+        Vector2d::new(3.0, 3.0), // 9
+        Vector2d::new(2.0, 0.0), // 10
+        Vector2d::new(3.0, 0.0), // 11
+    ];
+    let polygons = vec![
+        vec![0, 1, 3, 2],
+        vec![2, 3, 5, 4],
+        vec![3, 6, 7, 5],
+        vec![6, 8, 9, 7],
+        vec![10, 11, 8, 6],
+    ];
+    let mesh = NavMesh::new(vertices, polygons).unwrap();
+    let start = Vector2d::new(0.5, 0.5);
+    let goal = Vector2d::new(2.5, 0.5);
+    let path = mesh.find_path(start, goal).unwrap();
+    assert_eq!(
+        path,
+        vec![
+            start,
+            Vector2d::new(1.0, 2.0),
+            Vector2d::new(2.0, 2.0),
+            goal
+        ]
+    );
+
+    assert!(mesh.find_path(start, Vector2d::new(1.5, 1.0)).is_none());
+    let island = NavMesh::new(
+        vec![
+            Vector2d::new(0.0, 0.0),
+            Vector2d::new(1.0, 0.0),
+            Vector2d::new(0.0, 1.0),
+            Vector2d::new(5.0, 0.0),
+            Vector2d::new(6.0, 0.0),
+            Vector2d::new(5.0, 1.0),
+        ],
+        vec![vec![0, 1, 2], vec![3, 4, 5]],
+    )
+    .unwrap();
+    assert!(island
+        .find_path(Vector2d::new(0.2, 0.2), Vector2d::new(5.2, 0.2))
+        .is_none());
+    assert!(NavMesh::<f64>::new(vec![Vector2d::new(0.0, 0.0)], vec![vec![0, 1, 2]]).is_err());
+}
+
+#[test]
+fn string_pull_through_straight_corridor() {
+    let portals: Vec<(Vector2d<f64>, Vector2d<f64>)> = vec![
+        (Vector2d::new(0.0, 0.0), Vector2d::new(0.0, 0.0)),
+        (Vector2d::new(1.0, 1.0), Vector2d::new(1.0, -1.0)),
+        (Vector2d::new(2.0, 1.0), Vector2d::new(2.0, -1.0)),
+        (Vector2d::new(3.0, 0.0), Vector2d::new(3.0, 0.0)),
+    ];
+    assert_eq!(
+        string_pull(&portals),
+        vec![Vector2d::new(0.0, 0.0), Vector2d::new(3.0, 0.0)]
+    );
+}
@@ -0,0 +1,174 @@
+// -----------------------------------------
+//     Testing Physics World Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use vectorlib::math::physics_world_module::{RigidBody, World2d};
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn world_with_ground() -> World2d<f64> {
+    let mut world = World2d::new(Vector2d::new(0.0, -10.0));
+    let mut ground = RigidBody::rectangle(Vector2d::new(0.0, -1.0), Vector2d::new(20.0, 1.0), 1.0)
+        .unwrap()
+        .into_static();
+    ground.restitution = 0.0;
+    world.add_body(ground);
+    world
+}
+
+#[test]
+fn free_fall_follows_gravity() {
+    let mut world: World2d<f64> = World2d::new(Vector2d::new(0.0, -10.0));
+    let ball = world.add_body(RigidBody::circle(Vector2d::new(0.0, 100.0), 1.0, 1.0).unwrap());
+    let steps = world.advance(1.0, 0.01);
+    assert_eq!(steps, 100);
+    let body = world.body(ball).unwrap();
+    assert_approx_eq!(body.velocity.y, -10.0, 1e-9);
+    // Semi-implicit Euler overshoots the analytic 95.0 by g * dt * t / 2.
+    assert_approx_eq!(body.position.y, 100.0 - 5.0 - 0.05, 1e-9);
+}
+
+#[test]
+fn box_stack_comes_to_rest() {
+    let mut world = world_with_ground();
+    let boxes: Vec<_> = (0..3)
+        .map(|i| {
+            world.add_body(
+                RigidBody::rectangle(
+                    Vector2d::new(0.0, 0.5 + 1.05 * i as f64),
+                    Vector2d::new(0.5, 0.5),
+                    1.0,
+                )
+                .unwrap(),
+            )
+        })
+        .collect();
+    for _ in 0..300 {
+        world.step(1.0 / 60.0);
+    }
+    for (i, handle) in boxes.iter().enumerate() {
+        let body = world.body(*handle).unwrap();
+        assert_approx_eq!(body.position.y, 0.5 + i as f64, 0.05);
+        assert_approx_eq!(body.position.x, 0.0, 0.01);
+        assert!(body.velocity.magnitude() < 0.05);
+        assert!(body.angle.abs() < 0.01);
+    }
+    assert!(!world.contacts().is_empty());
+}
+
+#[test]
+fn restitution_makes_balls_bounce() {
+    let mut world = world_with_ground();
+    let mut bouncy = RigidBody::circle(Vector2d::new(-2.0, 5.0), 0.5, 1.0).unwrap();
+    bouncy.restitution = 0.9;
+    let mut dull = RigidBody::circle(Vector2d::new(2.0, 5.0), 0.5, 1.0).unwrap();
+    dull.restitution = 0.0;
+    let bouncy = world.add_body(bouncy);
+    let dull = world.add_body(dull);
+
+    let mut bouncy_peak: f64 = 0.0;
+    let mut dull_peak: f64 = 0.0;
+    let mut landed = false;
+    for _ in 0..240 {
+        world.step(1.0 / 120.0);
+        let (b, d) = (world.body(bouncy).unwrap(), world.body(dull).unwrap());
+        if b.velocity.y > 0.0 {
+            landed = true;
+        }
+        if landed {
+            bouncy_peak = bouncy_peak.max(b.position.y);
+            dull_peak = dull_peak.max(d.position.y);
+        }
+    }
+    assert!(landed);
+    assert!(bouncy_peak > 3.0, "bouncy peak {bouncy_peak}");
+    assert!(dull_peak < 0.6, "dull peak {dull_peak}");
+}
+
+#[test]
+fn restitution_threshold_suppresses_slow_bounces() {
+    let mut world = world_with_ground();
+    world.restitution_threshold = 1.0e6;
+    let mut ball = RigidBody::circle(Vector2d::new(0.0, 5.0), 0.5, 1.0).unwrap();
+    ball.restitution = 0.9;
+    let ball = world.add_body(ball);
+
+    let mut landed = false;
+    let mut peak: f64 = 0.0;
+    for _ in 0..240 {
+        world.step(1.0 / 120.0);
+        let b = world.body(ball).unwrap();
+        if !world.contacts().is_empty() {
+            landed = true;
+        }
+        if landed {
+            peak = peak.max(b.position.y);
+        }
+    }
+    assert!(landed);
+    assert!(peak < 0.6, "peak {peak}");
+}
+
+#[test]
+fn off_center_impulse_spins_body() {
+    let mut world: World2d<f64> = World2d::new(Vector2d::new(0.0, 0.0));
+    let plank = world.add_body(
+        RigidBody::rectangle(Vector2d::new(0.0, 0.0), Vector2d::new(2.0, 0.25), 1.0).unwrap(),
+    );
+    let body = world.body_mut(plank).unwrap();
+    body.apply_impulse(Vector2d::new(0.0, 1.0), Vector2d::new(2.0, 0.0));
+    assert!(body.angular_velocity > 0.0);
+    assert_approx_eq!(body.velocity.y, 1.0 / body.mass, 1e-12);
+    world.step(0.1);
+    assert!(world.body(plank).unwrap().angle > 0.0);
+}
+
+#[test]
+fn invalid_time_steps_take_no_steps() {
+    let mut world: World2d<f64> = World2d::new(Vector2d::new(0.0, -10.0));
+    let ball = world.add_body(RigidBody::circle(Vector2d::new(0.0, 10.0), 1.0, 1.0).unwrap());
+    assert_eq!(world.advance(1.0, 0.0), 0);
+    assert_eq!(world.advance(1.0, -0.01), 0);
+    assert_eq!(world.advance(1.0, f64::NAN), 0);
+    assert_eq!(world.advance(1.0, f64::INFINITY), 0);
+    assert_eq!(world.body(ball).unwrap().position, Vector2d::new(0.0, 10.0));
+    // Rejected calls leave no time behind in the accumulator.
+    assert_eq!(world.advance(0.05, 0.01), 5);
+}
+
+#[test]
+fn degenerate_boxes_are_rejected() {
+    let center = Vector2d::new(0.0, 0.0);
+    for half_extents in [
+        Vector2d::new(0.0, 1.0),
+        Vector2d::new(1.0, -0.0),
+        Vector2d::new(-1.0, 1.0),
+        Vector2d::new(1.0, f64::NAN),
+    ] {
+        assert!(RigidBody::rectangle(center, half_extents, 1.0).is_err());
+    }
+    assert!(RigidBody::rectangle(center, Vector2d::new(1.0, 2.0), 1.0).is_ok());
+}
+
+#[test]
+fn degenerate_circles_are_rejected() {
+    let center = Vector2d::new(0.0, 0.0);
+    for (radius, density) in [
+        (0.0, 1.0),
+        (-1.0, 1.0),
+        (f64::NAN, 1.0),
+        (1.0, 0.0),
+        (1.0, -2.0),
+        (1.0, f64::INFINITY),
+    ] {
+        assert!(RigidBody::circle(center, radius, density).is_err());
+    }
+    assert!(RigidBody::circle(center, 1.0, 1.0).is_ok());
+    let square = [
+        Vector2d::new(0.0, 0.0),
+        Vector2d::new(1.0, 0.0),
+        Vector2d::new(1.0, 1.0),
+        Vector2d::new(0.0, 1.0),
+    ];
+    assert!(RigidBody::polygon(&square, 0.0).is_err());
+}
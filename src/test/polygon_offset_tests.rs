@@ -0,0 +1,106 @@
+// -----------------------------------------
+//     Testing Polygon Offset Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use vectorlib::math::polygon_offset_module::{
+    offset_polygon, offset_polyline, signed_area, stroke_polyline, LineCap, LineJoin, StrokeStyle,
+};
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn unit_square() -> Vec<Vector2d<f64>> {
+    vec![
+        Vector2d::new(0.0, 0.0),
+        Vector2d::new(1.0, 0.0),
+        Vector2d::new(1.0, 1.0),
+        Vector2d::new(0.0, 1.0),
+    ]
+}
+
+#[test]
+fn offset_polygon_inflates_and_deflates() {
+    let style = StrokeStyle::new(LineJoin::Miter, LineCap::Butt);
+    let grown = offset_polygon(&unit_square(), 0.5, &style).unwrap();
+    assert_eq!(grown.len(), 4);
+    assert_approx_eq!(signed_area(&grown), 4.0, 1e-9);
+
+    let shrunk = offset_polygon(&unit_square(), -0.25, &style).unwrap();
+    assert_approx_eq!(signed_area(&shrunk), 0.25, 1e-9);
+
+    // Clockwise input still grows with a positive distance.
+    let mut clockwise = unit_square();
+    clockwise.reverse();
+    let grown_cw = offset_polygon(&clockwise, 0.5, &style).unwrap();
+    assert_approx_eq!(signed_area(&grown_cw), -4.0, 1e-9);
+}
+
+#[test]
+fn offset_polygon_joins() {
+    let mut style = StrokeStyle::new(LineJoin::Bevel, LineCap::Butt);
+    let bevel = offset_polygon(&unit_square(), 1.0, &style).unwrap();
+    assert_eq!(bevel.len(), 8);
+    // Square plus four edge rectangles plus four corner triangles.
+    assert_approx_eq!(signed_area(&bevel), 1.0 + 4.0 + 2.0, 1e-9);
+
+    style.join = LineJoin::Round;
+    style.arc_tolerance = 0.001;
+    let round = offset_polygon(&unit_square(), 1.0, &style).unwrap();
+    assert_approx_eq!(signed_area(&round), 5.0 + std::f64::consts::PI, 0.01);
+
+    // A miter limit below sqrt(2) turns the square corners into bevels.
+    style.join = LineJoin::Miter;
+    style.miter_limit = 1.2;
+    let limited = offset_polygon(&unit_square(), 1.0, &style).unwrap();
+    assert_eq!(limited, bevel);
+}
+
+#[test]
+fn offset_polyline_turns() {
+    let style = StrokeStyle::new(LineJoin::Miter, LineCap::Butt);
+    let path: Vec<Vector2d<f64>> = vec![
+        Vector2d::new(0.0, 0.0),
+        Vector2d::new(2.0, 0.0),
+        Vector2d::new(2.0, 2.0),
+    ];
+    let right = offset_polyline(&path, 1.0, &style).unwrap();
+    let expected: [Vector2d<f64>; 3] = [
+        Vector2d::new(0.0, -1.0),
+        Vector2d::new(3.0, -1.0),
+        Vector2d::new(3.0, 2.0),
+    ];
+    assert_eq!(right.len(), 3);
+    for (e, a) in expected.iter().zip(right.iter()) {
+        assert_vec2_equal!(e, a);
+    }
+    let left = offset_polyline(&path, -1.0, &style).unwrap();
+    assert_vec2_equal!(Vector2d::<f64>::new(1.0, 1.0), left[1]);
+}
+
+#[test]
+fn stroke_polyline_caps_and_rings() {
+    let line: Vec<Vector2d<f64>> = vec![Vector2d::new(0.0, 0.0), Vector2d::new(4.0, 0.0)];
+    let mut style = StrokeStyle::new(LineJoin::Miter, LineCap::Butt);
+    let butt = stroke_polyline(&line, 2.0, false, &style).unwrap();
+    assert_approx_eq!(signed_area(&butt[0]).abs(), 8.0, 1e-9);
+
+    style.cap = LineCap::Square;
+    let square = stroke_polyline(&line, 2.0, false, &style).unwrap();
+    assert_approx_eq!(signed_area(&square[0]).abs(), 12.0, 1e-9);
+
+    style.cap = LineCap::Round;
+    style.arc_tolerance = 0.001;
+    let round = stroke_polyline(&line, 2.0, false, &style).unwrap();
+    assert_approx_eq!(
+        signed_area(&round[0]).abs(),
+        8.0 + std::f64::consts::PI,
+        0.01
+    );
+
+    let rings = stroke_polyline(&unit_square(), 0.5, true, &style).unwrap();
+    assert_eq!(rings.len(), 2);
+    let total = signed_area(&rings[0]) + signed_area(&rings[1]);
+    assert_approx_eq!(total, 2.25 - 0.25, 1e-9);
+
+    assert!(stroke_polyline(&line, 0.0, false, &style).is_err());
+    assert!(stroke_polyline(&line[..1], 1.0, false, &style).is_err());
+}
@@ -0,0 +1,88 @@
+// -----------------------------------------
+//       Testing Polyline Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use rand::{Rng, SeedableRng};
+use vectorlib::math::polyline_module::{
+    chaikin_smooth, moving_average, polyline_length, resample_count, resample_spacing,
+    savitzky_golay, simplify_rdp, simplify_visvalingam,
+};
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn noisy_line(count: usize) -> Vec<Vector2d<f64>> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+    (0..count)
+        .map(|i| Vector2d::new(i as f64 * 0.1, rng.gen_range(-0.01..0.01)))
+        .collect()
+}
+
+#[test]
+fn simplification_keeps_shape() {
+    let mut path = noisy_line(200);
+    path.push(Vector2d::new(19.9, 10.0));
+
+    let rdp = simplify_rdp(&path, 0.05);
+    assert_eq!(rdp.len(), 3);
+    assert_eq!(rdp[0], path[0]);
+    assert_eq!(rdp[2], path[path.len() - 1]);
+
+    let vw = simplify_visvalingam(&path, 0.5);
+    assert_eq!(vw.len(), 3);
+    assert_eq!(vw[1], path[path.len() - 2]);
+
+    // A zero tolerance keeps every point off the straight line.
+    assert_eq!(simplify_rdp(&path, 0.0).len(), path.len());
+}
+
+#[test]
+fn resampling_is_uniform() {
+    let path: Vec<Vector2d<f64>> = vec![
+        Vector2d::new(0.0, 0.0),
+        Vector2d::new(3.0, 0.0),
+        Vector2d::new(3.0, 3.0),
+    ];
+    let by_count = resample_count(&path, 7).unwrap();
+    assert_eq!(by_count.len(), 7);
+    for pair in by_count.windows(2) {
+        assert_approx_eq!(pair[0].distance(pair[1]), 1.0, 1e-9);
+    }
+    assert_eq!(by_count[6], Vector2d::new(3.0, 3.0));
+
+    let by_spacing = resample_spacing(&path, 0.5).unwrap();
+    assert_eq!(by_spacing.len(), 13);
+    assert_approx_eq!(polyline_length(&by_spacing), 6.0, 1e-9);
+
+    assert!(resample_count(&path, 1).is_err());
+    assert!(resample_spacing(&path, 0.0).is_err());
+}
+
+#[test]
+fn smoothing_filters() {
+    let square: Vec<Vector2d<f64>> = vec![
+        Vector2d::new(0.0, 0.0),
+        Vector2d::new(1.0, 0.0),
+        Vector2d::new(1.0, 1.0),
+        Vector2d::new(0.0, 1.0),
+    ];
+    let closed = chaikin_smooth(&square, 2, true);
+    assert_eq!(closed.len(), 16);
+
+    let noisy = noisy_line(50);
+    let averaged = moving_average(&noisy, 5).unwrap();
+    let filtered = savitzky_golay(&noisy, 7, 2).unwrap();
+    let roughness = |p: &[Vector2d<f64>]| p.iter().map(|v| v.y.abs()).sum::<f64>();
+    assert!(roughness(&averaged) < roughness(&noisy));
+    assert!(roughness(&filtered) < roughness(&noisy));
+    assert_eq!(averaged[0], noisy[0]);
+
+    // A straight line is a fixed point of the Savitzky-Golay filter.
+    let line: Vec<Vector2d<f64>> = (0..10)
+        .map(|i| Vector2d::new(i as f64, 2.0 * i as f64))
+        .collect();
+    for (a, b) in savitzky_golay(&line, 5, 1).unwrap().iter().zip(line.iter()) {
+        assert_vec2_equal!(a, b);
+    }
+    assert!(moving_average(&noisy, 4).is_err());
+    assert!(savitzky_golay(&noisy, 3, 3).is_err());
+}
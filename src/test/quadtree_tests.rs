@@ -0,0 +1,119 @@
+// -----------------------------------------
+//     Testing Quadtree Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use rand::{Rng, SeedableRng};
+use vectorlib::math::quadtree_module::QuadTree;
+use vectorlib::math::shape_module::Aabb;
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn region() -> Aabb<f64> {
+    Aabb::new(Vector2d::new(-50.0, -50.0), Vector2d::new(50.0, 50.0))
+}
+
+fn random_tree(count: usize, seed: u64) -> (QuadTree<f64, usize>, Vec<Vector2d<f64>>) {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+    // Clustered in one corner so the tree is deliberately unbalanced.
+    let points: Vec<Vector2d<f64>> = (0..count)
+        .map(|_| {
+            let spread: f64 = if rng.gen_bool(0.8) { 5.0 } else { 50.0 };
+            Vector2d::new(
+                (rng.gen_range(-1.0..1.0) * spread - 40.0).max(-50.0),
+                (rng.gen_range(-1.0..1.0) * spread - 40.0).max(-50.0),
+            )
+        })
+        .collect();
+    let mut tree = QuadTree::with_limits(region(), 4, 10).unwrap();
+    for (i, p) in points.iter().enumerate() {
+        tree.insert_point(i, *p);
+    }
+    (tree, points)
+}
+
+#[test]
+fn range_queries_match_brute_force() {
+    let (tree, points) = random_tree(600, 3);
+    let area = Aabb::new(Vector2d::new(-45.0, -42.0), Vector2d::new(-30.0, -20.0));
+    let mut found = tree.query_rect(&area);
+    found.sort();
+    let expected: Vec<usize> = (0..points.len())
+        .filter(|&i| area.contains_point(points[i]))
+        .collect();
+    assert_eq!(found, expected);
+
+    let center = Vector2d::new(-38.0, -41.0);
+    let mut found = tree.query_circle(center, 4.0);
+    found.sort();
+    let expected: Vec<usize> = (0..points.len())
+        .filter(|&i| points[i].distance(center) <= 4.0)
+        .collect();
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn nearest_neighbors_match_brute_force() {
+    let (tree, points) = random_tree(400, 5);
+    let query = Vector2d::new(-20.0, -35.0);
+    let nearest = tree.nearest(query, 6);
+    let mut expected: Vec<(usize, f64)> = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i, p.distance(query)))
+        .collect();
+    expected.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    assert_eq!(nearest.len(), 6);
+    for (got, want) in nearest.iter().zip(expected.iter()) {
+        assert_approx_eq!(got.1, want.1, 1e-12);
+    }
+    assert!(tree.nearest(query, 0).is_empty());
+}
+
+#[test]
+fn removing_everything_collapses_the_tree() {
+    let (mut tree, points) = random_tree(200, 9);
+    assert!(tree.nodes().count() > 1);
+    let stored: usize = tree.nodes().map(|n| n.item_count).sum();
+    assert_eq!(stored, points.len());
+    assert!(tree.nodes().all(|n| n.depth <= 10));
+
+    for i in 0..points.len() {
+        assert!(tree.remove(i));
+    }
+    assert!(!tree.remove(0));
+    assert!(tree.is_empty());
+    let nodes: Vec<_> = tree.nodes().collect();
+    assert_eq!(nodes.len(), 1);
+    assert!(nodes[0].is_leaf);
+}
+
+#[test]
+fn boxes_and_moves_are_tracked() {
+    let mut tree: QuadTree<f64, &str> = QuadTree::with_limits(region(), 1, 6).unwrap();
+    tree.insert_point("a", Vector2d::new(-40.0, -40.0));
+    tree.insert_point("b", Vector2d::new(40.0, 40.0));
+    // Straddles the centre lines, so it stays in the root.
+    tree.insert_aabb(
+        "wide",
+        Aabb::new(Vector2d::new(-5.0, -5.0), Vector2d::new(5.0, 5.0)),
+    );
+    tree.insert_point("outside", Vector2d::new(80.0, 0.0));
+
+    assert!(tree.move_point("a", Vector2d::new(39.0, 39.0)));
+    assert!(!tree.move_point("missing", Vector2d::new(0.0, 0.0)));
+    let mut near_b = tree.query_circle(Vector2d::new(40.0, 40.0), 2.0);
+    near_b.sort();
+    assert_eq!(near_b, vec!["a", "b"]);
+    assert!(tree
+        .query_rect(&Aabb::new(
+            Vector2d::new(-50.0, -50.0),
+            Vector2d::new(-30.0, -30.0)
+        ))
+        .is_empty());
+    assert_eq!(
+        tree.query_circle(Vector2d::new(0.0, 0.0), 1.0),
+        vec!["wide"]
+    );
+    assert_eq!(tree.nearest(Vector2d::new(70.0, 0.0), 1)[0].0, "outside");
+    assert!(QuadTree::<f64, u8>::with_limits(region(), 0, 4).is_err());
+}
@@ -0,0 +1,171 @@
+// -----------------------------------------
+//     Testing Random Vector Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use rand::distributions::{Distribution, Standard};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use vectorlib::math::random_vector_module::{
+    Gaussian2d, UniformAnnulus, UniformDisk, UniformPolygon, UniformRect, UnitDirection,
+};
+use vectorlib::math::vector2d_module::Vector2d;
+
+const SAMPLES: usize = 20_000;
+
+fn mean(points: &[Vector2d<f64>]) -> Vector2d<f64> {
+    points
+        .iter()
+        .fold(Vector2d::new(0.0, 0.0), |acc, p| acc + *p)
+        * (1.0 / points.len() as f64)
+}
+
+#[test]
+fn seeded_generation_is_deterministic() {
+    let draw = |seed| {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..5)
+            .map(|_| Vector2d::random_in_disk(&mut rng, Vector2d::new(1.0, 1.0), 2.0))
+            .collect::<Vec<Vector2d<f64>>>()
+    };
+    assert_eq!(draw(3), draw(3));
+    assert_ne!(draw(3), draw(4));
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let square: Vector2d<f64> = rng.gen();
+    assert!((0.0..1.0).contains(&square.x) && (0.0..1.0).contains(&square.y));
+    let from_standard: Vec<Vector2d<f32>> = Standard.sample_iter(&mut rng).take(10).collect();
+    assert_eq!(from_standard.len(), 10);
+}
+
+#[test]
+fn unit_directions_and_disks_are_uniform() {
+    let mut rng = StdRng::seed_from_u64(10);
+    let directions: Vec<Vector2d<f64>> = (0..SAMPLES)
+        .map(|_| UnitDirection.sample(&mut rng))
+        .collect();
+    assert!(directions
+        .iter()
+        .all(|d| (d.magnitude() - 1.0).abs() < 1e-12));
+    assert!(mean(&directions).magnitude() < 0.03);
+
+    let disk = UniformDisk::new(Vector2d::new(5.0, -2.0), 3.0);
+    let points: Vec<Vector2d<f64>> = (0..SAMPLES).map(|_| disk.sample(&mut rng)).collect();
+    assert!(points.iter().all(|p| p.distance(disk.center) <= 3.0));
+    // Uniform over the area: a quarter of the points within half the radius.
+    let inner = points
+        .iter()
+        .filter(|p| p.distance(disk.center) < 1.5)
+        .count() as f64;
+    assert_approx_eq!(inner / SAMPLES as f64, 0.25, 0.02);
+    assert_approx_eq!(mean(&points).x, 5.0, 0.05);
+
+    let ring = UniformAnnulus::new(Vector2d::new(0.0, 0.0), 1.0, 2.0).unwrap();
+    for _ in 0..1000 {
+        let r = ring.sample(&mut rng).magnitude();
+        assert!((1.0..=2.0).contains(&r));
+    }
+    assert!(UniformAnnulus::new(Vector2d::new(0.0, 0.0), 2.0, 1.0).is_err());
+}
+
+#[test]
+fn triangles_and_polygons_cover_their_area() {
+    let mut rng = StdRng::seed_from_u64(11);
+    let (a, b, c) = (
+        Vector2d::new(0.0, 0.0),
+        Vector2d::new(3.0, 0.0),
+        Vector2d::new(0.0, 3.0),
+    );
+    let points: Vec<Vector2d<f64>> = (0..SAMPLES)
+        .map(|_| Vector2d::random_in_triangle(&mut rng, a, b, c))
+        .collect();
+    assert!(points
+        .iter()
+        .all(|p| p.x >= 0.0 && p.y >= 0.0 && p.x + p.y <= 3.0));
+    let centroid = mean(&points);
+    assert_approx_eq!(centroid.x, 1.0, 0.03);
+    assert_approx_eq!(centroid.y, 1.0, 0.03);
+
+    // Clockwise U shape: the notch between x in [1, 2] above y = 1 is empty.
+    let u_shape = vec![
+        Vector2d::new(0.0, 0.0),
+        Vector2d::new(0.0, 3.0),
+        Vector2d::new(1.0, 3.0),
+        Vector2d::new(1.0, 1.0),
+        Vector2d::new(2.0, 1.0),
+        Vector2d::new(2.0, 3.0),
+        Vector2d::new(3.0, 3.0),
+        Vector2d::new(3.0, 0.0),
+    ];
+    let polygon: UniformPolygon<f64> = UniformPolygon::new(&u_shape).unwrap();
+    assert_approx_eq!(polygon.area(), 7.0, 1e-12);
+    let points: Vec<Vector2d<f64>> = (0..SAMPLES).map(|_| polygon.sample(&mut rng)).collect();
+    assert!(points
+        .iter()
+        .all(|p| !(p.x > 1.0 && p.x < 2.0 && p.y > 1.0)));
+    let left_arm = points.iter().filter(|p| p.x < 1.0 && p.y > 1.0).count() as f64;
+    assert_approx_eq!(left_arm / SAMPLES as f64, 2.0 / 7.0, 0.02);
+
+    let line = vec![
+        Vector2d::new(0.0, 0.0),
+        Vector2d::new(1.0, 0.0),
+        Vector2d::new(2.0, 0.0),
+    ];
+    assert!(Vector2d::random_in_polygon(&mut rng, &line).is_err());
+}
+
+#[test]
+fn gaussian_moments() {
+    let mut rng = StdRng::seed_from_u64(12);
+    let gaussian = Gaussian2d {
+        mean: Vector2d::new(2.0, -1.0),
+        std_dev: Vector2d::new(0.5, 2.0),
+    };
+    let points: Vec<Vector2d<f64>> = (0..SAMPLES).map(|_| gaussian.sample(&mut rng)).collect();
+    let m = mean(&points);
+    assert_approx_eq!(m.x, 2.0, 0.02);
+    assert_approx_eq!(m.y, -1.0, 0.05);
+    let variance = points.iter().fold(Vector2d::new(0.0, 0.0), |acc, p| {
+        acc + Vector2d::new((p.x - m.x).powi(2), (p.y - m.y).powi(2))
+    }) * (1.0 / SAMPLES as f64);
+    assert_approx_eq!(variance.x.sqrt(), 0.5, 0.02);
+    assert_approx_eq!(variance.y.sqrt(), 2.0, 0.05);
+    let isotropic = Vector2d::random_gaussian(&mut rng, Vector2d::new(0.0, 0.0), 0.0);
+    assert_eq!(isotropic, Vector2d::new(0.0, 0.0));
+}
+
+// Always returns the largest `f64` below one, the worst case for `f32`.
+struct TopRng;
+
+impl rand::RngCore for TopRng {
+    fn next_u32(&mut self) -> u32 {
+        u32::MAX
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        u64::MAX
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        dest.fill(u8::MAX);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[test]
+fn f32_draws_next_to_one_stay_finite() {
+    let mut rng = TopRng;
+    assert_eq!(rng.gen::<f64>() as f32, 1.0);
+
+    let gaussian = Gaussian2d::new(Vector2d::new(0.0f32, 0.0), 1.0);
+    let v: Vector2d<f32> = gaussian.sample(&mut rng);
+    assert!(v.x.is_finite() && v.y.is_finite());
+
+    let rect = UniformRect::new(Vector2d::new(0.0f32, 0.0), Vector2d::new(1.0, 1.0));
+    let v: Vector2d<f32> = rect.sample(&mut rng);
+    assert!(v.x < 1.0 && v.y < 1.0);
+}
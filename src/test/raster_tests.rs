@@ -0,0 +1,153 @@
+// -----------------------------------------
+//     Testing Raster Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use vectorlib::math::raster_module::{FillRule, ImageBuffer, Rgba};
+use vectorlib::math::svg_path_module::SvgPath;
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn v(x: f64, y: f64) -> Vector2d<f64> {
+    Vector2d::new(x, y)
+}
+
+fn alpha(image: &ImageBuffer, x: usize, y: usize) -> u8 {
+    image.get(x, y).unwrap().a
+}
+
+fn total_coverage(image: &ImageBuffer) -> f64 {
+    image.pixels().iter().map(|p| p.a as f64 / 255.0).sum()
+}
+
+#[test]
+fn polygon_edges_are_anti_aliased() {
+    let mut image = ImageBuffer::new(10, 10);
+    let rectangle = [v(2.5, 2.0), v(5.5, 2.0), v(5.5, 6.0), v(2.5, 6.0)];
+    image.fill_polygon(&rectangle, FillRule::NonZero, Rgba::BLUE);
+    assert_eq!(image.get(3, 3), Some(Rgba::BLUE));
+    assert_eq!(alpha(&image, 2, 3), 128);
+    assert_eq!(alpha(&image, 5, 3), 128);
+    assert_eq!(alpha(&image, 3, 1), 0);
+    assert_eq!(alpha(&image, 3, 6), 0);
+    assert_approx_eq!(total_coverage(&image), 12.0, 0.05);
+}
+
+#[test]
+fn fill_rules_differ_on_overlaps() {
+    let star: Vec<Vector2d<f64>> = (0..5)
+        .map(|i| {
+            let angle = i as f64 * 4.0 * std::f64::consts::PI / 5.0;
+            v(20.0 + 18.0 * angle.sin(), 20.0 - 18.0 * angle.cos())
+        })
+        .collect();
+    let mut non_zero = ImageBuffer::new(40, 40);
+    non_zero.fill_polygon(&star, FillRule::NonZero, Rgba::RED);
+    let mut even_odd = ImageBuffer::new(40, 40);
+    even_odd.fill_polygon(&star, FillRule::EvenOdd, Rgba::RED);
+    assert_eq!(alpha(&non_zero, 20, 20), 255);
+    assert_eq!(alpha(&even_odd, 20, 20), 0);
+    // The tips are covered once under both rules.
+    assert_eq!(alpha(&non_zero, 20, 6), 255);
+    assert_eq!(alpha(&even_odd, 20, 6), 255);
+
+    // Two sub-paths with the same orientation: a hole only for even-odd.
+    let path: SvgPath = "M 2 2 H 18 V 18 H 2 Z M 6 6 H 14 V 14 H 6 Z"
+        .parse()
+        .unwrap();
+    let mut image = ImageBuffer::new(20, 20);
+    image.fill_path(&path, FillRule::EvenOdd, Rgba::BLACK);
+    assert_eq!(alpha(&image, 10, 10), 0);
+    assert_eq!(alpha(&image, 3, 10), 255);
+    image.fill_path(&path, FillRule::NonZero, Rgba::BLACK);
+    assert_eq!(alpha(&image, 10, 10), 255);
+}
+
+#[test]
+fn wu_lines_split_coverage() {
+    let mut image = ImageBuffer::new(12, 12);
+    image.draw_line(v(1.5, 4.5), v(10.5, 4.5), Rgba::BLACK);
+    for x in 2..10 {
+        assert_eq!(alpha(&image, x, 4), 255);
+        assert_eq!(alpha(&image, x, 3), 0);
+        assert_eq!(alpha(&image, x, 5), 0);
+    }
+
+    // Between two pixel rows the coverage is shared evenly.
+    let mut image = ImageBuffer::new(12, 12);
+    image.draw_line(v(1.5, 5.0), v(10.5, 5.0), Rgba::BLACK);
+    assert_eq!(alpha(&image, 6, 4), 128);
+    assert_eq!(alpha(&image, 6, 5), 128);
+
+    // A steep diagonal puts one pixel of ink in every row.
+    let mut image = ImageBuffer::new(32, 32);
+    image.draw_line(v(4.5, 1.5), v(12.5, 30.5), Rgba::BLACK);
+    for y in 3..29 {
+        let row: f64 = (0..32).map(|x| alpha(&image, x, y) as f64 / 255.0).sum();
+        assert_approx_eq!(row, 1.0, 0.02);
+    }
+
+    // Lines far outside the image are clipped without panicking.
+    image.draw_line(v(-1e6, -1e6), v(1e6, 1e6), Rgba::RED);
+    image.draw_line(v(f64::NAN, 0.0), v(3.0, 3.0), Rgba::RED);
+}
+
+#[test]
+fn circles_and_curves() {
+    let mut image = ImageBuffer::new(32, 32);
+    image.fill_circle(v(16.0, 16.0), 8.0, Rgba::GREEN);
+    assert_eq!(image.get(16, 16), Some(Rgba::GREEN));
+    assert_eq!(alpha(&image, 1, 1), 0);
+    let area = std::f64::consts::PI * 64.0;
+    assert_approx_eq!(total_coverage(&image), area, area * 0.02);
+
+    let mut ring = ImageBuffer::new(32, 32);
+    ring.draw_circle(v(16.0, 16.0), 10.0, 2.0, Rgba::RED);
+    assert_eq!(alpha(&ring, 16, 16), 0);
+    assert!(alpha(&ring, 26, 16) > 240);
+    let area = 2.0 * std::f64::consts::PI * 10.0 * 2.0;
+    assert_approx_eq!(total_coverage(&ring), area, area * 0.03);
+
+    // A symmetric arch peaks at three quarters of its control height.
+    let mut curve = ImageBuffer::new(32, 32);
+    curve.draw_cubic(
+        v(2.5, 28.5),
+        v(2.5, 4.5),
+        v(29.5, 4.5),
+        v(29.5, 28.5),
+        Rgba::BLACK,
+    );
+    // Flattening stays within a quarter pixel of the curve.
+    assert!(alpha(&curve, 16, 10) > 160);
+    let column: f64 = (0..12).map(|y| alpha(&curve, 16, y) as f64 / 255.0).sum();
+    assert_approx_eq!(column, 1.0, 0.02);
+    assert_eq!(alpha(&curve, 16, 4), 0);
+    assert!(alpha(&curve, 2, 28) > 0);
+
+    let mut quadratic = ImageBuffer::new(32, 32);
+    quadratic.draw_quadratic(v(2.5, 28.5), v(16.5, -11.5), v(30.5, 28.5), Rgba::BLACK);
+    assert!(alpha(&quadratic, 16, 8) > 160);
+}
+
+#[test]
+fn ppm_and_pam_output() {
+    let mut image = ImageBuffer::new(2, 1);
+    image.set(0, 0, Rgba::new(255, 0, 0, 255));
+    image.set(1, 0, Rgba::new(0, 0, 255, 128));
+
+    let ppm = image.to_ppm_over(Rgba::WHITE);
+    let header = b"P6\n2 1\n255\n";
+    assert_eq!(&ppm[..header.len()], header);
+    assert_eq!(&ppm[header.len()..], &[255, 0, 0, 127, 127, 255]);
+
+    let pam = image.to_pam();
+    let text = String::from_utf8_lossy(&pam);
+    assert!(text.starts_with("P7\nWIDTH 2\nHEIGHT 1\nDEPTH 4\nMAXVAL 255\n"));
+    assert!(text.contains("TUPLTYPE RGB_ALPHA\nENDHDR\n"));
+    assert_eq!(&pam[pam.len() - 8..], &[255, 0, 0, 255, 0, 0, 255, 128]);
+
+    let path = std::env::temp_dir().join("vectorlib_raster_test.pam");
+    image.save(&path).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), pam);
+    let _ = std::fs::remove_file(&path);
+    assert!(image.save("/nonexistent/dir/frame.ppm").is_err());
+}
@@ -0,0 +1,124 @@
+// -----------------------------------------
+//     Testing Sampling Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use vectorlib::math::sampling_module::{
+    cranley_patterson, halton, poisson_disk_polygon, poisson_disk_rect, poisson_disk_variable, r2,
+    sobol,
+};
+use vectorlib::math::shape_module::Aabb;
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn min_spacing(points: &[Vector2d<f64>]) -> f64 {
+    let mut best = f64::INFINITY;
+    for (i, a) in points.iter().enumerate() {
+        for b in &points[i + 1..] {
+            best = best.min(a.distance(*b));
+        }
+    }
+    best
+}
+
+#[test]
+fn poisson_rect_is_spaced_and_maximal() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let bounds = Aabb::new(Vector2d::new(-5.0, 0.0), Vector2d::new(15.0, 10.0));
+    let points = poisson_disk_rect(&mut rng, &bounds, 0.75).unwrap();
+    assert!(points.iter().all(|p| bounds.contains_point(*p)));
+    assert!(min_spacing(&points) >= 0.75);
+
+    // Every probe point of a dense grid lies within 2r of some sample.
+    for i in 0..=40 {
+        for j in 0..=20 {
+            let probe = Vector2d::new(-5.0 + i as f64 * 0.5, j as f64 * 0.5);
+            assert!(points.iter().any(|p| p.distance(probe) < 1.5));
+        }
+    }
+
+    let mut again = StdRng::seed_from_u64(7);
+    assert_eq!(
+        points,
+        poisson_disk_rect(&mut again, &bounds, 0.75).unwrap()
+    );
+    assert!(poisson_disk_rect(&mut rng, &bounds, 0.0).is_err());
+}
+
+#[test]
+fn poisson_polygon_stays_inside() {
+    let mut rng = StdRng::seed_from_u64(11);
+    // An L shape.
+    let polygon = [
+        Vector2d::new(0.0, 0.0),
+        Vector2d::new(8.0, 0.0),
+        Vector2d::new(8.0, 3.0),
+        Vector2d::new(3.0, 3.0),
+        Vector2d::new(3.0, 8.0),
+        Vector2d::new(0.0, 8.0),
+    ];
+    let points = poisson_disk_polygon(&mut rng, &polygon, 0.5).unwrap();
+    assert!(points.len() > 50);
+    assert!(min_spacing(&points) >= 0.5);
+    assert!(points.iter().all(|p| p.x <= 3.0 || p.y <= 3.0));
+    assert!(points.iter().any(|p| p.x > 6.0) && points.iter().any(|p| p.y > 6.0));
+}
+
+#[test]
+fn poisson_variable_radius_thins_out() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let bounds = Aabb::new(Vector2d::new(0.0, 0.0), Vector2d::new(20.0, 10.0));
+    let points = poisson_disk_variable(&mut rng, &bounds, 0.4, 2.0, |p| 0.4 + p.x * 0.08).unwrap();
+    let left = points.iter().filter(|p| p.x < 5.0).count();
+    let right = points.iter().filter(|p| p.x > 15.0).count();
+    assert!(left > 4 * right);
+    for (i, a) in points.iter().enumerate() {
+        for b in &points[i + 1..] {
+            let radius = |p: &Vector2d<f64>| (0.4 + p.x * 0.08).min(2.0);
+            assert!(a.distance(*b) >= (radius(a) + radius(b)) / 2.0 - 1e-9);
+        }
+    }
+    assert!(poisson_disk_variable(&mut rng, &bounds, 1.0, 0.5, |_| 1.0).is_err());
+}
+
+#[test]
+fn low_discrepancy_sequences_fill_the_square() {
+    let unit = Aabb::new(Vector2d::new(0.0, 0.0), Vector2d::new(1.0, 1.0));
+    let sobol_points: Vec<Vector2d<f64>> = sobol(4, &unit);
+    assert_eq!(
+        sobol_points,
+        vec![
+            Vector2d::new(0.5, 0.5),
+            Vector2d::new(0.75, 0.25),
+            Vector2d::new(0.25, 0.75),
+            Vector2d::new(0.375, 0.375),
+        ]
+    );
+
+    // 256 points leave no empty cell in a 8x8 grid.
+    let sequences: [Vec<Vector2d<f64>>; 3] =
+        [halton(256, &unit), sobol(256, &unit), r2(256, &unit)];
+    for points in &sequences {
+        let mut cells = [0; 64];
+        for p in points {
+            assert!(unit.contains_point(*p));
+            cells[(p.y * 8.0) as usize * 8 + (p.x * 8.0) as usize] += 1;
+        }
+        assert!(cells.iter().all(|&c| c > 0));
+    }
+}
+
+#[test]
+fn cranley_patterson_shift_is_seeded() {
+    let bounds = Aabb::new(Vector2d::new(2.0, 2.0), Vector2d::new(4.0, 6.0));
+    let base = halton(64, &bounds);
+    let shift = |seed| cranley_patterson(&base, &bounds, &mut StdRng::seed_from_u64(seed));
+    assert_eq!(shift(1), shift(1));
+    assert_ne!(shift(1), shift(2));
+    assert!(shift(5).iter().all(|p| bounds.contains_point(*p)));
+    // The shift is rigid modulo the box, so pairwise x differences survive.
+    let shifted = shift(1);
+    let dx = |a: Vector2d<f64>, b: Vector2d<f64>| (a.x - b.x).rem_euclid(2.0);
+    assert_approx_eq!(dx(base[3], base[9]), dx(shifted[3], shifted[9]), 1e-9);
+}
@@ -0,0 +1,80 @@
+// -----------------------------------------
+//     Testing SAT Collision Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use vectorlib::math::sat_collision_module::{sat_manifold, sat_overlap};
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn square(center: Vector2d<f64>, half: f64) -> Vec<Vector2d<f64>> {
+    vec![
+        center + Vector2d::new(-half, -half),
+        center + Vector2d::new(half, -half),
+        center + Vector2d::new(half, half),
+        center + Vector2d::new(-half, half),
+    ]
+}
+
+#[test]
+fn sat_separated_and_touching() {
+    let a = square(Vector2d::new(0.0, 0.0), 1.0);
+    let b = square(Vector2d::new(3.0, 0.0), 1.0);
+    let touching = square(Vector2d::new(2.0, 0.0), 1.0);
+    assert!(sat_overlap(&a, &b).is_none());
+    assert!(sat_overlap(&a, &touching).is_none());
+    assert!(sat_manifold(&a, &b).is_none());
+
+    // Separated only along a triangle edge normal, not along the box axes.
+    let triangle: Vec<Vector2d<f64>> = vec![
+        Vector2d::new(1.5, 1.5),
+        Vector2d::new(3.0, 0.2),
+        Vector2d::new(3.0, 3.0),
+    ];
+    assert!(sat_overlap(&a, &triangle).is_none());
+}
+
+#[test]
+fn sat_minimum_translation() {
+    let a = square(Vector2d::new(0.0, 0.0), 1.0);
+    let mut b = square(Vector2d::new(0.2, 1.7), 1.0);
+    // Winding order does not matter.
+    b.reverse();
+    let mtv = sat_overlap(&a, &b).unwrap();
+    assert_vec2_equal!(mtv.axis, Vector2d::new(0.0, 1.0));
+    assert_approx_eq!(mtv.depth, 0.3, 1e-9);
+
+    let reversed = sat_overlap(&b, &a).unwrap();
+    assert_vec2_equal!(reversed.axis, Vector2d::new(0.0, -1.0));
+
+    // Moving b along the axis by the depth resolves the overlap.
+    let moved: Vec<_> = b
+        .iter()
+        .map(|&v| v + mtv.axis * (mtv.depth + 1e-9))
+        .collect();
+    assert!(sat_overlap(&a, &moved).is_none());
+}
+
+#[test]
+fn sat_contact_manifolds() {
+    let ground = square(Vector2d::new(0.0, -1.0), 1.0);
+    let resting = square(Vector2d::new(0.5, 0.9), 1.0);
+    let manifold = sat_manifold(&ground, &resting).unwrap();
+    assert_vec2_equal!(manifold.normal, Vector2d::new(0.0, 1.0));
+    assert_eq!(manifold.contacts.len(), 2);
+    for contact in &manifold.contacts {
+        assert_approx_eq!(contact.depth, 0.1, 1e-9);
+        assert_approx_eq!(contact.position.y, -0.1, 1e-9);
+    }
+
+    // A diamond poking a box edge yields a single contact at its tip.
+    let diamond: Vec<Vector2d<f64>> = vec![
+        Vector2d::new(0.0, -0.2),
+        Vector2d::new(1.0, 0.8),
+        Vector2d::new(0.0, 1.8),
+        Vector2d::new(-1.0, 0.8),
+    ];
+    let manifold = sat_manifold(&ground, &diamond).unwrap();
+    assert_eq!(manifold.contacts.len(), 1);
+    assert_vec2_equal!(manifold.contacts[0].position, Vector2d::new(0.0, -0.2));
+    assert_approx_eq!(manifold.contacts[0].depth, 0.2, 1e-9);
+}
@@ -0,0 +1,79 @@
+// -----------------------------------------
+//     Testing Serde Support
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use serde::{Deserialize, Serialize};
+use vectorlib::math::vector2d_module::Vector2d;
+use vectorlib::math::vector2d_verbose_module::VerboseVector2d;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Scene {
+    #[serde(with = "vectorlib::math::serde_module::compact")]
+    camera: Vector2d<f32>,
+    #[serde(with = "vectorlib::math::serde_module::compact_vec")]
+    path: Vec<Vector2d<f32>>,
+    spawn: Vector2d<f32>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct TileMap {
+    #[serde(with = "vectorlib::math::serde_module::compact")]
+    size: Vector2d<i32>,
+    #[serde(with = "vectorlib::math::serde_module::compact_vec")]
+    spawns: Vec<Vector2d<u16>>,
+}
+
+#[test]
+fn vector_round_trips_in_struct_form() {
+    let v = Vector2d::new(0.1, -3.25e10);
+    let json = serde_json::to_string(&v).unwrap();
+    assert_eq!(json, r#"{"x":0.1,"y":-32500000000.0}"#);
+    assert_eq!(serde_json::from_str::<Vector2d<f64>>(&json).unwrap(), v);
+
+    // The array form is accepted as well, integers included.
+    let from_array: Vector2d<i32> = serde_json::from_str("[4, -7]").unwrap();
+    assert_eq!(from_array, Vector2d::new(4, -7));
+    assert!(serde_json::from_str::<Vector2d<f64>>(r#"{"x":1.0}"#).is_err());
+    assert!(serde_json::from_str::<Vector2d<f64>>("[1.0, 2.0, 3.0]").is_err());
+}
+
+#[test]
+fn compact_fields_use_the_array_form() {
+    let scene = Scene {
+        camera: Vector2d::new(1.0, 2.0),
+        path: vec![Vector2d::new(0.0, 0.0), Vector2d::new(0.5, -1.5)],
+        spawn: Vector2d::new(3.0, 4.0),
+    };
+    let json = serde_json::to_string(&scene).unwrap();
+    assert_eq!(
+        json,
+        r#"{"camera":[1.0,2.0],"path":[[0.0,0.0],[0.5,-1.5]],"spawn":{"x":3.0,"y":4.0}}"#
+    );
+    assert_eq!(serde_json::from_str::<Scene>(&json).unwrap(), scene);
+    assert!(serde_json::from_str::<Scene>(
+        r#"{"camera":{"x":1.0,"y":2.0},"path":[],"spawn":[3.0,4.0]}"#
+    )
+    .is_err());
+}
+
+#[test]
+fn compact_fields_accept_integer_components() {
+    let map = TileMap {
+        size: Vector2d::new(64, -3),
+        spawns: vec![Vector2d::new(1, 2), Vector2d::new(30, 40)],
+    };
+    let json = serde_json::to_string(&map).unwrap();
+    assert_eq!(json, r#"{"size":[64,-3],"spawns":[[1,2],[30,40]]}"#);
+    assert_eq!(serde_json::from_str::<TileMap>(&json).unwrap(), map);
+}
+
+#[test]
+fn verbose_vector_round_trips() {
+    let verbose = VerboseVector2d::new(Vector2d::new(1.5, 2.5), false);
+    let json = serde_json::to_string(&verbose).unwrap();
+    assert_eq!(json, r#"{"vector":{"x":1.5,"y":2.5},"verbose":false}"#);
+    let back: VerboseVector2d<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, verbose);
+    assert_eq!(back.into_inner(), Some(Vector2d::new(1.5, 2.5)));
+}
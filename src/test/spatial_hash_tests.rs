@@ -0,0 +1,143 @@
+// -----------------------------------------
+//     Testing Spatial Hash Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use rand::{Rng, SeedableRng};
+use vectorlib::math::shape_module::Aabb;
+use vectorlib::math::spatial_hash_module::SpatialHash;
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn random_points(count: usize, seed: u64) -> Vec<Vector2d<f64>> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+    (0..count)
+        .map(|_| Vector2d::new(rng.gen_range(-10.0..10.0), rng.gen_range(-10.0..10.0)))
+        .collect()
+}
+
+#[test]
+fn radius_query_matches_brute_force() {
+    let points = random_points(500, 7);
+    let mut grid: SpatialHash<f64, usize> = SpatialHash::new(1.5).unwrap();
+    for (i, p) in points.iter().enumerate() {
+        grid.insert_point(i, *p);
+    }
+    let center = Vector2d::new(1.0, -2.0);
+    let mut found = grid.query_radius(center, 3.0);
+    found.sort();
+    let expected: Vec<usize> = (0..points.len())
+        .filter(|&i| points[i].distance(center) <= 3.0)
+        .collect();
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn pairs_match_brute_force_without_duplicates() {
+    let points = random_points(300, 11);
+    let mut grid: SpatialHash<f64, usize> = SpatialHash::new(0.8).unwrap();
+    for (i, p) in points.iter().enumerate() {
+        grid.insert_point(i, *p);
+    }
+    let mut pairs: Vec<(usize, usize)> = grid
+        .pairs_within(1.0)
+        .into_iter()
+        .map(|(a, b)| (a.min(b), a.max(b)))
+        .collect();
+    pairs.sort();
+    let mut expected = Vec::new();
+    for i in 0..points.len() {
+        for j in i + 1..points.len() {
+            if points[i].distance(points[j]) <= 1.0 {
+                expected.push((i, j));
+            }
+        }
+    }
+    assert_eq!(pairs, expected);
+}
+
+#[test]
+fn boxes_spanning_cells_are_reported_once() {
+    let mut grid: SpatialHash<f64, &str> = SpatialHash::new(1.0).unwrap();
+    grid.insert_aabb(
+        "wall",
+        Aabb::new(Vector2d::new(-3.5, -0.2), Vector2d::new(3.5, 0.2)),
+    );
+    grid.insert_point("dot", Vector2d::new(0.0, 2.0));
+    let area = Aabb::new(Vector2d::new(-5.0, -5.0), Vector2d::new(5.0, 1.0));
+    assert_eq!(grid.query_rect(&area), vec!["wall"]);
+    assert_eq!(
+        grid.query_radius(Vector2d::new(3.0, 1.0), 0.9),
+        vec!["wall"]
+    );
+    assert_eq!(grid.pairs_within(1.7), Vec::<(&str, &str)>::new());
+    assert_eq!(grid.pairs_within(1.9), vec![("wall", "dot")]);
+}
+
+#[test]
+fn update_and_remove_keep_the_grid_consistent() {
+    let mut grid: SpatialHash<f64, u32> = SpatialHash::new(1.0).unwrap();
+    for i in 0..10 {
+        grid.insert_point(i, Vector2d::new(i as f64, 0.0));
+    }
+    assert!(grid.remove(0));
+    assert!(!grid.remove(0));
+    grid.update_point(9, Vector2d::new(0.2, 0.1));
+    assert_eq!(grid.len(), 9);
+    let mut near = grid.query_radius(Vector2d::new(0.0, 0.0), 1.0);
+    near.sort();
+    assert_eq!(near, vec![1, 9]);
+    assert_eq!(grid.bounds(9).unwrap().min, Vector2d::new(0.2, 0.1));
+
+    grid.clear();
+    assert!(grid.is_empty());
+    assert!(grid.query_radius(Vector2d::new(0.0, 0.0), 100.0).is_empty());
+    assert!(SpatialHash::<f64, u32>::new(0.0).is_err());
+}
+
+#[test]
+fn huge_and_far_boxes_stay_cheap_and_findable() {
+    let mut grid: SpatialHash<f64, u32> = SpatialHash::new(1.0).unwrap();
+    for (i, p) in random_points(50, 9).into_iter().enumerate() {
+        grid.insert_point(i as u32, p);
+    }
+    // Far more cells than entries: answered by scanning the entries.
+    assert_eq!(grid.query_radius(Vector2d::new(0.0, 0.0), 1e6).len(), 50);
+
+    let huge = Aabb::new(Vector2d::new(-1e9, -1e9), Vector2d::new(1e9, 1e9));
+    grid.insert_aabb(100, huge);
+    assert!(grid.bucket_count() <= 50);
+    assert!(grid
+        .query_radius(Vector2d::new(3.5, -2.5), 0.1)
+        .contains(&100));
+    assert_eq!(grid.pairs_within(0.0).len(), 50);
+
+    // Coordinates beyond the cell range saturate instead of landing in cell 0.
+    let far = Aabb::new(Vector2d::new(-1e20, 0.0), Vector2d::new(-1e20, 0.0));
+    grid.insert_aabb(200, far);
+    assert!(grid
+        .query_rect(&Aabb::new(
+            Vector2d::new(-2e20, -1.0),
+            Vector2d::new(-5e19, 1.0)
+        ))
+        .contains(&200));
+
+    assert!(grid.remove(100));
+    assert!(!grid
+        .query_radius(Vector2d::new(3.5, -2.5), 0.1)
+        .contains(&100));
+    assert!(grid.remove(200));
+    assert_eq!(grid.len(), 50);
+}
+
+#[test]
+fn moving_objects_drop_empty_buckets() {
+    let mut grid: SpatialHash<f64, u32> = SpatialHash::new(1.0).unwrap();
+    grid.insert_point(1, Vector2d::new(0.5, 0.5));
+    for step in 0..1000 {
+        grid.update_point(1, Vector2d::new(step as f64 + 0.5, 0.5));
+    }
+    assert_eq!(grid.bucket_count(), 1);
+    assert_eq!(grid.query_radius(Vector2d::new(999.5, 0.5), 0.1), vec![1]);
+    assert!(grid.remove(1));
+    assert_eq!(grid.bucket_count(), 0);
+}
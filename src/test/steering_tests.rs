@@ -0,0 +1,165 @@
+// -----------------------------------------
+//     Testing Steering Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use rand::SeedableRng;
+use vectorlib::math::shape_module::Circle;
+use vectorlib::math::steering_module::{
+    prioritized_sum, truncate, weighted_sum, Agent, FlockWeights, Wander,
+};
+use vectorlib::math::vector2d_module::Vector2d;
+
+const DT: f64 = 1.0 / 30.0;
+
+#[test]
+fn seek_flee_and_arrive() {
+    let agent: Agent<f64> = Agent::new(Vector2d::new(0.0, 0.0), 4.0, 2.0);
+    let seek = agent.seek(Vector2d::new(10.0, 0.0));
+    assert_approx_eq!(seek.x, 2.0, 1e-12);
+    assert!(agent.flee(Vector2d::new(1.0, 0.0), 5.0).x < 0.0);
+    assert_eq!(
+        agent.flee(Vector2d::new(10.0, 0.0), 5.0),
+        Vector2d::new(0.0, 0.0)
+    );
+
+    // Arrive comes to rest on the target without overshooting much.
+    let mut agent = agent;
+    let target = Vector2d::new(10.0, 5.0);
+    let mut max_overshoot: f64 = 0.0;
+    for _ in 0..600 {
+        let force = agent.arrive(target, 8.0);
+        agent.apply_force(force, DT);
+        max_overshoot = max_overshoot.max(agent.position.x - target.x);
+        assert!(agent.velocity.magnitude() <= agent.max_speed + 1e-12);
+    }
+    assert!(agent.position.distance(target) < 0.05);
+    assert!(agent.velocity.magnitude() < 0.05);
+    assert!(max_overshoot < 0.5);
+}
+
+#[test]
+fn pursuit_catches_a_moving_target_and_evade_escapes() {
+    let mut hunter: Agent<f64> = Agent::new(Vector2d::new(0.0, 0.0), 6.0, 8.0);
+    let mut prey = Agent::new(Vector2d::new(10.0, 0.0), 3.0, 8.0);
+    prey.velocity = Vector2d::new(0.0, 3.0);
+    let mut caught = false;
+    for _ in 0..300 {
+        let force = hunter.pursue(prey.position, prey.velocity);
+        hunter.apply_force(force, DT);
+        prey.position += prey.velocity * DT;
+        if hunter.position.distance(prey.position) < 0.5 {
+            caught = true;
+            break;
+        }
+    }
+    assert!(caught);
+
+    let runner: Agent<f64> = Agent::new(Vector2d::new(0.0, 0.0), 3.0, 8.0);
+    let evade = runner.evade(Vector2d::new(-2.0, 0.0), Vector2d::new(1.0, 0.0), 10.0);
+    assert!(evade.x > 0.0);
+}
+
+#[test]
+fn obstacle_avoidance_and_path_following() {
+    let mut agent: Agent<f64> = Agent::new(Vector2d::new(0.0, 0.1), 3.0, 6.0);
+    agent.velocity = Vector2d::new(3.0, 0.0);
+    let rock = Circle::new(Vector2d::new(5.0, 0.0), 1.0);
+    let mut closest = f64::MAX;
+    for _ in 0..150 {
+        let force = prioritized_sum(
+            &[
+                (agent.avoid_obstacles(&[rock], 3.0, 0.3), 1.0),
+                (agent.seek(Vector2d::new(12.0, 0.0)), 1.0),
+            ],
+            agent.max_force,
+        );
+        agent.apply_force(force, DT);
+        closest = closest.min(agent.position.distance(rock.center));
+    }
+    assert!(closest > 1.0, "closest {closest}");
+
+    let path = vec![
+        Vector2d::new(0.0, 0.0),
+        Vector2d::new(10.0, 0.0),
+        Vector2d::new(10.0, 10.0),
+    ];
+    let mut follower: Agent<f64> = Agent::new(Vector2d::new(0.0, 2.0), 3.0, 6.0);
+    let mut worst: f64 = 0.0;
+    for step in 0..400 {
+        let force = follower.follow_path(&path, 0.5, 0.5);
+        follower.apply_force(force, DT);
+        if step > 60 {
+            let d = path
+                .windows(2)
+                .map(|s| {
+                    let t = ((follower.position - s[0]).dot_product(s[1] - s[0]) / 100.0)
+                        .clamp(0.0, 1.0);
+                    follower.position.distance(s[0] + (s[1] - s[0]) * t)
+                })
+                .fold(f64::MAX, f64::min);
+            worst = worst.max(d);
+        }
+    }
+    assert!(follower.position.distance(path[2]) < 0.6);
+    assert!(worst < 1.5, "worst {worst}");
+}
+
+#[test]
+fn flocking_aligns_and_keeps_distance() {
+    let mut flock: Vec<Agent<f64>> = (0..16)
+        .map(|i| {
+            let mut a = Agent::new(
+                Vector2d::new((i % 4) as f64 * 1.5, (i / 4) as f64 * 1.5),
+                2.0,
+                3.0,
+            );
+            a.velocity = Vector2d::new(1.0, 0.0).rotate(i as f64 * 0.4);
+            a
+        })
+        .collect();
+    let weights = FlockWeights::new(6.0);
+    for _ in 0..400 {
+        let forces: Vec<Vector2d<f64>> = flock.iter().map(|a| a.flock(&flock, &weights)).collect();
+        for (agent, force) in flock.iter_mut().zip(forces) {
+            agent.apply_force(force, DT);
+        }
+    }
+    let mean = flock
+        .iter()
+        .fold(Vector2d::new(0.0, 0.0), |acc, a| acc + a.heading().unwrap())
+        * (1.0 / flock.len() as f64);
+    assert!(mean.magnitude() > 0.9, "alignment {}", mean.magnitude());
+    for (i, a) in flock.iter().enumerate() {
+        for b in &flock[i + 1..] {
+            assert!(a.position.distance(b.position) > 0.5);
+        }
+    }
+}
+
+#[test]
+fn wander_and_combination_helpers() {
+    let agent: Agent<f64> = Agent::new(Vector2d::new(0.0, 0.0), 2.0, 1.0);
+    let mut wander = Wander::new(2.0, 1.0, 0.5);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+    for _ in 0..10 {
+        assert!(wander.steer(&agent, &mut rng).magnitude() <= 1.0 + 1e-12);
+    }
+    assert!(wander.angle != 0.0);
+
+    let a = Vector2d::new(3.0, 0.0);
+    let b = Vector2d::new(0.0, 3.0);
+    assert_eq!(
+        weighted_sum(&[(a, 1.0), (b, 0.0)], 2.0),
+        Vector2d::new(2.0, 0.0)
+    );
+    // The first force uses the whole budget, so the second is dropped.
+    assert_eq!(
+        prioritized_sum(&[(a, 1.0), (b, 1.0)], 2.0),
+        Vector2d::new(2.0, 0.0)
+    );
+    assert_eq!(
+        truncate(Vector2d::new(0.0, 0.0), 1.0),
+        Vector2d::new(0.0, 0.0)
+    );
+}
@@ -0,0 +1,133 @@
+// -----------------------------------------
+//     Testing SVG Debug Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use vectorlib::math::svg_debug_module::{Style, SvgDebugCanvas};
+use vectorlib::math::svg_path_module::SvgPath;
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn v(x: f64, y: f64) -> Vector2d<f64> {
+    Vector2d::new(x, y)
+}
+
+#[test]
+fn view_box_fits_the_drawing() {
+    let mut canvas = SvgDebugCanvas::new();
+    canvas.margin = 0.0;
+    canvas.y_up = false;
+    canvas.segment(v(0.0, 0.0), v(10.0, 5.0), &Style::default());
+    let svg = canvas.to_svg();
+    assert!(svg.contains(r#"width="800" height="400" viewBox="0 0 10 5""#));
+
+    canvas.margin = 0.1;
+    let svg = canvas.to_svg();
+    assert!(svg.contains(r#"viewBox="-1 -1 12 7""#));
+}
+
+#[test]
+fn tiny_drawings_keep_their_coordinates() {
+    let mut canvas = SvgDebugCanvas::new();
+    canvas.margin = 0.0;
+    canvas.y_up = false;
+    canvas.segment(v(0.0, 0.0), v(2e-7, 1e-7), &Style::default());
+    let svg = canvas.to_svg();
+    assert!(svg.contains(r#"viewBox="0 0 0.0000002 0.0000001""#));
+    assert!(svg.contains(r#"x1="0" y1="0" x2="0.0000002" y2="0.0000001""#));
+}
+
+#[test]
+fn y_up_flips_coordinates() {
+    let mut canvas = SvgDebugCanvas::new();
+    canvas.margin = 0.0;
+    canvas.segment(v(0.0, 1.0), v(4.0, 3.0), &Style::default());
+    let svg = canvas.to_svg();
+    assert!(svg.contains(r#"viewBox="0 -3 4 2""#));
+    assert!(svg.contains(r#"x1="0" y1="-1" x2="4" y2="-3""#));
+
+    let path: SvgPath = "M 0 1 L 4 3".parse().unwrap();
+    canvas.clear();
+    canvas.path(&path, &Style::new("blue"));
+    assert!(canvas.to_svg().contains(r#"d="M0 -1 L4 -3""#));
+}
+
+#[test]
+fn every_primitive_is_written() {
+    let mut canvas = SvgDebugCanvas::new();
+    assert!(canvas.is_empty());
+    let style = Style::filled("#3366ff");
+    canvas
+        .arrow(v(0.0, 0.0), v(2.0, 0.0), &style)
+        .point(v(1.0, 1.0), &style)
+        .polyline(&[v(0.0, 0.0), v(1.0, 2.0), v(2.0, 0.0)], &style)
+        .polygon(&[v(0.0, 0.0), v(1.0, 0.0), v(0.0, 1.0)], &style)
+        .circle(v(5.0, 5.0), 2.0, &style);
+    assert_eq!(canvas.len(), 5);
+
+    let svg = canvas.to_svg();
+    assert_eq!(svg.matches("<line").count(), 1);
+    // The arrow head and the filled triangle.
+    assert_eq!(svg.matches("<polygon").count(), 2);
+    assert_eq!(svg.matches("<polyline").count(), 1);
+    assert_eq!(svg.matches("<circle").count(), 2);
+    assert!(svg.contains(r#"<polyline points="0,0 1,-2 2,0" fill="none""#));
+
+    let bounds = canvas.bounds().unwrap();
+    assert_approx_eq!(bounds.min.x, 0.0);
+    assert_approx_eq!(bounds.min.y, 0.0);
+    assert_approx_eq!(bounds.max.x, 7.0);
+    assert_approx_eq!(bounds.max.y, 7.0);
+}
+
+#[test]
+fn text_and_colors_are_escaped() {
+    let mut canvas = SvgDebugCanvas::new();
+    canvas.background = None;
+    canvas.text(v(0.0, 0.0), r#"a < b & "c""#, &Style::new("red"));
+    let svg = canvas.to_svg();
+    assert!(svg.contains("a &lt; b &amp; &quot;c&quot;</text>"));
+    assert!(!svg.contains("<rect"));
+}
+
+#[test]
+fn empty_canvas_and_save() {
+    let canvas = SvgDebugCanvas::new();
+    assert!(canvas.bounds().is_none());
+    let svg = canvas.to_svg();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.ends_with("</svg>\n"));
+
+    let path = std::env::temp_dir().join("vectorlib_svg_debug_test.svg");
+    canvas.save(&path).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), svg);
+    let _ = std::fs::remove_file(&path);
+
+    assert!(canvas.save("/nonexistent/dir/out.svg").is_err());
+}
+
+#[test]
+fn non_finite_primitives_are_skipped() {
+    let mut canvas = SvgDebugCanvas::new();
+    let style = Style::new("black");
+    canvas
+        .segment(v(0.0, 0.0), v(2.0, 1.0), &style)
+        .point(v(f64::NAN, 1.0), &style)
+        .arrow(v(0.0, 0.0), v(f64::INFINITY, 0.0), &style)
+        .polyline(&[v(0.0, 0.0), v(1.0, f64::NEG_INFINITY)], &style)
+        .circle(v(1.0, 1.0), f64::INFINITY, &style)
+        .path(&"M 0 0 L 1 1".parse::<SvgPath>().unwrap(), &style);
+    canvas.circle(v(f64::MAX, 0.0), f64::MAX, &style);
+
+    let bounds = canvas.bounds().unwrap();
+    assert_eq!((bounds.min, bounds.max), (v(0.0, 0.0), v(2.0, 1.0)));
+    let svg = canvas.to_svg();
+    assert!(!svg.contains("NaN") && !svg.contains("inf"), "{svg}");
+    assert_eq!(svg.matches("<line").count(), 1);
+    assert_eq!(svg.matches("<path").count(), 1);
+    assert!(!svg.contains("<circle") && !svg.contains("<polyline"));
+
+    canvas.clear();
+    canvas.point(v(f64::NAN, f64::NAN), &style);
+    assert!(canvas.bounds().is_none());
+    assert!(!canvas.to_svg().contains("NaN"));
+}
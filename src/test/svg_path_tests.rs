@@ -0,0 +1,159 @@
+// -----------------------------------------
+//     Testing SVG Path Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use vectorlib::math::svg_path_module::{arc_to_cubics, PathSegment, SvgPath};
+use vectorlib::math::vector2d_module::Vector2d;
+
+fn v(x: f64, y: f64) -> Vector2d<f64> {
+    Vector2d::new(x, y)
+}
+
+fn cubic_at(from: Vector2d<f64>, segment: &PathSegment, t: f64) -> Vector2d<f64> {
+    let PathSegment::Cubic {
+        control1,
+        control2,
+        to,
+    } = *segment
+    else {
+        panic!("expected a cubic");
+    };
+    let s = 1.0 - t;
+    from * (s * s * s)
+        + control1 * (3.0 * s * s * t)
+        + control2 * (3.0 * s * t * t)
+        + to * (t * t * t)
+}
+
+#[test]
+fn relative_absolute_and_shorthand_commands() {
+    let absolute = SvgPath::parse(
+        "M10,20 L30 20 H40 V50 C40 60 50 70 60 70 S80 80 80 90 Q90 100 100 90 T120 90 Z",
+    )
+    .unwrap();
+    let relative =
+        SvgPath::parse("m10 20l20 0h10v30c0 10 10 20 20 20s20 10 20 20q10 10 20 0t20 0z").unwrap();
+    assert_eq!(absolute, relative);
+    let segments = &absolute.subpaths[0].segments;
+    assert_eq!(segments.len(), 7);
+    // S reflects the previous cubic control point, T the previous quadratic one.
+    assert_eq!(
+        segments[4],
+        PathSegment::Cubic {
+            control1: v(70.0, 70.0),
+            control2: v(80.0, 80.0),
+            to: v(80.0, 90.0)
+        }
+    );
+    assert_eq!(
+        segments[6],
+        PathSegment::Quadratic {
+            control: v(110.0, 80.0),
+            to: v(120.0, 90.0)
+        }
+    );
+    assert!(absolute.subpaths[0].closed);
+
+    // Implicit linetos after a moveto, packed numbers, and drawing on from
+    // the start of a closed subpath.
+    let compact = SvgPath::parse("M1-2.5.5 3e1,4 0z l1 1 m-1-1 2 2").unwrap();
+    assert_eq!(compact.subpaths.len(), 3);
+    assert_eq!(
+        compact.subpaths[0].segments,
+        vec![
+            PathSegment::Line { to: v(0.5, 30.0) },
+            PathSegment::Line { to: v(4.0, 0.0) }
+        ]
+    );
+    assert_eq!(compact.subpaths[1].start, v(1.0, -2.5));
+    assert_eq!(compact.subpaths[1].end(), v(2.0, -1.5));
+    assert_eq!(compact.subpaths[2].start, v(1.0, -2.5));
+    assert_eq!(compact.subpaths[2].end(), v(3.0, -0.5));
+    assert_eq!(SvgPath::parse("  ").unwrap(), SvgPath::default());
+}
+
+#[test]
+fn arcs_become_cubics_on_the_ellipse() {
+    // Rotated ellipse with radii 30 and 10, centred on the origin.
+    let (rx, ry, phi) = (30.0, 10.0, 30f64.to_radians());
+    let on = |t: f64| {
+        v(
+            phi.cos() * rx * t.cos() - phi.sin() * ry * t.sin(),
+            phi.sin() * rx * t.cos() + phi.cos() * ry * t.sin(),
+        )
+    };
+    let (from, to) = (on(0.3), on(2.0));
+    for (large, sweep, pieces) in [(false, true, 2), (true, false, 3)] {
+        let segments = arc_to_cubics(from, v(rx, ry), 30.0, large, sweep, to);
+        assert_eq!(segments.len(), pieces);
+        assert_eq!(segments.last().unwrap().end(), to);
+        let mut start = from;
+        for segment in &segments {
+            for i in 0..=10 {
+                let p = cubic_at(start, segment, i as f64 / 10.0);
+                // Back in the ellipse frame the point has unit radius.
+                let local = v(
+                    phi.cos() * p.x + phi.sin() * p.y,
+                    -phi.sin() * p.x + phi.cos() * p.y,
+                );
+                let radius = ((local.x / rx).powi(2) + (local.y / ry).powi(2)).sqrt();
+                assert_approx_eq!(radius, 1.0, 1e-3);
+            }
+            start = segment.end();
+        }
+    }
+    // Radii too small are scaled up to a half ellipse; zero radii draw a line.
+    let scaled = arc_to_cubics(v(0.0, 0.0), v(1.0, 1.0), 0.0, false, true, v(10.0, 0.0));
+    assert_eq!(scaled.len(), 2);
+    assert_approx_eq!(scaled[0].end().y, -5.0, 1e-9);
+    assert_eq!(
+        arc_to_cubics(v(0.0, 0.0), v(0.0, 4.0), 0.0, false, true, v(1.0, 1.0)),
+        vec![PathSegment::Line { to: v(1.0, 1.0) }]
+    );
+    assert!(arc_to_cubics(v(2.0, 2.0), v(1.0, 1.0), 0.0, false, true, v(2.0, 2.0)).is_empty());
+
+    // Flags may be packed against the following numbers.
+    let packed = SvgPath::parse("M0 0a5 5 0 1110 0").unwrap();
+    let spaced = SvgPath::parse("M0 0 A 5 5 0 1 1 10 0").unwrap();
+    assert_eq!(packed, spaced);
+}
+
+#[test]
+fn serializer_round_trips() {
+    let source = "M0.1 -3 l1e-3 2 c1 2 3 4 5 6 q1 1 2 0 a4 2 10 0 1 8 0 Z M 5 5 h 0.333";
+    let path = SvgPath::parse(source).unwrap();
+    let written = path.to_string();
+    assert!(written.starts_with("M0.1 -3 L0.101 -1 C"));
+    assert_eq!(SvgPath::parse(&written).unwrap(), path);
+    assert_eq!(
+        format!("{:.1}", SvgPath::parse("M1 2 L3.14159 0").unwrap()),
+        "M1.0 2.0 L3.1 0.0"
+    );
+}
+
+#[test]
+fn errors_point_at_the_offending_byte() {
+    let cases = [
+        ("L 1 2", 0, "moveto"),
+        ("M 1 2 L 3", 9, "Expected a number"),
+        ("M 1 2 K 3 4", 6, "Unknown command"),
+        ("M 1 2 3", 7, "Expected a number"),
+        ("M 1 2 Z 3 4", 8, "Expected a command"),
+        ("M 0 0 A 1 1 0 2 0 3 3", 14, "arc flag"),
+        ("M 1e 2", 3, "exponent"),
+        ("M 1 2 L 1e999 0", 8, "out of range"),
+        ("M 1 2 L 3 4,, 5 6", 12, "found ','"),
+    ];
+    for (data, offset, message) in cases {
+        let error = SvgPath::parse(data).unwrap_err();
+        assert_eq!(error.offset, offset, "{}", data);
+        assert!(
+            error.message.contains(message),
+            "{}: {}",
+            data,
+            error.message
+        );
+        assert!(error.to_string().starts_with("[::ERROR::]"));
+    }
+}
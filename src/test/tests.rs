@@ -14,14 +14,6 @@ use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
 use vectorlib::math::vector2d_module::Vector2d;
 use vectorlib::math::vector2d_verbose_module::VerboseVector2d;
 
-macro_rules! assert_vec2_equal {
-    ($expected:expr, $actual:expr) => {
-        let tolerance = 0.0001;
-        assert_approx_eq!($expected.x, $actual.x, tolerance);
-        assert_approx_eq!($expected.y, $actual.y, tolerance);
-    };
-}
-
 #[test]
 #[allow(clippy::unnecessary_cast)]
 fn vector_intializtion() {
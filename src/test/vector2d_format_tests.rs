@@ -0,0 +1,58 @@
+// -----------------------------------------
+//     Testing Vector2d Formatting
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use vectorlib::math::vector2d_module::Vector2d;
+
+#[test]
+fn display_defaults_to_three_decimals() {
+    let v = Vector2d::new(1.0, -2.5);
+    assert_eq!(v.to_string(), "< 1.000, -2.500 >");
+    assert_eq!(
+        format!("{}", Vector2d::new(1.0f32, 2.0)),
+        "< 1.000, 2.000 >"
+    );
+}
+
+#[test]
+fn display_honors_formatter_flags() {
+    let v = Vector2d::new(std::f64::consts::PI, -1.0);
+    assert_eq!(format!("{:.6}", v), "< 3.141593, -1.000000 >");
+    assert_eq!(format!("{:.0}", v), "< 3, -1 >");
+    assert_eq!(format!("{:+}", v), "< +3.142, -1.000 >");
+    assert_eq!(format!("{:8.2}", v), "<     3.14,    -1.00 >");
+    assert_eq!(format!("{:<8.2}|", v), "< 3.14    , -1.00    >|");
+    assert_eq!(format!("{:*^8.1}", v), "< **3.1***, **-1.0** >");
+    assert_eq!(format!("{:+08.2}", v), "< +0003.14, -0001.00 >");
+    assert_eq!(format!("{:.*}", 1, v), "< 3.1, -1.0 >");
+    // Text wider than the width is never cut.
+    assert_eq!(format!("{:2.3}", v), "< 3.142, -1.000 >");
+}
+
+#[test]
+fn scientific_notation() {
+    let v = Vector2d::new(123456.0, 0.00042);
+    assert_eq!(format!("{:e}", v), "< 1.23456e5, 4.2e-4 >");
+    assert_eq!(format!("{:E}", v), "< 1.23456E5, 4.2E-4 >");
+    assert_eq!(format!("{:.2e}", v), "< 1.23e5, 4.20e-4 >");
+    assert_eq!(format!("{:+10.1E}", v), "<     +1.2E5,    +4.2E-4 >");
+    let parsed: Vector2d<f64> = format!("{:e}", v).parse().unwrap();
+    assert_eq!(parsed, v);
+}
+
+#[test]
+fn colored_output() {
+    let v = Vector2d::new(-30.0, 40.0);
+    colored::control::set_override(true);
+    let colored = v.to_colored_string(1, 35.0);
+    colored::control::set_override(false);
+    let plain = v.to_colored_string(1, 35.0);
+    colored::control::unset_override();
+
+    assert_eq!(plain, "< -30.0, 40.0 > |50.0|");
+    assert!(colored.contains("\x1b[31m-30.0\x1b[0m"));
+    // Only `y` exceeds the highlight threshold, so only it is bold.
+    assert!(colored.contains("\x1b[1;32m40.0\x1b[0m"));
+    assert!(colored.contains("\x1b[36m|50.0|\x1b[0m"));
+}
@@ -0,0 +1,96 @@
+// -----------------------------------------
+//     Testing Vector2d Parsing
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use vectorlib::math::vector2d_module::{ParseVector2dError, Vector2d};
+
+#[test]
+fn accepted_forms() {
+    let expected = Vector2d::new(1.5, -2.0);
+    for text in [
+        "< 1.5, -2 >",
+        "<1.5,-2>",
+        "(1.5, -2.0)",
+        "[ 1.5 , -2 ]",
+        "1.5,-2",
+        "  1.5e0 ,\t-2e0  ",
+    ] {
+        assert_eq!(text.parse::<Vector2d<f64>>(), Ok(expected), "{text}");
+    }
+    let v: Vector2d<f64> = "(inf, -inf)".parse().unwrap();
+    assert!(v.x.is_infinite() && v.x > 0.0 && v.y < 0.0);
+}
+
+#[test]
+fn display_output_parses_back() {
+    let v = Vector2d::new(1.0, 2.25);
+    assert_eq!(v.to_string().parse::<Vector2d<f64>>(), Ok(v));
+
+    // The default display rounds to three decimals; the lossless one does not.
+    let v = Vector2d::new(std::f64::consts::PI, -0.000123);
+    assert_ne!(v.to_string().parse::<Vector2d<f64>>(), Ok(v));
+    assert_eq!(v.lossless().to_string().parse::<Vector2d<f64>>(), Ok(v));
+}
+
+#[test]
+fn lossless_round_trip_is_exact() {
+    let mut rng = StdRng::seed_from_u64(48);
+    for _ in 0..1000 {
+        let v = Vector2d::new(
+            rng.gen_range(-1e6..1e6) * 10f64.powi(rng.gen_range(-20..20)),
+            rng.gen::<f64>() - 0.5,
+        );
+        let parsed: Vector2d<f64> = v.lossless().to_string().parse().unwrap();
+        assert_eq!(parsed.x.to_bits(), v.x.to_bits());
+        assert_eq!(parsed.y.to_bits(), v.y.to_bits());
+
+        let w = Vector2d::new(v.x as f32, v.y as f32);
+        assert_eq!(w.lossless().to_string().parse::<Vector2d<f32>>(), Ok(w));
+    }
+    let negative_zero: Vector2d<f64> = Vector2d::new(-0.0, 0.0)
+        .lossless()
+        .to_string()
+        .parse()
+        .unwrap();
+    assert!(negative_zero.x.is_sign_negative());
+}
+
+#[test]
+fn typed_errors() {
+    let parse = |text: &str| text.parse::<Vector2d<f64>>();
+    assert_eq!(parse(""), Err(ParseVector2dError::Empty));
+    assert_eq!(parse("   "), Err(ParseVector2dError::Empty));
+    assert_eq!(
+        parse("(1, 2"),
+        Err(ParseVector2dError::UnbalancedDelimiters)
+    );
+    assert_eq!(
+        parse("<1, 2)"),
+        Err(ParseVector2dError::UnbalancedDelimiters)
+    );
+    assert_eq!(
+        parse("1, 2]"),
+        Err(ParseVector2dError::UnbalancedDelimiters)
+    );
+    assert_eq!(parse("<1>"), Err(ParseVector2dError::ComponentCount(1)));
+    assert_eq!(parse("1, 2, 3"), Err(ParseVector2dError::ComponentCount(3)));
+    assert_eq!(
+        parse("< 1, two >"),
+        Err(ParseVector2dError::InvalidComponent {
+            index: 1,
+            text: "two".to_string()
+        })
+    );
+    assert_eq!(
+        parse("(, 2)"),
+        Err(ParseVector2dError::InvalidComponent {
+            index: 0,
+            text: String::new()
+        })
+    );
+    let message = parse("< 1, two >").unwrap_err().to_string();
+    assert_eq!(message, "[::ERROR::] -> Invalid y component \"two\".");
+}
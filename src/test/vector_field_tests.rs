@@ -0,0 +1,116 @@
+// -----------------------------------------
+//     Testing Vector Field Module
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use vectorlib::math::noise_module::{OpenSimplex, Perlin};
+use vectorlib::math::pathfinding_module::OccupancyGrid;
+use vectorlib::math::vector2d_module::Vector2d;
+use vectorlib::math::vector_field_module::{CurlNoiseField, FnField, GridField, VectorField2d};
+
+#[test]
+fn divergence_and_curl_of_analytic_fields() {
+    // F = (x^2 y, -x y^2 + sin x): div = 2xy - 2xy = 0, curl = -y^2 + cos x - x^2.
+    let field = FnField::new(|p: Vector2d<f64>| {
+        Vector2d::new(p.x * p.x * p.y, -p.x * p.y * p.y + p.x.sin())
+    });
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..50 {
+        let p = Vector2d::new(rng.gen_range(-3.0..3.0), rng.gen_range(-3.0..3.0));
+        assert_approx_eq!(field.divergence(p), 0.0, 1e-5);
+        assert_approx_eq!(field.curl(p), -p.y * p.y + p.x.cos() - p.x * p.x, 1e-5);
+    }
+}
+
+#[test]
+fn grid_field_interpolates_linear_fields_exactly() {
+    let linear = FnField::new(|p: Vector2d<f64>| Vector2d::new(2.0 * p.x - p.y, 0.5 * p.y + 1.0));
+    let grid = GridField::from_field(8, 6, Vector2d::new(-2.0, -1.0), 0.5, &linear).unwrap();
+    let mut rng = StdRng::seed_from_u64(2);
+    for _ in 0..100 {
+        // Between the outer cell centres bilinear sampling is exact.
+        let p = Vector2d::new(rng.gen_range(-1.75..1.75), rng.gen_range(-0.75..1.75));
+        let (sampled, expected) = (grid.sample(p), linear.sample(p));
+        assert_approx_eq!(sampled.x, expected.x, 1e-9);
+        assert_approx_eq!(sampled.y, expected.y, 1e-9);
+        assert_approx_eq!(grid.divergence(p), 2.5, 1e-6);
+    }
+    // Outside the centres the border value is held.
+    assert_eq!(grid.sample(Vector2d::new(-10.0, -10.0)), grid.get(0, 0));
+    assert!(GridField::<f64>::new(0, 3, Vector2d::new(0.0, 0.0), 1.0).is_err());
+}
+
+#[test]
+fn streamlines_follow_rk4_and_curl_noise_is_divergence_free() {
+    // Exponential growth: x(t) = x0 * e^t.
+    let growth = FnField::new(|p: Vector2d<f64>| p);
+    let line = growth.streamline(Vector2d::new(1.0, 0.5), 0.01, 100);
+    assert_approx_eq!(line[100].x, 1f64.exp(), 1e-9);
+    assert_approx_eq!(line[100].y, 0.5 * 1f64.exp(), 1e-9);
+    let back = growth.streamline(line[100], -0.01, 100);
+    assert_approx_eq!(back[100].x, 1.0, 1e-9);
+    // A sink stops the trace early.
+    let still = FnField::new(|_: Vector2d<f64>| Vector2d::new(0.0, 0.0));
+    assert_eq!(still.streamline(Vector2d::new(1.0, 1.0), 0.1, 10).len(), 1);
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let perlin = CurlNoiseField::new(Perlin::new(&mut rng), 0.3, 2.0);
+    let simplex = CurlNoiseField::new(OpenSimplex::new(&mut rng), 0.3, 2.0);
+    for _ in 0..50 {
+        let p: Vector2d<f64> =
+            Vector2d::new(rng.gen_range(-20.0..20.0), rng.gen_range(-20.0..20.0));
+        assert!(perlin.divergence(p).abs() < 1e-3);
+        assert!(simplex.divergence(p).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn flow_field_leads_every_cell_to_the_goal() {
+    let grid = OccupancyGrid::from_rows(
+        &[
+            "..........",
+            ".########.",
+            ".#......#.",
+            ".#.####.#.",
+            ".#....#...",
+            ".######.#.",
+            "........#.",
+        ],
+        Vector2d::new(0.0, 0.0),
+        1.0,
+    )
+    .unwrap();
+    let goal = Vector2d::new(3.5, 4.5);
+    let flow = GridField::flow_field(&grid, &[goal]).unwrap();
+    let distances = grid.distance_map(&[goal]);
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            let Some(start) = distances[y * grid.width() + x] else {
+                assert_eq!(flow.get(x, y), Vector2d::new(0.0, 0.0));
+                continue;
+            };
+            // Following the arrows cell by cell reaches the goal in order of
+            // decreasing distance.
+            let (mut cx, mut cy, mut last) = (x, y, start);
+            for _ in 0..grid.width() * grid.height() {
+                let step: Vector2d<f64> = flow.get(cx, cy);
+                if step == Vector2d::new(0.0, 0.0) {
+                    break;
+                }
+                cx = (cx as f64 + step.x.round()) as usize;
+                cy = (cy as f64 + step.y.round()) as usize;
+                let next = distances[cy * grid.width() + cx].unwrap();
+                assert!(next < last);
+                last = next;
+            }
+            assert_eq!(last, 0.0);
+            assert_eq!(grid.cell_at(goal), Some((cx, cy)));
+        }
+    }
+
+    let mut broken = grid.clone();
+    broken.cell_size = 0.0;
+    assert!(GridField::flow_field(&broken, &[goal]).is_err());
+}
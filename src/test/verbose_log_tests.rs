@@ -0,0 +1,160 @@
+// -----------------------------------------
+//     Testing Verbose Vector Logging
+// -----------------------------------------
+#[allow(unused_imports)]
+use assert_approx_eq::*;
+use log::kv::{Key, Value, VisitSource};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, Once};
+use vectorlib::math::vector2d_module::Vector2d;
+use vectorlib::math::vector2d_verbose_module::{
+    set_verbose_config, VerboseConfig, VerbosePolicy, VerboseVector2d,
+};
+
+struct Captured {
+    target: String,
+    level: log::Level,
+    message: String,
+    fields: HashMap<String, String>,
+}
+
+struct CaptureLogger {
+    records: Mutex<Vec<Captured>>,
+}
+
+struct Fields<'a>(&'a mut HashMap<String, String>);
+
+impl<'kvs> VisitSource<'kvs> for Fields<'_> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), log::kv::Error> {
+        self.0.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+}
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        let mut fields = HashMap::new();
+        let _ = record.key_values().visit(&mut Fields(&mut fields));
+        self.records.lock().unwrap().push(Captured {
+            target: record.target().to_string(),
+            level: record.level(),
+            message: record.args().to_string(),
+            fields,
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger {
+    records: Mutex::new(Vec::new()),
+};
+static INSTALL: Once = Once::new();
+// The configuration is global, so these tests take turns.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn setup() -> MutexGuard<'static, ()> {
+    INSTALL.call_once(|| {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+    });
+    let guard = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    set_verbose_config(VerboseConfig::default());
+    guard
+}
+
+// Records about the vector with this (unique per test) x component.
+fn records_for(x: &str) -> Vec<Captured> {
+    let mut records = LOGGER.records.lock().unwrap();
+    let (matching, rest) = records
+        .drain(..)
+        .partition(|r| r.fields.get("value").is_some_and(|v| v.contains(x)));
+    *records = rest;
+    matching
+}
+
+#[test]
+fn display_logs_with_structured_fields() {
+    let _guard = setup();
+    let quiet = VerboseVector2d::new(Vector2d::new(101.0, 0.0), false);
+    quiet.display_if_verbose();
+    drop(quiet);
+    assert!(records_for("101.000").is_empty());
+
+    let loud = VerboseVector2d::new(Vector2d::new(102.0, 2.5), true);
+    loud.display_if_verbose();
+    let line = line!() - 1;
+    let records = records_for("102.000");
+    assert_eq!(records.len(), 1);
+    let record = &records[0];
+    assert_eq!(record.target, "vectorlib::verbose");
+    assert_eq!(record.level, log::Level::Debug);
+    assert_eq!(record.fields["event"], "display");
+    assert_eq!(record.fields["value"], "< 102.000, 2.500 >");
+    assert!(record.fields["address"].starts_with("0x"));
+    let site = format!("verbose_log_tests.rs:{}:", line);
+    assert!(record.fields["call_site"].contains(&site));
+    assert!(record
+        .message
+        .starts_with("Vector2d: < 102.000, 2.500 > (display) at "));
+}
+
+#[test]
+fn drop_reports_the_creation_site() {
+    let _guard = setup();
+    let vector = VerboseVector2d::new(Vector2d::new(103.0, 0.0), true);
+    let created = line!() - 1;
+    drop(vector);
+    let records = records_for("103.000");
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].fields["event"], "drop");
+    let site = format!("verbose_log_tests.rs:{}:", created);
+    assert!(records[0].fields["call_site"].contains(&site));
+
+    // Moving the vector out logs once; the empty wrapper stays silent.
+    let vector = VerboseVector2d::new(Vector2d::new(104.0, 0.0), true);
+    let inner = vector.into_inner();
+    assert_eq!(inner, Some(Vector2d::new(104.0, 0.0)));
+    let records = records_for("104.000");
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].fields["event"], "into_inner");
+}
+
+#[test]
+fn global_policy_target_and_level() {
+    let _guard = setup();
+    set_verbose_config(VerboseConfig {
+        policy: VerbosePolicy::Never,
+        ..VerboseConfig::default()
+    });
+    let loud = VerboseVector2d::new(Vector2d::new(105.0, 0.0), true);
+    loud.display_if_verbose();
+    drop(loud);
+    assert!(records_for("105.000").is_empty());
+
+    set_verbose_config(VerboseConfig {
+        target: "engine::vectors",
+        level: log::Level::Warn,
+        policy: VerbosePolicy::Always,
+    });
+    let mut quiet = VerboseVector2d::new(Vector2d::new(106.0, 0.0), false);
+    quiet.display_if_verbose();
+    let records = records_for("106.000");
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].target, "engine::vectors");
+    assert_eq!(records[0].level, log::Level::Warn);
+
+    // Per instance, the flag can be switched on and off.
+    set_verbose_config(VerboseConfig::default());
+    quiet.display_if_verbose();
+    assert!(records_for("106.000").is_empty());
+    quiet.set_verbose(true);
+    assert!(quiet.is_verbose());
+    quiet.display_if_verbose();
+    assert_eq!(records_for("106.000").len(), 1);
+    quiet.set_verbose(false);
+}